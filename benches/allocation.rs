@@ -0,0 +1,82 @@
+//! Benchmarks of allocations while running scripts
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+// counting allocations requires a global allocator
+#![allow(unsafe_code)]
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	sync::atomic::{AtomicUsize, Ordering},
+	time::Duration,
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use tinyscript::{Runtime, environment::DefaultEnvironment, execution::VM};
+
+const SAMPLES: usize = 100;
+const ITERATIONS: usize = 100;
+const DURATION: Duration = Duration::from_secs(5);
+
+/// Allocator counting the number of allocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		unsafe { System.alloc(layout) }
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		unsafe { System.dealloc(ptr, layout) };
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const SCRIPTS: [(&str, &str); 4] = [
+	("constants", "'this is a ' == 'test string';"),
+	("strings", "'this is a ' + 'test string' == 'this is a test string';"),
+	("template", "`{'this is a '}{'test string'}` == 'this is a test string';"),
+	("numbers", "!(5 - 4 > 3 * 2 == !nil);"),
+];
+
+fn allocation(c: &mut Criterion) {
+	let mut group = c.benchmark_group("allocation");
+	group
+		.measurement_time(DURATION)
+		.sample_size(SAMPLES);
+
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	// without optimization the scripts are not folded into a constant
+	runtime.set_optimization(false);
+	let mut vm = VM::default();
+	let mut stdout = std::io::sink();
+
+	for (name, script) in SCRIPTS {
+		let chunk = runtime.parse(script).unwrap();
+
+		let before = ALLOCATIONS.load(Ordering::Relaxed);
+		vm.run(&chunk, &mut env, &mut stdout).unwrap();
+		let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+		println!("allocation/{name}: {allocations} allocations per run");
+
+		group.bench_function(name, |b| {
+			b.iter(|| {
+				for _ in 1..=ITERATIONS {
+					vm.run(&chunk, &mut env, &mut stdout).unwrap();
+				}
+				std::hint::black_box(());
+			});
+		});
+	}
+}
+
+criterion_group!(benches, allocation);
+
+criterion_main!(benches);
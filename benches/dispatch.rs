@@ -0,0 +1,54 @@
+//! Benchmarks of the instruction dispatch
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use std::time::Duration;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use tinyscript::{Runtime, environment::DefaultEnvironment, execution::VM};
+
+const SAMPLES: usize = 100;
+const ITERATIONS: usize = 100;
+const DURATION: Duration = Duration::from_secs(5);
+
+/// Compares running the lowered instructions with decoding the bytecode while running.
+fn dispatch(c: &mut Criterion) {
+	let mut group = c.benchmark_group("dispatch");
+	group
+		.measurement_time(DURATION)
+		.sample_size(SAMPLES);
+
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	// without optimization the expression is not folded into a constant
+	runtime.set_optimization(false);
+	let mut vm = VM::default();
+	let mut stdout = std::io::sink();
+
+	let chunk = runtime
+		.parse("!(5 - 4 > 3 * 2 == !nil) ? (3 + 2) * (4 - 1) : 7 | 2 ^ 0xF0;")
+		.unwrap();
+	group.bench_function("bytecode", |b| {
+		b.iter(|| {
+			for _ in 1..=ITERATIONS {
+				vm.run_bytecode(&chunk, &mut env, &mut stdout)
+					.unwrap();
+			}
+			std::hint::black_box(());
+		});
+	});
+	group.bench_function("instructions", |b| {
+		b.iter(|| {
+			for _ in 1..=ITERATIONS {
+				vm.run(&chunk, &mut env, &mut stdout).unwrap();
+			}
+			std::hint::black_box(());
+		});
+	});
+}
+
+criterion_group!(benches, dispatch);
+
+criterion_main!(benches);
@@ -57,6 +57,72 @@ fn expression(c: &mut Criterion) {
 	});
 }
 
-criterion_group!(benches, expression);
+/// Compares accessing variables by name with accessing them by the handles of a linkage.
+fn variables(c: &mut Criterion) {
+	let mut group = c.benchmark_group("variables");
+	group
+		.measurement_time(DURATION)
+		.sample_size(SAMPLES);
+
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	let chunk = runtime
+		.parse("a := 3; b := 2; c := a * b; c = c + a - b; c += a > b ? a : b;")
+		.unwrap();
+	group.bench_function("named", |b| {
+		b.iter(|| {
+			for _ in 1..=ITERATIONS {
+				runtime.execute(&chunk, &mut env).unwrap();
+			}
+			std::hint::black_box(());
+		});
+	});
+
+	let linkage = chunk.link(&mut env);
+	group.bench_function("linked", |b| {
+		b.iter(|| {
+			for _ in 1..=ITERATIONS {
+				runtime
+					.execute_linked(&chunk, &linkage, &mut env)
+					.unwrap();
+			}
+			std::hint::black_box(());
+		});
+	});
+}
+
+/// Compares running scripts from source with and without the compile cache.
+fn run(c: &mut Criterion) {
+	let mut group = c.benchmark_group("run");
+	group
+		.measurement_time(DURATION)
+		.sample_size(SAMPLES);
+
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	let script = "a := 3; b := 2; c := a * b; c = c + a - b; c += a > b ? a : b;";
+
+	group.bench_function("cached", |b| {
+		b.iter(|| {
+			for _ in 1..=ITERATIONS {
+				runtime.run(script, &mut env).unwrap();
+			}
+			std::hint::black_box(());
+		});
+	});
+
+	runtime.set_cache_capacity(0);
+	group.bench_function("uncached", |b| {
+		b.iter(|| {
+			for _ in 1..=ITERATIONS {
+				runtime.run(script, &mut env).unwrap();
+			}
+			std::hint::black_box(());
+		});
+	});
+}
+
+criterion_group!(benches, expression, variables, run);
 
 criterion_main!(benches);
@@ -0,0 +1,78 @@
+//! `tinyscript` formatter example
+//! Copyright © 2026 Stephan Kunz
+//!
+//! Formats the given script files in place, or stdin to stdout without files.
+//! With `--check` nothing is written, files which are not formatted are listed instead.
+
+use std::{
+	fs,
+	io::{Read, stdin},
+	process::ExitCode,
+};
+
+use tinyscript::compilation::Formatter;
+
+fn main() -> ExitCode {
+	let mut check = false;
+	let mut files = Vec::new();
+	for arg in std::env::args().skip(1) {
+		if arg == "--check" {
+			check = true;
+		} else {
+			files.push(arg);
+		}
+	}
+
+	let fmt = Formatter::new();
+	if files.is_empty() {
+		let mut source = String::new();
+		if let Err(error) = stdin().read_to_string(&mut source) {
+			eprintln!("stdin: {error}");
+			return ExitCode::FAILURE;
+		}
+		return match fmt.format(&source) {
+			Ok(formatted) if check && formatted != source => {
+				println!("stdin is not formatted");
+				ExitCode::FAILURE
+			}
+			Ok(_) if check => ExitCode::SUCCESS,
+			Ok(formatted) => {
+				print!("{formatted}");
+				ExitCode::SUCCESS
+			}
+			Err(error) => {
+				eprintln!("stdin: {error}");
+				ExitCode::FAILURE
+			}
+		};
+	}
+
+	let mut code = ExitCode::SUCCESS;
+	for file in &files {
+		let formatted = fs::read_to_string(file)
+			.map_err(|error| error.to_string())
+			.and_then(|source| {
+				fmt.format(&source)
+					.map(|formatted| (formatted != source).then_some(formatted))
+					.map_err(|error| error.to_string())
+			});
+		match formatted {
+			Ok(None) => {}
+			Ok(Some(_)) if check => {
+				println!("{file} is not formatted");
+				code = ExitCode::FAILURE;
+			}
+			Ok(Some(formatted)) => {
+				if let Err(error) = fs::write(file, formatted) {
+					eprintln!("{file}: {error}");
+					code = ExitCode::FAILURE;
+				}
+			}
+			Err(error) => {
+				eprintln!("{file}: {error}");
+				code = ExitCode::FAILURE;
+			}
+		}
+	}
+	code
+}
@@ -0,0 +1,357 @@
+// Copyright © 2026 Stephan Kunz
+//! [`Analyzer`] answering the questions of an editor about a script.
+//!
+//! The enum values, the environment variables and the host functions are not known from a script,
+//! they are configured by the `initializationOptions` of the client:
+//! ```json
+//! {
+//!     "enums": { "Red": 1, "Green": 2 },
+//!     "variables": { "speed": "float", "name": "string" },
+//!     "functions": { "sensor": "float" }
+//! }
+//! ```
+//! Without configured variables or functions the types are not checked,
+//! as every environment variable would be reported as not defined.
+
+use std::collections::BTreeMap;
+
+use lsp_types::{
+	CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, Hover, HoverContents, MarkupContent, MarkupKind,
+	SemanticToken, SemanticTokenType,
+};
+use serde_json::Value;
+use tinyscript::{
+	ScriptingValue,
+	compilation::{CompilationError, Lexer, Linter, Parser, Schema, Stmt, Token, TokenKind, Type, TypeChecker, TypeError},
+	execution::ExecutionResult,
+};
+
+use crate::lines::{Lines, to_u32, utf16_len};
+
+/// The token types of the semantic tokens, their index is used in [`Analyzer::semantic_tokens`].
+pub const TOKEN_TYPES: [SemanticTokenType; 7] = [
+	SemanticTokenType::KEYWORD,
+	SemanticTokenType::VARIABLE,
+	SemanticTokenType::FUNCTION,
+	SemanticTokenType::ENUM_MEMBER,
+	SemanticTokenType::NUMBER,
+	SemanticTokenType::STRING,
+	SemanticTokenType::OPERATOR,
+];
+
+/// Name of the diagnostics source.
+const SOURCE: &str = "tinyscript";
+
+/// Host functions are not executed, so they accept any arguments.
+#[allow(clippy::unnecessary_wraps)]
+const fn host_function(_args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Nil())
+}
+
+/// Parse the name of a configured type.
+fn parse_type(value: &Value) -> Type {
+	match value.as_str() {
+		Some("nil") => Type::Nil,
+		Some("bool") => Type::Bool,
+		Some("int") => Type::Int,
+		Some("float") => Type::Float,
+		Some("string") => Type::String,
+		_ => Type::Any,
+	}
+}
+
+/// The entries of a configured object.
+fn entries<'a>(options: Option<&'a Value>, key: &str) -> impl Iterator<Item = (&'a String, &'a Value)> {
+	options
+		.and_then(|options| options.get(key))
+		.and_then(Value::as_object)
+		.into_iter()
+		.flatten()
+}
+
+// region:		--- Analyzer
+/// Analyzer implementation.
+pub struct Analyzer {
+	enums: BTreeMap<String, i8>,
+	/// The configured environment variables.
+	variables: BTreeMap<String, Type>,
+	/// Parser with the host functions registered.
+	parser: Parser,
+	/// The types, if any variable or function is configured.
+	schema: Option<Schema>,
+	linter: Linter,
+}
+
+impl Analyzer {
+	/// Create an Analyzer from the `initializationOptions` of the client.
+	pub fn new(options: Option<&Value>) -> Self {
+		let mut enums = BTreeMap::new();
+		let mut linter = Linter::new();
+		for (name, value) in entries(options, "enums") {
+			if let Some(value) = value
+				.as_i64()
+				.and_then(|value| i8::try_from(value).ok())
+			{
+				enums.insert(name.clone(), value);
+				linter.register_enum(name);
+			}
+		}
+
+		let mut variables = BTreeMap::new();
+		let mut parser = Parser::new();
+		let mut schema = Schema::new();
+		let mut typed = false;
+		for (name, value) in entries(options, "variables") {
+			let var_type = parse_type(value);
+			variables.insert(name.clone(), var_type);
+			schema.register_variable(name, var_type);
+			linter.register_variable(name);
+			typed = true;
+		}
+		for (name, value) in entries(options, "functions") {
+			// a name already used by the library is kept
+			let _ = parser
+				.library_mut()
+				.register_function(name, 0..=u8::MAX, host_function);
+			schema.register_function(name, parse_type(value));
+			typed = true;
+		}
+
+		Self {
+			enums,
+			variables,
+			parser,
+			schema: typed.then_some(schema),
+			linter,
+		}
+	}
+
+	/// The tokens of a script up to the first lexing error.
+	fn tokens(&self, text: &str) -> Vec<Token> {
+		Lexer::new(&self.enums, text)
+			.map_while(Result::ok)
+			.collect()
+	}
+
+	/// The types of the variables defined by a script.
+	fn script_variables(&mut self, text: &str) -> BTreeMap<String, Type> {
+		let Ok(stmts) = self.parser.parse_ast(&self.enums, text) else {
+			return BTreeMap::new();
+		};
+		let empty = Schema::new();
+		let schema = self.schema.as_ref().unwrap_or(&empty);
+		TypeChecker::new(schema, self.parser.library())
+			.variables(&stmts)
+			.into_iter()
+			.map(|(name, var_type)| (name.to_string(), var_type))
+			.collect()
+	}
+
+	/// The compilation error, the type errors and the lint warnings of a script.
+	pub fn diagnostics(&mut self, text: &str) -> Vec<Diagnostic> {
+		let lines = Lines::new(text);
+		let mut diagnostics = Vec::new();
+		if let Err(error) = self.parser.parse(&self.enums, text) {
+			diagnostics.push(error_diagnostic(&lines, &error));
+		}
+		let Ok(stmts) = self.parser.parse_ast(&self.enums, text) else {
+			return diagnostics;
+		};
+		diagnostics.extend(self.type_errors(&stmts).map(|error| {
+			diagnostic(
+				lines.range(error.span.start, error.span.end),
+				DiagnosticSeverity::ERROR,
+				error.to_string(),
+			)
+		}));
+		// enums are linted as variables, so a variable shadowing an enum value is found
+		if let Ok(warnings) = self.linter.lint(text) {
+			diagnostics.extend(warnings.into_iter().map(|warning| {
+				diagnostic(
+					lines.range(warning.span.start, warning.span.end),
+					DiagnosticSeverity::WARNING,
+					warning.to_string(),
+				)
+			}));
+		}
+		diagnostics
+	}
+
+	/// The type errors, if the types are checked.
+	fn type_errors(&self, stmts: &[Stmt]) -> impl Iterator<Item = TypeError> {
+		self.schema
+			.as_ref()
+			.map(|schema| TypeChecker::new(schema, self.parser.library()).check(stmts))
+			.unwrap_or_default()
+			.into_iter()
+	}
+
+	/// Describe the enum value, function, constant or variable at a byte offset.
+	pub fn hover(&mut self, text: &str, offset: usize) -> Option<Hover> {
+		let tokens = self.tokens(text);
+		let index = tokens
+			.iter()
+			.position(|token| token.offset() <= offset && offset <= token.end())?;
+		let token = &tokens[index];
+		let name = token.origin();
+		let description = match token.kind() {
+			TokenKind::Enum => format!("`{name}`: enum value {}", self.enums.get(name)?),
+			TokenKind::Ident if is_call(&tokens, index) => {
+				self.parser.library().function(name)?;
+				self.schema
+					.as_ref()
+					.and_then(|schema| schema.function(name))
+					.map_or_else(
+						|| format!("`{name}()`: function"),
+						|result| format!("`{name}()`: function returning {result}"),
+					)
+			}
+			TokenKind::Ident => {
+				if let Some(value) = self.parser.library().constant(name) {
+					format!("`{name}`: {} constant {value}", Type::from(value))
+				} else if let Some(var_type) = self.variables.get(name) {
+					format!("`{name}`: {var_type} environment variable")
+				} else {
+					let var_type = self.script_variables(text).get(name).copied()?;
+					format!("`{name}`: {var_type} script variable")
+				}
+			}
+			_ => return None,
+		};
+		let lines = Lines::new(text);
+		Some(Hover {
+			contents: HoverContents::Markup(MarkupContent {
+				kind: MarkupKind::Markdown,
+				value: description,
+			}),
+			range: Some(lines.range(token.offset(), token.end())),
+		})
+	}
+
+	/// The enum values, variables, constants and functions known in a script, ordered by their name.
+	pub fn completion(&mut self, text: &str) -> Vec<CompletionItem> {
+		let mut items = BTreeMap::new();
+		let mut add = |label: &str, kind, detail: String| {
+			items
+				.entry(label.to_owned())
+				.or_insert_with(|| CompletionItem {
+					label: label.to_owned(),
+					kind: Some(kind),
+					detail: Some(detail),
+					..CompletionItem::default()
+				});
+		};
+		for (name, value) in &self.enums {
+			add(name, CompletionItemKind::ENUM_MEMBER, format!("enum value {value}"));
+		}
+		for (name, var_type) in &self.variables {
+			add(name, CompletionItemKind::VARIABLE, format!("{var_type} environment variable"));
+		}
+		for (name, var_type) in self.script_variables(text) {
+			add(&name, CompletionItemKind::VARIABLE, format!("{var_type} script variable"));
+		}
+		let library = self.parser.library();
+		for name in library.constant_names() {
+			if let Some(value) = library.constant(name) {
+				add(name, CompletionItemKind::CONSTANT, format!("{} constant", Type::from(value)));
+			}
+		}
+		for name in library.function_names() {
+			let result = self
+				.schema
+				.as_ref()
+				.and_then(|schema| schema.function(name));
+			let detail = result.map_or_else(|| "function".into(), |result| format!("function returning {result}"));
+			add(name, CompletionItemKind::FUNCTION, detail);
+		}
+		items.into_values().collect()
+	}
+
+	/// The semantic tokens of a script, encoded relative to each other.
+	pub fn semantic_tokens(&self, text: &str) -> Vec<SemanticToken> {
+		let lines = Lines::new(text);
+		let tokens = self.tokens(text);
+		let mut encoded = Vec::with_capacity(tokens.len());
+		let (mut line, mut start) = (0, 0);
+		for (index, token) in tokens.iter().enumerate() {
+			let Some(token_type) = token_type(&tokens, index) else {
+				continue;
+			};
+			// a template may span several lines, which is split into one token per line
+			let mut offset = token.offset();
+			for part in text[token.offset()..token.end()].split_inclusive('\n') {
+				let position = lines.position(offset);
+				let length = utf16_len(part.trim_end_matches(['\r', '\n']));
+				offset += part.len();
+				if length == 0 {
+					continue;
+				}
+				let delta_line = position.line - line;
+				let delta_start = if delta_line == 0 {
+					position.character - start
+				} else {
+					position.character
+				};
+				(line, start) = (position.line, position.character);
+				encoded.push(SemanticToken {
+					delta_line,
+					delta_start,
+					length: to_u32(length),
+					token_type,
+					token_modifiers_bitset: 0,
+				});
+			}
+		}
+		encoded
+	}
+}
+// endregion:	--- Analyzer
+
+/// Whether the identifier at the index is the name of a called function.
+fn is_call(tokens: &[Token], index: usize) -> bool {
+	tokens
+		.get(index + 1)
+		.is_some_and(|next| next.kind() == TokenKind::LeftParen)
+}
+
+/// The index of the semantic token type in [`TOKEN_TYPES`], punctuation is not highlighted.
+fn token_type(tokens: &[Token], index: usize) -> Option<u32> {
+	let token_type = match tokens[index].kind() {
+		TokenKind::As | TokenKind::Nil | TokenKind::True | TokenKind::False | TokenKind::Print | TokenKind::Yield => {
+			SemanticTokenType::KEYWORD
+		}
+		TokenKind::Ident if is_call(tokens, index) => SemanticTokenType::FUNCTION,
+		TokenKind::Ident => SemanticTokenType::VARIABLE,
+		TokenKind::Enum => SemanticTokenType::ENUM_MEMBER,
+		TokenKind::FloatNumber | TokenKind::HexNumber | TokenKind::IntNumber => SemanticTokenType::NUMBER,
+		TokenKind::String | TokenKind::Template => SemanticTokenType::STRING,
+		TokenKind::None | TokenKind::Semicolon | TokenKind::Comma | TokenKind::LeftParen | TokenKind::RightParen => {
+			return None;
+		}
+		_ => SemanticTokenType::OPERATOR,
+	};
+	TOKEN_TYPES
+		.iter()
+		.position(|known| *known == token_type)
+		.map(to_u32)
+}
+
+/// A diagnostic of this server.
+fn diagnostic(range: lsp_types::Range, severity: DiagnosticSeverity, message: String) -> Diagnostic {
+	Diagnostic {
+		range,
+		severity: Some(severity),
+		source: Some(SOURCE.into()),
+		message,
+		..Diagnostic::default()
+	}
+}
+
+/// A compilation error only knows its line, so the whole line is marked.
+fn error_diagnostic(lines: &Lines, error: &CompilationError) -> Diagnostic {
+	let range = match error {
+		CompilationError::TypeCheck { source } => lines.range(source.span.start, source.span.end),
+		error => lines.line(error.line().unwrap_or(1)),
+	};
+	diagnostic(range, DiagnosticSeverity::ERROR, error.to_string())
+}
@@ -0,0 +1,76 @@
+// Copyright © 2026 Stephan Kunz
+//! [`Lines`] converting between byte offsets and the positions of the language server protocol,
+//! which count lines from 0 and characters in UTF-16 code units.
+
+use lsp_types::{Position, Range};
+
+/// Index of the line starts of a document.
+pub struct Lines<'a> {
+	text: &'a str,
+	/// Byte offsets of the line starts.
+	starts: Vec<usize>,
+}
+
+impl<'a> Lines<'a> {
+	/// Create the index for a document.
+	pub fn new(text: &'a str) -> Self {
+		let starts = core::iter::once(0)
+			.chain(
+				text.match_indices('\n')
+					.map(|(index, _)| index + 1),
+			)
+			.collect();
+		Self { text, starts }
+	}
+
+	/// The position of a byte offset.
+	pub fn position(&self, offset: usize) -> Position {
+		let offset = offset.min(self.text.len());
+		let line = self
+			.starts
+			.partition_point(|start| *start <= offset)
+			- 1;
+		let character = utf16_len(&self.text[self.starts[line]..offset]);
+		Position::new(to_u32(line), to_u32(character))
+	}
+
+	/// The byte offset of a position, positions behind a line end at the line end.
+	pub fn offset(&self, position: Position) -> usize {
+		let Some(start) = self.starts.get(position.line as usize).copied() else {
+			return self.text.len();
+		};
+		let mut units = 0;
+		for (index, c) in self.text[start..].char_indices() {
+			if c == '\n' || units >= position.character as usize {
+				return start + index;
+			}
+			units += c.len_utf16();
+		}
+		self.text.len()
+	}
+
+	/// The range of two byte offsets.
+	pub fn range(&self, start: usize, end: usize) -> Range {
+		Range::new(self.position(start), self.position(end))
+	}
+
+	/// The range of a whole line, the lines of the script count from 1.
+	pub fn line(&self, line: usize) -> Range {
+		let index = line.saturating_sub(1).min(self.starts.len() - 1);
+		let start = self.starts[index];
+		let end = self.text[start..]
+			.find('\n')
+			.map_or(self.text.len(), |end| start + end);
+		self.range(start, end)
+	}
+}
+
+/// Length of a string in UTF-16 code units.
+pub fn utf16_len(text: &str) -> usize {
+	text.chars().map(char::len_utf16).sum()
+}
+
+/// Positions beyond `u32::MAX` are not representable in the protocol.
+pub fn to_u32(value: usize) -> u32 {
+	u32::try_from(value).unwrap_or(u32::MAX)
+}
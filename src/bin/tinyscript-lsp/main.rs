@@ -0,0 +1,200 @@
+//! `tinyscript` language server
+//! Copyright © 2026 Stephan Kunz
+//!
+//! Provides diagnostics, hover, completion and semantic tokens for scripts to editors,
+//! speaking the language server protocol over stdin and stdout.
+//! Enum values, environment variables and host functions are configured by the `initializationOptions`,
+//! see [`Analyzer`](analysis::Analyzer).
+
+mod analysis;
+mod lines;
+
+use std::{collections::HashMap, error::Error, process::ExitCode};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+	CompletionOptions, CompletionParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+	HoverParams, HoverProviderCapability, InitializeParams, PublishDiagnosticsParams, SemanticTokens,
+	SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, SemanticTokensParams,
+	SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Uri,
+	notification::{
+		DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics,
+	},
+	request::{Completion, HoverRequest, Request as _, SemanticTokensFullRequest},
+};
+
+use analysis::{Analyzer, TOKEN_TYPES};
+use lines::Lines;
+
+type ServerResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+fn capabilities() -> ServerCapabilities {
+	ServerCapabilities {
+		text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+		hover_provider: Some(HoverProviderCapability::Simple(true)),
+		completion_provider: Some(CompletionOptions::default()),
+		semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+			SemanticTokensOptions {
+				legend: SemanticTokensLegend {
+					token_types: TOKEN_TYPES.to_vec(),
+					token_modifiers: Vec::new(),
+				},
+				full: Some(SemanticTokensFullOptions::Bool(true)),
+				..SemanticTokensOptions::default()
+			},
+		)),
+		..ServerCapabilities::default()
+	}
+}
+
+// region:		--- Server
+/// The open documents and their analysis.
+struct Server {
+	connection: Connection,
+	analyzer: Analyzer,
+	documents: HashMap<Uri, String>,
+}
+
+impl Server {
+	fn run(mut self) -> ServerResult<()> {
+		while let Ok(message) = self.connection.receiver.recv() {
+			match message {
+				Message::Request(request) => {
+					if self.connection.handle_shutdown(&request)? {
+						return Ok(());
+					}
+					let response = self.request(request);
+					self.connection
+						.sender
+						.send(Message::Response(response))?;
+				}
+				Message::Notification(notification) => {
+					let method = notification.method.clone();
+					// a malformed notification does not end the session
+					if let Err(error) = self.notification(notification) {
+						eprintln!("tinyscript-lsp: {method}: {error}");
+					}
+				}
+				Message::Response(_) => {}
+			}
+		}
+		Ok(())
+	}
+
+	fn request(&mut self, request: Request) -> Response {
+		let id = request.id.clone();
+		let result = match request.method.as_str() {
+			HoverRequest::METHOD => serde_json::from_value::<HoverParams>(request.params).and_then(|params| {
+				let position = params.text_document_position_params;
+				let hover = self
+					.documents
+					.get(&position.text_document.uri)
+					.and_then(|text| {
+						let offset = Lines::new(text).offset(position.position);
+						self.analyzer.hover(text, offset)
+					});
+				serde_json::to_value(hover)
+			}),
+			Completion::METHOD => serde_json::from_value::<CompletionParams>(request.params).and_then(|params| {
+				let uri = params.text_document_position.text_document.uri;
+				let items = self
+					.documents
+					.get(&uri)
+					.map(|text| self.analyzer.completion(text))
+					.unwrap_or_default();
+				serde_json::to_value(items)
+			}),
+			SemanticTokensFullRequest::METHOD => {
+				serde_json::from_value::<SemanticTokensParams>(request.params).and_then(|params| {
+					let data = self
+						.documents
+						.get(&params.text_document.uri)
+						.map(|text| self.analyzer.semantic_tokens(text))
+						.unwrap_or_default();
+					serde_json::to_value(SemanticTokens { result_id: None, data })
+				})
+			}
+			method => {
+				return Response::new_err(id, ErrorCode::MethodNotFound as i32, format!("unsupported request {method}"));
+			}
+		};
+		match result {
+			Ok(result) => Response {
+				id,
+				result: Some(result),
+				error: None,
+			},
+			Err(error) => Response::new_err(id, ErrorCode::InvalidParams as i32, error.to_string()),
+		}
+	}
+
+	fn notification(&mut self, notification: Notification) -> ServerResult<()> {
+		match notification.method.as_str() {
+			DidOpenTextDocument::METHOD => {
+				let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+				let document = params.text_document;
+				self.documents
+					.insert(document.uri.clone(), document.text);
+				self.publish(document.uri)
+			}
+			DidChangeTextDocument::METHOD => {
+				let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+				// with full synchronization the last change is the whole document
+				if let Some(change) = params.content_changes.into_iter().last() {
+					let uri = params.text_document.uri;
+					self.documents.insert(uri.clone(), change.text);
+					self.publish(uri)?;
+				}
+				Ok(())
+			}
+			DidCloseTextDocument::METHOD => {
+				let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+				let uri = params.text_document.uri;
+				self.documents.remove(&uri);
+				self.publish(uri)
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Send the diagnostics of a document, which are empty for a closed one.
+	fn publish(&mut self, uri: Uri) -> ServerResult<()> {
+		let diagnostics = self
+			.documents
+			.get(&uri)
+			.map(|text| self.analyzer.diagnostics(text))
+			.unwrap_or_default();
+		let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+		self.connection
+			.sender
+			.send(Message::Notification(Notification::new(
+				PublishDiagnostics::METHOD.into(),
+				params,
+			)))?;
+		Ok(())
+	}
+}
+// endregion:	--- Server
+
+fn serve(connection: Connection) -> ServerResult<()> {
+	let capabilities = serde_json::to_value(capabilities())?;
+	let params: InitializeParams = serde_json::from_value(connection.initialize(capabilities)?)?;
+	let server = Server {
+		connection,
+		analyzer: Analyzer::new(params.initialization_options.as_ref()),
+		documents: HashMap::new(),
+	};
+	server.run()
+}
+
+fn main() -> ExitCode {
+	let (connection, io_threads) = Connection::stdio();
+	let result = serve(connection).and_then(|()| Ok(io_threads.join()?));
+	match result {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(error) => {
+			eprintln!("tinyscript-lsp: {error}");
+			ExitCode::FAILURE
+		}
+	}
+}
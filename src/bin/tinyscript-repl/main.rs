@@ -0,0 +1,286 @@
+//! `tinyscript` `REPL`
+//! Copyright © 2026 Stephan Kunz
+//!
+//! Reads scripts with line editing and a history, statements which are not finished
+//! are continued on the next line. Commands starting with `:` inspect and control the session.
+
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use rustyline::{DefaultEditor, error::ReadlineError};
+use tinyscript::{
+	Chunk, Error, Runtime, ScriptingValue,
+	compilation::{CompilationError, TokenKind},
+	environment::DefaultEnvironment,
+	execution::{Debugger, Disassembler},
+};
+
+const PROMPT: &str = "> ";
+const CONTINUATION: &str = ". ";
+const HISTORY: &str = ".tinyscript_history";
+const HELP: &str = "commands:
+  :help            show this help
+  :env             show the variables
+  :dis             disassemble the last script
+  :enum <Name=value>...
+                   register enum values
+  :load <file>     run a script file
+  :reset           forget the variables, the enum values and the last script
+  :quit            quit, as does 'Ctrl+D'
+debugging commands:
+  :debug <script>  start debugging a script, paused before its first instruction
+  :step            execute the next instruction
+  :continue        run until the next breakpoint
+  :break <line>    add a breakpoint
+  :delete <line>   remove a breakpoint
+  :abort           abort debugging
+an empty line finishes an unfinished statement, 'Ctrl+C' discards it";
+
+/// Whether the source ended before the statement was finished.
+fn unfinished(error: &Error) -> bool {
+	let Error::Compilation { source } = error else {
+		return false;
+	};
+	let end = TokenKind::None.to_string();
+	match source {
+		CompilationError::UnterminatedComment { .. } | CompilationError::UnterminatedString { .. } => true,
+		CompilationError::ExpressionExpected { token, .. } => **token == *end,
+		CompilationError::TokenExpected { found, .. } => **found == *end,
+		_ => false,
+	}
+}
+
+/// The file keeping the history between sessions.
+fn history_file() -> Option<PathBuf> {
+	std::env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY))
+}
+
+// region:		--- Repl
+/// State of a session.
+#[derive(Default)]
+struct Repl {
+	runtime: Runtime,
+	env: DefaultEnvironment,
+	/// The last successfully compiled script.
+	chunk: Option<Chunk>,
+	debugger: Debugger,
+	/// The script being debugged.
+	debugged: Option<Chunk>,
+}
+
+impl Repl {
+	fn print_stdout(&mut self) {
+		print!("{}", String::from_utf8_lossy(self.runtime.stdout()));
+		self.runtime.clear();
+	}
+
+	/// Whether the statements in the source are not finished yet.
+	fn unfinished(&self, source: &str) -> bool {
+		self.runtime
+			.parse_ast(source)
+			.is_err_and(|error| unfinished(&error))
+	}
+
+	/// Compile and execute a script, the result is shown unless it is `nil`.
+	fn run(&mut self, source: &str) {
+		let chunk = match self.runtime.parse(source) {
+			Ok(chunk) => chunk,
+			Err(error) => {
+				println!("{error}");
+				return;
+			}
+		};
+		let result = self.runtime.execute(&chunk, &mut self.env);
+		self.chunk = Some(chunk);
+		self.print_stdout();
+		match result {
+			Ok(ScriptingValue::Nil()) => {}
+			Ok(value) => println!("{value}"),
+			Err(error) => println!("{error}"),
+		}
+	}
+
+	fn print_state(&mut self, result: Result<Option<ScriptingValue>, Error>) {
+		match result {
+			Ok(Some(value)) => {
+				println!("finished with: {value}");
+				self.debugged = None;
+			}
+			Ok(None) => {
+				if let Some(paused) = self.debugger.paused() {
+					let stack: Vec<String> = paused
+						.stack()
+						.iter()
+						.map(ToString::to_string)
+						.collect();
+					println!(
+						"paused at line {} ip {} stack [{}]",
+						paused.line(),
+						paused.ip(),
+						stack.join(", ")
+					);
+				}
+			}
+			Err(error) => {
+				println!("{error}");
+				self.debugged = None;
+			}
+		}
+	}
+
+	fn register_enums(&mut self, argument: &str) {
+		for definition in argument.split_whitespace() {
+			let registered = definition
+				.split_once('=')
+				.and_then(|(name, value)| Some((name, value.parse::<i8>().ok()?)))
+				.map(|(name, value)| self.runtime.register_enum_tuple(name, value));
+			match registered {
+				Some(Ok(())) => {}
+				Some(Err(error)) => println!("{error}"),
+				None => println!("invalid enum value: {definition}, expected <Name=value>"),
+			}
+		}
+	}
+
+	/// Execute a command, returns `false` to quit.
+	fn command(&mut self, command: &str) -> bool {
+		let (command, argument) = command
+			.split_once(' ')
+			.map_or((command, ""), |(command, argument)| (command, argument.trim()));
+		match command {
+			":quit" => return false,
+			":env" => {
+				for (name, value) in self.env.iter() {
+					println!("{name} = {value}");
+				}
+			}
+			":dis" => match &self.chunk {
+				Some(chunk) => print!("{}", Disassembler::new(chunk)),
+				None => println!("no script compiled yet"),
+			},
+			":enum" => self.register_enums(argument),
+			":load" => match fs::read_to_string(argument) {
+				Ok(source) => self.run(&source),
+				Err(error) => println!("{argument}: {error}"),
+			},
+			":reset" => *self = Self::default(),
+			":debug" => match self.runtime.parse(argument) {
+				Ok(chunk) => {
+					self.debugger.abort();
+					let result = self
+						.runtime
+						.debug_step(&chunk, &mut self.debugger, &mut self.env);
+					self.debugged = Some(chunk);
+					self.print_state(result);
+				}
+				Err(error) => println!("{error}"),
+			},
+			":step" | ":continue" => {
+				if let Some(chunk) = self.debugged.take() {
+					let result = if command == ":step" {
+						self.runtime
+							.debug_step(&chunk, &mut self.debugger, &mut self.env)
+					} else {
+						self.runtime
+							.debug_continue(&chunk, &mut self.debugger, &mut self.env)
+					};
+					self.debugged = Some(chunk);
+					self.print_stdout();
+					self.print_state(result);
+				} else {
+					println!("nothing to debug");
+				}
+			}
+			":break" | ":delete" => match argument.parse::<usize>() {
+				Ok(line) => {
+					if command == ":break" {
+						self.debugger.add_breakpoint(line);
+					} else {
+						self.debugger.remove_breakpoint(line);
+					}
+					let lines: Vec<String> = self
+						.debugger
+						.breakpoints()
+						.map(|line| line.to_string())
+						.collect();
+					println!("breakpoints: [{}]", lines.join(", "));
+				}
+				Err(_) => println!("invalid line: {argument}"),
+			},
+			":abort" => {
+				self.debugger.abort();
+				self.debugged = None;
+			}
+			_ => println!("{HELP}"),
+		}
+		true
+	}
+}
+// endregion:	--- Repl
+
+fn main() -> ExitCode {
+	let mut editor = match DefaultEditor::new() {
+		Ok(editor) => editor,
+		Err(error) => {
+			eprintln!("could not create the line editor: {error}");
+			return ExitCode::FAILURE;
+		}
+	};
+	let history = history_file();
+	if let Some(history) = &history {
+		// there is no history at the first start
+		let _ = editor.load_history(history);
+	}
+
+	let mut repl = Repl::default();
+	let mut input = String::new();
+	println!("Quit with ':quit' or 'Ctrl+D', ':help' shows the commands");
+	loop {
+		let prompt = if input.is_empty() { PROMPT } else { CONTINUATION };
+		let line = match editor.readline(prompt) {
+			Ok(line) => line,
+			Err(ReadlineError::Interrupted) => {
+				input.clear();
+				continue;
+			}
+			Err(ReadlineError::Eof) => {
+				println!("bye");
+				break;
+			}
+			Err(error) => {
+				eprintln!("could not read input: {error}");
+				break;
+			}
+		};
+
+		if input.is_empty() {
+			let command = line.trim();
+			if command.is_empty() {
+				continue;
+			}
+			if command.starts_with(':') {
+				let _ = editor.add_history_entry(command);
+				if repl.command(command) {
+					continue;
+				}
+				break;
+			}
+		}
+		input.push_str(&line);
+		input.push('\n');
+		if !line.trim().is_empty() && repl.unfinished(&input) {
+			continue;
+		}
+		// without trailing line breaks the end of the input is on the last line with code
+		let source = input.trim_end();
+		let _ = editor.add_history_entry(source);
+		repl.run(source);
+		input.clear();
+	}
+
+	if let Some(history) = &history
+		&& let Err(error) = editor.save_history(history)
+	{
+		eprintln!("could not save the history: {error}");
+	}
+	ExitCode::SUCCESS
+}
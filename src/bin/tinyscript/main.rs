@@ -0,0 +1,262 @@
+//! `tinyscript` command line runner
+//! Copyright © 2026 Stephan Kunz
+//!
+//! Executes a script file or stdin with preloaded variables and enum values,
+//! then prints the output of the script, its result and the final environment.
+//! Compilation and execution errors are printed with their location and end the runner with a failure.
+
+use std::{
+	collections::BTreeMap,
+	ffi::OsStr,
+	fs,
+	io::{Read, stdin},
+	path::Path,
+	process::ExitCode,
+};
+
+use tinyscript::{
+	DefaultEnvironment, Environment, Error, Runtime, ScriptingValue,
+	compilation::{Lexer, TokenKind},
+};
+
+const USAGE: &str = "usage: tinyscript [options] [file]
+Executes the script file, or stdin without a file or with '-'.
+options:
+  --vars <file>      define the variables of a JSON or TOML file
+  --set <key=value>  define a variable, the value is a literal like 1, -0x0f, 1.5, true, nil or 'text',
+                     any other value is taken as text
+  --enums <file>     register the enum values of a JSON or TOML file
+  --help             show this help";
+
+/// Reasons to stop the runner.
+enum Failure {
+	/// Wrong arguments, reported with exit code 2.
+	Usage(String),
+	/// Anything else, reported with exit code 1.
+	Error(String),
+}
+
+/// What to run.
+#[derive(Default)]
+struct Options {
+	/// The script file, `None` for stdin.
+	file: Option<String>,
+	variables: Vec<(String, ScriptingValue)>,
+	enums: Vec<(String, i8)>,
+}
+
+// region:		--- loading
+/// A value in a script literal, strings are quoted.
+fn literal(value: &ScriptingValue) -> String {
+	match value {
+		ScriptingValue::String(text) => format!("'{text}'"),
+		value => value.to_string(),
+	}
+}
+
+fn from_json(key: &str, value: serde_json::Value) -> Result<ScriptingValue, String> {
+	match value {
+		serde_json::Value::Null => Ok(ScriptingValue::Nil()),
+		serde_json::Value::Bool(value) => Ok(value.into()),
+		serde_json::Value::Number(number) => number
+			.as_i64()
+			.map(ScriptingValue::from)
+			.or_else(|| number.as_f64().map(ScriptingValue::from))
+			.ok_or_else(|| format!("the number of {key} is out of range")),
+		serde_json::Value::String(text) => Ok(text.into()),
+		serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+			Err(format!("the value of {key} is not nil, a boolean, a number or a string"))
+		}
+	}
+}
+
+fn from_toml(key: &str, value: toml::Value) -> Result<ScriptingValue, String> {
+	match value {
+		toml::Value::Boolean(value) => Ok(value.into()),
+		toml::Value::Integer(value) => Ok(value.into()),
+		toml::Value::Float(value) => Ok(value.into()),
+		toml::Value::String(text) => Ok(text.into()),
+		_ => Err(format!("the value of {key} is not a boolean, a number or a string")),
+	}
+}
+
+/// Load the key value pairs of a JSON or TOML file, the format is given by the extension.
+fn load(path: &str) -> Result<Vec<(String, ScriptingValue)>, Failure> {
+	let extension = Path::new(path)
+		.extension()
+		.and_then(OsStr::to_str)
+		.map(str::to_ascii_lowercase);
+	let toml = match extension.as_deref() {
+		Some("toml") => true,
+		Some("json") => false,
+		_ => return Err(Failure::Usage(format!("{path}: only .json and .toml files are supported"))),
+	};
+	let text = fs::read_to_string(path).map_err(|error| Failure::Error(format!("{path}: {error}")))?;
+	let values = if toml {
+		text.parse::<toml::Table>()
+			.map_err(|error| error.to_string())
+			.and_then(|table| {
+				table
+					.into_iter()
+					.map(|(key, value)| from_toml(&key, value).map(|value| (key, value)))
+					.collect()
+			})
+	} else {
+		serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&text)
+			.map_err(|error| error.to_string())
+			.and_then(|object| {
+				object
+					.into_iter()
+					.map(|(key, value)| from_json(&key, value).map(|value| (key, value)))
+					.collect()
+			})
+	};
+	values.map_err(|error| Failure::Error(format!("{path}: {error}")))
+}
+
+/// The value of `--set`, which is taken as text if it is not a single literal.
+/// Numbers may be negative, nothing is evaluated.
+fn parse_value(value: &str) -> ScriptingValue {
+	let enums = BTreeMap::new();
+	let (sign, literal) = value
+		.strip_prefix('-')
+		.map_or(("", value), |literal| ("-", literal));
+	let tokens: Result<Vec<_>, _> = Lexer::new(&enums, literal).collect();
+	let parsed = match tokens.as_deref() {
+		Ok([token]) => match (token.kind(), sign) {
+			(TokenKind::IntNumber, _) => format!("{sign}{}", token.origin())
+				.parse::<i64>()
+				.ok()
+				.map(ScriptingValue::from),
+			(TokenKind::HexNumber, _) => i64::from_str_radix(&format!("{sign}{}", &token.origin()[2..]), 16)
+				.ok()
+				.map(ScriptingValue::from),
+			(TokenKind::FloatNumber, _) => format!("{sign}{}", token.origin())
+				.parse::<f64>()
+				.ok()
+				.map(ScriptingValue::from),
+			(TokenKind::True, "") => Some(true.into()),
+			(TokenKind::False, "") => Some(false.into()),
+			(TokenKind::Nil, "") => Some(ScriptingValue::Nil()),
+			(TokenKind::String, "") => Some(token.origin().into()),
+			_ => None,
+		},
+		_ => None,
+	};
+	parsed.unwrap_or_else(|| value.into())
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, Failure> {
+	let mut options = Options::default();
+	while let Some(arg) = args.next() {
+		let mut value = |name: &str| {
+			args.next()
+				.ok_or_else(|| Failure::Usage(format!("{name} expects a value")))
+		};
+		match arg.as_str() {
+			"--help" => return Err(Failure::Usage(String::new())),
+			"--vars" => options.variables.extend(load(&value("--vars")?)?),
+			"--set" => {
+				let definition = value("--set")?;
+				let Some((key, value)) = definition.split_once('=') else {
+					return Err(Failure::Usage(format!("--set expects <key=value>, got {definition}")));
+				};
+				options
+					.variables
+					.push((key.trim().to_owned(), parse_value(value.trim())));
+			}
+			"--enums" => {
+				let path = value("--enums")?;
+				for (key, value) in load(&path)? {
+					let discriminant = match value {
+						ScriptingValue::Int64(value) => i8::try_from(value).ok(),
+						_ => None,
+					}
+					.ok_or_else(|| {
+						Failure::Error(format!("{path}: the enum value {key} is not an integer in -128..=127"))
+					})?;
+					options.enums.push((key, discriminant));
+				}
+			}
+			option if option.starts_with("--") => return Err(Failure::Usage(format!("unknown option {option}"))),
+			file => {
+				if options.file.is_some() {
+					return Err(Failure::Usage(format!("only one script can be executed, got {file}")));
+				}
+				if file != "-" {
+					options.file = Some(file.to_owned());
+				}
+			}
+		}
+	}
+	Ok(options)
+}
+// endregion:	--- loading
+
+/// An error with the line of the source it refers to.
+fn located(name: &str, source: &str, line: Option<usize>, error: &Error) -> String {
+	match line.and_then(|line| Some((line, source.lines().nth(line.checked_sub(1)?)?))) {
+		Some((line, code)) => format!("{name}:{line}: {error}\n{line:>4} | {code}"),
+		None => format!("{name}: {error}"),
+	}
+}
+
+fn run(options: Options) -> Result<(), Failure> {
+	let (name, source) = if let Some(file) = &options.file {
+		let source = fs::read_to_string(file).map_err(|error| Failure::Error(format!("{file}: {error}")))?;
+		(file.as_str(), source)
+	} else {
+		let mut source = String::new();
+		stdin()
+			.read_to_string(&mut source)
+			.map_err(|error| Failure::Error(format!("stdin: {error}")))?;
+		("stdin", source)
+	};
+
+	let mut runtime = Runtime::default();
+	for (key, value) in &options.enums {
+		runtime
+			.register_enum_tuple(key, *value)
+			.map_err(|error| Failure::Error(error.to_string()))?;
+	}
+	let mut env = DefaultEnvironment::default();
+	for (key, value) in options.variables {
+		env.define_env(&key, value)
+			.map_err(|error| Failure::Error(format!("{key}: {error}")))?;
+	}
+
+	let chunk = runtime.parse(&source).map_err(|error| {
+		let line = match &error {
+			Error::Compilation { source } => source.line(),
+			_ => None,
+		};
+		Failure::Error(located(name, &source, line, &error))
+	})?;
+	let result = runtime.execute(&chunk, &mut env);
+	// the output of the script is shown even if it fails
+	print!("{}", String::from_utf8_lossy(runtime.stdout()));
+	let value = result.map_err(|error| Failure::Error(located(name, &source, runtime.last_line(&chunk), &error)))?;
+	println!("result: {}", literal(&value));
+	for (key, value) in env.iter() {
+		println!("{key} = {}", literal(value));
+	}
+	Ok(())
+}
+
+fn main() -> ExitCode {
+	match parse_args(std::env::args().skip(1)).and_then(run) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(Failure::Usage(message)) if message.is_empty() => {
+			println!("{USAGE}");
+			ExitCode::SUCCESS
+		}
+		Err(Failure::Usage(message)) => {
+			eprintln!("{message}\n{USAGE}");
+			ExitCode::from(2)
+		}
+		Err(Failure::Error(message)) => {
+			eprintln!("{message}");
+			ExitCode::FAILURE
+		}
+	}
+}
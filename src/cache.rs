@@ -0,0 +1,117 @@
+// Copyright © 2026 Stephan Kunz
+//! A bounded least recently used cache for compiled [`Chunk`]s.
+
+// region:		--- modules
+use alloc::{collections::btree_map::BTreeMap, sync::Arc};
+
+use crate::{ConstString, execution::Chunk};
+// endregion:	--- modules
+
+/// Default number of [`Chunk`]s kept in a [`ChunkCache`].
+pub const DEFAULT_CAPACITY: usize = 32;
+
+// region:		--- ChunkCache
+/// A bounded least recently used cache of compiled [`Chunk`]s keyed by their source text.
+///
+/// The cache is intended for a small number of short scripts,
+/// so finding the least recently used entry on eviction is a linear search.
+#[derive(Debug)]
+pub struct ChunkCache {
+	/// Maximum number of entries, `0` disables the cache.
+	capacity: usize,
+	/// The chunks together with the tick of their last usage.
+	entries: BTreeMap<ConstString, (Arc<Chunk>, u64)>,
+	/// Counter to order the usages.
+	tick: u64,
+}
+
+impl Default for ChunkCache {
+	fn default() -> Self {
+		Self::new(DEFAULT_CAPACITY)
+	}
+}
+
+impl ChunkCache {
+	/// Create a cache holding at most `capacity` [`Chunk`]s.
+	#[must_use]
+	pub const fn new(capacity: usize) -> Self {
+		Self {
+			capacity,
+			entries: BTreeMap::new(),
+			tick: 0,
+		}
+	}
+
+	/// Maximum number of cached [`Chunk`]s.
+	#[must_use]
+	pub const fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Change the maximum number of cached [`Chunk`]s, evicting the least recently used ones if necessary.
+	pub fn set_capacity(&mut self, capacity: usize) {
+		self.capacity = capacity;
+		while self.entries.len() > capacity {
+			self.evict();
+		}
+	}
+
+	/// Number of cached [`Chunk`]s.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Check whether the cache is empty.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Check whether the [`Chunk`] for `source` is cached, without marking it as used.
+	#[must_use]
+	pub fn contains(&self, source: &str) -> bool {
+		self.entries.contains_key(source)
+	}
+
+	/// Get the cached [`Chunk`] for `source` and mark it as most recently used.
+	pub fn get(&mut self, source: &str) -> Option<Arc<Chunk>> {
+		self.tick += 1;
+		let tick = self.tick;
+		self.entries.get_mut(source).map(|(chunk, used)| {
+			*used = tick;
+			chunk.clone()
+		})
+	}
+
+	/// Insert the [`Chunk`] for `source`, evicting the least recently used one if the cache is full.
+	pub fn insert(&mut self, source: &str, chunk: Arc<Chunk>) {
+		if self.capacity == 0 {
+			return;
+		}
+		if !self.entries.contains_key(source) && self.entries.len() >= self.capacity {
+			self.evict();
+		}
+		self.tick += 1;
+		self.entries
+			.insert(source.into(), (chunk, self.tick));
+	}
+
+	/// Remove all cached [`Chunk`]s.
+	pub fn clear(&mut self) {
+		self.entries.clear();
+	}
+
+	/// Remove the least recently used entry.
+	fn evict(&mut self) {
+		let oldest = self
+			.entries
+			.iter()
+			.min_by_key(|(_, (_, used))| *used)
+			.map(|(source, _)| source.clone());
+		if let Some(source) = oldest {
+			self.entries.remove(&source);
+		}
+	}
+}
+// endregion:	--- ChunkCache
@@ -0,0 +1,509 @@
+// Copyright © 2026 Stephan Kunz
+//! [`Assembler`] creating a [`Chunk`] from the textual format of the [`Disassembler`].
+//!
+//! Each line contains at most one instruction, `//` starts a comment:
+//! ```no-test
+//! line        → label* ( offset? source_line? instruction )? comment? ;
+//! label       → IDENTIFIER ":" ;
+//! offset      → DIGIT+ ;
+//! source_line → DIGIT+ | "|" ;
+//! instruction → OP_NAME operands ;
+//! operands    → ( pos? LITERAL ) | COUNT | ( pos? IDENTIFIER "(" COUNT ")" ) | target ;
+//! target      → offset | IDENTIFIER ;
+//! LITERAL     → "nil" | "true" | "false" | INTNUMBER | FLOATNUMBER | STRING ;
+//! STRING      → "\"" <any char, escaped as by Rust's `Debug`> "\"" ;
+//! ```
+//! With two numbers in front of the instruction the first is the offset and the second the source line,
+//! a single number is the source line. A `|` repeats the source line of the previous instruction,
+//! without a source line the line within the text is used.
+//!
+//! A jump target is either a label or the offset written in front of an instruction,
+//! so the output of the [`Disassembler`] can be edited and assembled again.
+//! The position of a constant is kept if given, functions get their position on first use.
+//!
+//! The last instruction must be `OP_RETURN`. The stack depth is followed along all paths through the instructions,
+//! an instruction taking values from an empty stack or exceeding the stack size is rejected.
+//!
+//! [`Disassembler`]: crate::execution::Disassembler
+
+// region:		--- modules
+use alloc::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec,
+};
+
+use crate::{
+	ConstString,
+	execution::{Chunk, Instruction, STACK_SIZE, op_code::OpCode},
+	library::Library,
+	scripting_value::ScriptingValue,
+};
+
+use super::error::{CompilationError, CompilationResult};
+// endregion:	--- modules
+
+// region:		--- helpers
+/// A jump target to resolve after all lines are read.
+enum Target<'a> {
+	Label(&'a str),
+	Offset(usize),
+}
+
+/// A jump waiting for its target.
+struct Jump<'a> {
+	/// Index of the jump instruction.
+	index: usize,
+	target: Target<'a>,
+	/// Position(line) in the text.
+	pos: usize,
+}
+
+/// Split off the next token, a string literal is a single token.
+/// Returns `None` at the end of the line or at the start of a comment.
+fn next_token<'a>(line: &mut &'a str) -> Option<&'a str> {
+	let rest = line.trim_start();
+	if rest.is_empty() || rest.starts_with("//") {
+		*line = "";
+		return None;
+	}
+	let end = if rest.starts_with('"') {
+		let mut escaped = false;
+		rest.char_indices()
+			.skip(1)
+			.find(|(_, c)| {
+				let end = !escaped && *c == '"';
+				escaped = !escaped && *c == '\\';
+				end
+			})
+			.map_or(rest.len(), |(i, _)| i + 1)
+	} else {
+		rest.find(char::is_whitespace)
+			.unwrap_or(rest.len())
+	};
+	let (token, remainder) = rest.split_at(end);
+	*line = remainder;
+	Some(token)
+}
+
+fn is_identifier(token: &str) -> bool {
+	let mut chars = token.chars();
+	chars
+		.next()
+		.is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+		&& chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Decode a string literal as written by [`str`]'s `Debug` implementation.
+fn unescape(token: &str) -> Option<String> {
+	let content = token.strip_prefix('"')?.strip_suffix('"')?;
+	let mut result = String::with_capacity(content.len());
+	let mut chars = content.chars();
+	while let Some(c) = chars.next() {
+		if c != '\\' {
+			result.push(c);
+			continue;
+		}
+		let c = match chars.next()? {
+			'n' => '\n',
+			'r' => '\r',
+			't' => '\t',
+			'0' => '\0',
+			'u' => {
+				let rest = chars.as_str().strip_prefix('{')?;
+				let (hex, rest) = rest.split_once('}')?;
+				chars = rest.chars();
+				char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+			}
+			c @ ('\\' | '"' | '\'') => c,
+			_ => return None,
+		};
+		result.push(c);
+	}
+	Some(result)
+}
+
+fn literal(token: &str) -> Option<ScriptingValue> {
+	match token {
+		"nil" => Some(ScriptingValue::nil()),
+		"true" => Some(ScriptingValue::Boolean(true)),
+		"false" => Some(ScriptingValue::Boolean(false)),
+		_ if token.starts_with('"') => Some(ScriptingValue::String(unescape(token)?.into())),
+		_ => token.parse::<i64>().map_or_else(
+			|_| {
+				token
+					.parse::<f64>()
+					.ok()
+					.map(ScriptingValue::Float64)
+			},
+			|i| Some(ScriptingValue::Int64(i)),
+		),
+	}
+}
+
+/// Whether two values are identical, floats are compared bitwise.
+fn identical(a: &ScriptingValue, b: &ScriptingValue) -> bool {
+	match (a, b) {
+		(ScriptingValue::Nil(), ScriptingValue::Nil()) => true,
+		(ScriptingValue::Boolean(a), ScriptingValue::Boolean(b)) => a == b,
+		(ScriptingValue::Float64(a), ScriptingValue::Float64(b)) => a.to_bits() == b.to_bits(),
+		(ScriptingValue::Int64(a), ScriptingValue::Int64(b)) => a == b,
+		(ScriptingValue::String(a), ScriptingValue::String(b)) => a == b,
+		_ => false,
+	}
+}
+// endregion:	--- helpers
+
+// region:		--- Assembler
+/// Assembler implementation.
+#[derive(Debug)]
+pub struct Assembler {
+	/// The callable native functions.
+	library: Arc<Library>,
+}
+
+impl Default for Assembler {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Assembler {
+	/// Create an Assembler with the default [`Library`].
+	#[must_use]
+	pub fn new() -> Self {
+		Self::with_library(Arc::new(Library::default()))
+	}
+
+	/// Create an Assembler with the given [`Library`].
+	#[must_use]
+	pub const fn with_library(library: Arc<Library>) -> Self {
+		Self { library }
+	}
+
+	/// Create a bytecode [`Chunk`] from assembly text.
+	/// # Errors
+	/// - if the text is not valid assembly
+	/// - if a called function is not in the [`Library`]
+	pub fn assemble(&self, text: &str) -> CompilationResult<Chunk> {
+		let mut state = State::default();
+		for (index, line) in text.lines().enumerate() {
+			state.line(&self.library, line, index + 1)?;
+		}
+		state.finish()
+	}
+}
+// endregion:	--- Assembler
+
+// region:		--- State
+/// The state while assembling a text.
+#[derive(Default)]
+struct State<'a> {
+	chunk: Chunk,
+	instructions: Vec<Instruction>,
+	lines: Vec<usize>,
+	constants: Vec<Option<ScriptingValue>>,
+	labels: BTreeMap<&'a str, usize>,
+	offsets: BTreeMap<usize, usize>,
+	jumps: Vec<Jump<'a>>,
+	/// Position(line) in the text of each instruction.
+	positions: Vec<usize>,
+}
+
+impl<'a> State<'a> {
+	/// Assemble a single line of text.
+	fn line(&mut self, library: &Library, mut line: &'a str, pos: usize) -> CompilationResult<()> {
+		let invalid = |token: &str| CompilationError::InvalidOperand {
+			token: token.into(),
+			pos,
+		};
+		let mut token = next_token(&mut line);
+		while let Some(label) = token.and_then(|token| token.strip_suffix(':')) {
+			if !is_identifier(label) {
+				return Err(invalid(label));
+			}
+			if self
+				.labels
+				.insert(label, self.instructions.len())
+				.is_some()
+			{
+				return Err(CompilationError::DuplicateLabel { name: label.into(), pos });
+			}
+			token = next_token(&mut line);
+		}
+		let Some(mut token) = token else {
+			return Ok(());
+		};
+
+		let mut numbers = Vec::new();
+		while !token.starts_with("OP_") {
+			if numbers.len() == 2 {
+				return Err(invalid(token));
+			}
+			let number = if token == "|" {
+				None
+			} else {
+				Some(
+					token
+						.parse::<usize>()
+						.map_err(|_| invalid(token))?,
+				)
+			};
+			numbers.push(number);
+			token = next_token(&mut line).ok_or_else(|| invalid(token))?;
+		}
+		let source_line = match numbers.as_slice() {
+			[Some(offset), line] => {
+				self.offsets
+					.insert(*offset, self.instructions.len());
+				*line
+			}
+			[line] | [_, line] => *line,
+			_ => Some(pos),
+		}
+		.or_else(|| self.lines.last().copied())
+		.unwrap_or(pos);
+
+		let op_code = (0..=u8::MAX)
+			.filter_map(OpCode::from_byte)
+			.find(|op_code| *op_code != OpCode::None && op_code.name() == token)
+			.ok_or_else(|| CompilationError::UnknownOpCode { name: token.into(), pos })?;
+		let operands: Vec<&'a str> = core::iter::from_fn(|| next_token(&mut line)).collect();
+		let instruction = self.instruction(library, op_code, &operands, pos)?;
+		self.instructions.push(instruction);
+		self.lines.push(source_line);
+		self.positions.push(pos);
+		Ok(())
+	}
+
+	/// Create the instruction for an [`OpCode`] from its operands.
+	fn instruction(
+		&mut self,
+		library: &Library,
+		op_code: OpCode,
+		operands: &[&'a str],
+		pos: usize,
+	) -> CompilationResult<Instruction> {
+		let invalid = |token: &str| CompilationError::InvalidOperand {
+			token: token.into(),
+			pos,
+		};
+		let byte = |token: &str| token.parse::<u8>().map_err(|_| invalid(token));
+		let instruction = match (op_code, operands) {
+			(OpCode::Constant | OpCode::DefineExternal | OpCode::GetExternal | OpCode::SetExternal, [value]) => {
+				let value = literal(value).ok_or_else(|| invalid(value))?;
+				self.constant(op_code, None, value, pos)?
+			}
+			(OpCode::Constant | OpCode::DefineExternal | OpCode::GetExternal | OpCode::SetExternal, [index, value]) => {
+				let index = byte(index)?;
+				let value = literal(value).ok_or_else(|| invalid(value))?;
+				self.constant(op_code, Some(index), value, pos)?
+			}
+			(OpCode::Concat, [count]) => Instruction::Concat(byte(count)?),
+			(OpCode::Call, [name, count] | [_, name, count]) => {
+				let count = count
+					.strip_prefix('(')
+					.and_then(|count| count.strip_suffix(')'))
+					.unwrap_or(count);
+				let count = byte(count)?;
+				let function = library
+					.function(name)
+					.ok_or_else(|| CompilationError::FunctionNotFound {
+						name: (*name).into(),
+						pos,
+					})?;
+				if !function.accepts(usize::from(count)) {
+					return Err(CompilationError::WrongArgumentCount {
+						name: (*name).into(),
+						found: usize::from(count),
+						pos,
+					});
+				}
+				let function = self.chunk.add_function(function)?;
+				Instruction::Call { function, count }
+			}
+			(OpCode::Jmp | OpCode::JmpIfFalse | OpCode::JmpIfTrue, [target]) => {
+				let target = if is_identifier(target) {
+					Target::Label(target)
+				} else {
+					Target::Offset(target.parse().map_err(|_| invalid(target))?)
+				};
+				self.jumps.push(Jump {
+					index: self.instructions.len(),
+					target,
+					pos,
+				});
+				match op_code {
+					OpCode::Jmp => Instruction::Jmp(0),
+					OpCode::JmpIfFalse => Instruction::JmpIfFalse(0),
+					_ => Instruction::JmpIfTrue(0),
+				}
+			}
+			(_, [operand, ..]) => return Err(invalid(operand)),
+			(OpCode::None, []) => {
+				return Err(CompilationError::UnknownOpCode {
+					name: op_code.name().into(),
+					pos,
+				});
+			}
+			(OpCode::Nil, []) => Instruction::Nil,
+			(OpCode::True, []) => Instruction::True,
+			(OpCode::False, []) => Instruction::False,
+			(OpCode::Pop, []) => Instruction::Pop,
+			(OpCode::Equal, []) => Instruction::Equal,
+			(OpCode::Greater, []) => Instruction::Greater,
+			(OpCode::Less, []) => Instruction::Less,
+			(OpCode::Add, []) => Instruction::Add,
+			(OpCode::Subtract, []) => Instruction::Subtract,
+			(OpCode::Multiply, []) => Instruction::Multiply,
+			(OpCode::Divide, []) => Instruction::Divide,
+			(OpCode::BitwiseNot, []) => Instruction::BitwiseNot,
+			(OpCode::BitwiseAnd, []) => Instruction::BitwiseAnd,
+			(OpCode::BitwiseOr, []) => Instruction::BitwiseOr,
+			(OpCode::BitwiseXor, []) => Instruction::BitwiseXor,
+			(OpCode::Not, []) => Instruction::Not,
+			(OpCode::Negate, []) => Instruction::Negate,
+			(OpCode::Return, []) => Instruction::Return,
+			(OpCode::Yield, []) => Instruction::Yield,
+			#[cfg(feature = "std")]
+			(OpCode::Print, []) => Instruction::Print,
+			(_, []) => {
+				return Err(CompilationError::OperandExpected {
+					name: op_code.name().into(),
+					pos,
+				});
+			}
+		};
+		Ok(instruction)
+	}
+
+	/// Store a constant, at the given position or appended.
+	fn constant(
+		&mut self,
+		op_code: OpCode,
+		index: Option<u8>,
+		value: ScriptingValue,
+		pos: usize,
+	) -> CompilationResult<Instruction> {
+		let index = if let Some(index) = index {
+			let slot = usize::from(index);
+			if self.constants.len() <= slot {
+				self.constants.resize(slot + 1, None);
+			}
+			match &self.constants[slot] {
+				Some(stored) if !identical(stored, &value) => {
+					return Err(CompilationError::InvalidOperand {
+						token: index.to_string().into(),
+						pos,
+					});
+				}
+				_ => self.constants[slot] = Some(value),
+			}
+			index
+		} else {
+			self.constants.push(Some(value));
+			u8::try_from(self.constants.len() - 1).map_err(|_| CompilationError::ConstantStorageOverflow)?
+		};
+		let instruction = match op_code {
+			OpCode::Constant => Instruction::Constant(index),
+			OpCode::DefineExternal => Instruction::DefineExternal(index),
+			OpCode::GetExternal => Instruction::GetExternal(index),
+			_ => Instruction::SetExternal(index),
+		};
+		Ok(instruction)
+	}
+
+	/// Resolve the jump targets and create the [`Chunk`].
+	fn finish(mut self) -> CompilationResult<Chunk> {
+		if let Some(last) = self.instructions.last()
+			&& *last != Instruction::Return
+		{
+			return Err(CompilationError::ReturnExpected {
+				pos: self.positions.last().copied().unwrap_or_default(),
+			});
+		}
+
+		let mut offsets = Vec::with_capacity(self.instructions.len());
+		let mut offset = 0;
+		for instruction in &self.instructions {
+			offsets.push(offset);
+			offset += instruction.size();
+		}
+		for jump in &self.jumps {
+			let (index, target): (_, ConstString) = match jump.target {
+				Target::Label(label) => {
+					let index = self
+						.labels
+						.get(label)
+						.ok_or_else(|| CompilationError::UnknownLabel {
+							name: label.into(),
+							pos: jump.pos,
+						})?;
+					(Some(index), label.into())
+				}
+				Target::Offset(offset) => (self.offsets.get(&offset), offset.to_string().into()),
+			};
+			// the target must be an instruction and jump addresses are limited to 16 bit
+			let index = index
+				.copied()
+				.filter(|index| {
+					offsets
+						.get(*index)
+						.is_some_and(|offset| u16::try_from(*offset).is_ok())
+				})
+				.and_then(|index| u16::try_from(index).ok())
+				.ok_or(CompilationError::InvalidJumpTarget { target, pos: jump.pos })?;
+			self.instructions[jump.index] = self.instructions[jump.index].with_target(index);
+		}
+		self.check_stack()?;
+
+		for value in self.constants {
+			self.chunk
+				.add_constant(value.unwrap_or_else(ScriptingValue::nil))?;
+		}
+		self.chunk
+			.replace_instructions(self.instructions, self.lines);
+		self.chunk.finalize();
+		Ok(self.chunk)
+	}
+
+	/// Follow all paths through the instructions with resolved jump targets
+	/// and check that the stack neither runs empty nor overflows.
+	/// As the depth is limited, each instruction is visited at most once per possible depth.
+	fn check_stack(&self) -> CompilationResult<()> {
+		let mut visited = BTreeSet::new();
+		let mut pending: Vec<(usize, usize)> = Vec::from([(0, 0)]);
+		while let Some((index, depth)) = pending.pop() {
+			let Some(instruction) = self.instructions.get(index).copied() else {
+				continue;
+			};
+			if !visited.insert((index, depth)) {
+				continue;
+			}
+			let name = || instruction.op_code().name().into();
+			let pos = self.positions[index];
+			let (taken, put) = instruction.stack_effect();
+			let depth = match depth.checked_sub(taken) {
+				Some(depth) => depth,
+				None if instruction == Instruction::Return => 0,
+				#[cfg(feature = "std")]
+				None if instruction == Instruction::Print => 0,
+				None => return Err(CompilationError::StackUnderflow { name: name(), pos }),
+			} + put;
+			if depth > STACK_SIZE {
+				return Err(CompilationError::StackOverflow { name: name(), pos });
+			}
+			match (instruction, instruction.target()) {
+				(Instruction::Return, _) => {}
+				(Instruction::Jmp(_), Some(target)) => pending.push((usize::from(target), depth)),
+				(_, target) => {
+					pending.push((index + 1, depth));
+					pending.extend(target.map(|target| (usize::from(target), depth)));
+				}
+			}
+		}
+		Ok(())
+	}
+}
+// endregion:	--- State
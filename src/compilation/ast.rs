@@ -0,0 +1,336 @@
+// Copyright © 2026 Stephan Kunz
+//! Abstract syntax tree created by [`Parser::parse_ast`](crate::compilation::Parser::parse_ast).
+//!
+//! A script is a list of [`Stmt`]s, each containing [`Expr`]s.
+//! Every node carries the [`Span`] of its source code, so tools can relate it to the script.
+//! The tree is purely syntactic: functions, library constants and type names are resolved
+//! by the [`Compiler`](crate::compilation::Compiler) when creating a [`Chunk`](crate::execution::Chunk).
+
+// region:		--- modules
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::ConstString;
+// endregion:	--- modules
+
+// region:		--- Span
+/// Location of a node in the source code.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+	/// Byte offset of the start.
+	pub start: usize,
+	/// Byte offset behind the end.
+	pub end: usize,
+	/// Line of the start.
+	pub line: usize,
+	/// Line of the end.
+	pub end_line: usize,
+}
+
+impl Span {
+	/// The span from the start of `self` to the end of `other`.
+	#[must_use]
+	pub const fn to(self, other: Self) -> Self {
+		Self {
+			start: self.start,
+			end: other.end,
+			line: self.line,
+			end_line: other.end_line,
+		}
+	}
+}
+// endregion:	--- Span
+
+// region:		--- operators
+/// Prefix operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOp {
+	/// `!`
+	Not,
+	/// `-`
+	Negate,
+	/// `+`
+	Plus,
+	/// `~`
+	BitwiseNot,
+}
+
+impl UnaryOp {
+	/// The operator as written in a script.
+	#[must_use]
+	pub const fn symbol(self) -> &'static str {
+		match self {
+			Self::Not => "!",
+			Self::Negate => "-",
+			Self::Plus => "+",
+			Self::BitwiseNot => "~",
+		}
+	}
+}
+
+/// Infix operators evaluating both operands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+	/// `+`
+	Add,
+	/// `-`
+	Subtract,
+	/// `*`
+	Multiply,
+	/// `/`
+	Divide,
+	/// `==`
+	Equal,
+	/// `!=`
+	NotEqual,
+	/// `>`
+	Greater,
+	/// `>=`
+	GreaterEqual,
+	/// `<`
+	Less,
+	/// `<=`
+	LessEqual,
+	/// `&`
+	BitwiseAnd,
+	/// `|`
+	BitwiseOr,
+	/// `^`
+	BitwiseXor,
+}
+
+impl BinaryOp {
+	/// The operator as written in a script.
+	#[must_use]
+	pub const fn symbol(self) -> &'static str {
+		match self {
+			Self::Add => "+",
+			Self::Subtract => "-",
+			Self::Multiply => "*",
+			Self::Divide => "/",
+			Self::Equal => "==",
+			Self::NotEqual => "!=",
+			Self::Greater => ">",
+			Self::GreaterEqual => ">=",
+			Self::Less => "<",
+			Self::LessEqual => "<=",
+			Self::BitwiseAnd => "&",
+			Self::BitwiseOr => "|",
+			Self::BitwiseXor => "^",
+		}
+	}
+}
+
+/// Short circuiting infix operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogicalOp {
+	/// `&&`
+	And,
+	/// `||`
+	Or,
+}
+
+impl LogicalOp {
+	/// The operator as written in a script.
+	#[must_use]
+	pub const fn symbol(self) -> &'static str {
+		match self {
+			Self::And => "&&",
+			Self::Or => "||",
+		}
+	}
+}
+
+/// Assignment operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssignOp {
+	/// `:=`
+	Define,
+	/// `=`
+	Assign,
+	/// `+=`
+	Add,
+	/// `-=`
+	Subtract,
+	/// `*=`
+	Multiply,
+	/// `/=`
+	Divide,
+}
+
+impl AssignOp {
+	/// The operator as written in a script.
+	#[must_use]
+	pub const fn symbol(self) -> &'static str {
+		match self {
+			Self::Define => ":=",
+			Self::Assign => "=",
+			Self::Add => "+=",
+			Self::Subtract => "-=",
+			Self::Multiply => "*=",
+			Self::Divide => "/=",
+		}
+	}
+}
+// endregion:	--- operators
+
+// region:		--- Expr
+/// A literal value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+	/// `nil`
+	Nil,
+	/// `true` or `false`
+	Boolean(bool),
+	/// Decimal or hexadecimal integer number.
+	Int(i64),
+	/// Floating point number.
+	Float(f64),
+	/// String without the quotes.
+	String(ConstString),
+}
+
+/// A piece of a template string.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemplatePart {
+	/// Literal text with `{{` and `}}` already replaced.
+	Text(ConstString),
+	/// An interpolated `{expression}`.
+	Expr(Expr),
+}
+
+/// The kinds of expressions.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExprKind {
+	/// A literal value.
+	Literal(Literal),
+	/// A registered enum value.
+	Enum {
+		/// Name of the value.
+		name: ConstString,
+		/// The discriminant.
+		value: i8,
+	},
+	/// A variable or a library constant.
+	Variable(ConstString),
+	/// A template string.
+	Template(Vec<TemplatePart>),
+	/// An expression in parentheses.
+	Grouping(Box<Expr>),
+	/// A prefix operation.
+	Unary {
+		/// The operator.
+		op: UnaryOp,
+		/// The operand.
+		operand: Box<Expr>,
+	},
+	/// An infix operation.
+	Binary {
+		/// The operator.
+		op: BinaryOp,
+		/// Location of the operator.
+		operator: Span,
+		/// Left hand side.
+		left: Box<Expr>,
+		/// Right hand side.
+		right: Box<Expr>,
+	},
+	/// A short circuiting operation.
+	Logical {
+		/// The operator.
+		op: LogicalOp,
+		/// Location of the operator.
+		operator: Span,
+		/// Left hand side.
+		left: Box<Expr>,
+		/// Right hand side, only evaluated if necessary.
+		right: Box<Expr>,
+	},
+	/// `condition ? then : otherwise`
+	Ternary {
+		/// The condition.
+		condition: Box<Expr>,
+		/// Result if the condition is true.
+		then: Box<Expr>,
+		/// Result if the condition is false.
+		otherwise: Box<Expr>,
+		/// Location of the `?`.
+		question: Span,
+		/// Location of the `:`.
+		colon: Span,
+	},
+	/// Definition of or assignment to a variable.
+	Assign {
+		/// The operator.
+		op: AssignOp,
+		/// Name of the variable.
+		name: ConstString,
+		/// The assigned value.
+		value: Box<Expr>,
+	},
+	/// Call of a native function.
+	Call {
+		/// Name of the function.
+		name: ConstString,
+		/// The arguments.
+		args: Vec<Expr>,
+		/// Locations of the `,` between the arguments.
+		commas: Vec<Span>,
+	},
+	/// Type conversion `value as type`.
+	Cast {
+		/// The converted value.
+		value: Box<Expr>,
+		/// Location of the `as`.
+		operator: Span,
+		/// Name of the target type.
+		type_name: ConstString,
+	},
+}
+
+/// An expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expr {
+	/// What kind of expression.
+	pub kind: ExprKind,
+	/// Location in the source code.
+	pub span: Span,
+}
+
+impl Expr {
+	/// Create an expression.
+	#[must_use]
+	pub const fn new(kind: ExprKind, span: Span) -> Self {
+		Self { kind, span }
+	}
+}
+// endregion:	--- Expr
+
+// region:		--- Stmt
+/// The kinds of statements.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StmtKind {
+	/// An expression, its value stays on the stack.
+	Expression(Expr),
+	/// `print expression;`
+	Print(Expr),
+	/// `yield;`
+	Yield,
+}
+
+/// A statement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stmt {
+	/// What kind of statement.
+	pub kind: StmtKind,
+	/// Location in the source code including the terminating `;`.
+	pub span: Span,
+}
+
+impl Stmt {
+	/// Create a statement.
+	#[must_use]
+	pub const fn new(kind: StmtKind, span: Span) -> Self {
+		Self { kind, span }
+	}
+}
+// endregion:	--- Stmt
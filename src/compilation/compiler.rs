@@ -0,0 +1,390 @@
+// Copyright © 2026 Stephan Kunz
+//! [`Compiler`] creating a bytecode [`Chunk`] from the abstract syntax tree.
+//!
+//! The [`Stmt`]s are compiled in order, the value of an expression statement stays on the stack
+//! and the [`Chunk`] ends with a return of the topmost value.
+//! Functions, library constants and type names are resolved against the [`Library`].
+//! With a [`Schema`] the statements are checked by the [`TypeChecker`] first.
+//! Each instruction gets the source line of the token it originates from.
+
+// region:		--- modules
+use alloc::sync::Arc;
+
+use crate::{
+	ConstString,
+	execution::{Chunk, STACK_SIZE, op_code::OpCode},
+	library::{Library, NativeFunction, conversion_function},
+	scripting_value::ScriptingValue,
+};
+
+use super::{
+	ast::{AssignOp, BinaryOp, Expr, ExprKind, Literal, LogicalOp, Stmt, StmtKind, TemplatePart, UnaryOp},
+	error::{CompilationError, CompilationResult},
+	optimizer,
+	type_checker::{Schema, TypeChecker},
+};
+// endregion:	--- modules
+
+// region:		--- Compiler
+/// Compiler implementation.
+#[derive(Clone, Debug)]
+pub struct Compiler {
+	/// The callable native functions.
+	library: Arc<Library>,
+	/// Whether the created [`Chunk`]s are optimized.
+	optimize: bool,
+	/// The types to check the statements against.
+	schema: Option<Arc<Schema>>,
+}
+
+impl Default for Compiler {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Compiler {
+	/// Create a Compiler with the default [`Library`].
+	#[must_use]
+	pub fn new() -> Self {
+		Self::with_library(Arc::new(Library::default()))
+	}
+
+	/// Create a Compiler with the given [`Library`].
+	#[must_use]
+	pub const fn with_library(library: Arc<Library>) -> Self {
+		Self {
+			library,
+			optimize: true,
+			schema: None,
+		}
+	}
+
+	/// Enable or disable the optimization of the created [`Chunk`]s, it is enabled by default.
+	pub const fn set_optimization(&mut self, enabled: bool) {
+		self.optimize = enabled;
+	}
+
+	/// Check whether the created [`Chunk`]s are optimized.
+	#[must_use]
+	pub const fn optimization(&self) -> bool {
+		self.optimize
+	}
+
+	/// Set the [`Schema`] to check the types of the statements against before compiling them,
+	/// without a schema the types are not checked, which is the default.
+	pub fn set_schema(&mut self, schema: Option<Schema>) {
+		self.schema = schema.map(Arc::new);
+	}
+
+	/// Access the [`Schema`] the types are checked against.
+	#[must_use]
+	pub fn schema(&self) -> Option<&Schema> {
+		self.schema.as_deref()
+	}
+
+	/// Access the [`Library`].
+	#[must_use]
+	pub const fn library(&self) -> &Arc<Library> {
+		&self.library
+	}
+
+	/// Mutable access to the [`Library`].
+	pub fn library_mut(&mut self) -> &mut Library {
+		Arc::make_mut(&mut self.library)
+	}
+
+	/// Create a bytecode [`Chunk`] from statements.
+	/// # Errors
+	/// - if a function, library constant or type is used wrongly
+	/// - if the storages of the [`Chunk`] overflow
+	/// - if a [`Schema`] is set and the [`TypeChecker`] finds an error
+	pub fn compile(&self, statements: &[Stmt]) -> CompilationResult<Chunk> {
+		if let Some(schema) = &self.schema
+			&& let Some(error) = TypeChecker::new(schema, &self.library)
+				.check(statements)
+				.into_iter()
+				.next()
+		{
+			return Err(CompilationError::TypeCheck { source: error });
+		}
+		let mut chunk = Chunk::default();
+		for statement in statements {
+			self.statement(statement, &mut chunk)?;
+		}
+		let line = statements
+			.last()
+			.map_or(0, |statement| statement.span.end_line);
+		chunk.write(OpCode::Return as u8, line);
+		chunk.lower()?;
+		if self.optimize {
+			optimizer::optimize(&mut chunk);
+		}
+		chunk.finalize();
+		Ok(chunk)
+	}
+
+	fn statement(&self, statement: &Stmt, chunk: &mut Chunk) -> CompilationResult<()> {
+		let line = statement.span.end_line;
+		match &statement.kind {
+			StmtKind::Expression(expr) => self.expression(expr, chunk)?,
+			StmtKind::Print(expr) => {
+				self.expression(expr, chunk)?;
+				#[cfg(feature = "std")]
+				chunk.write(OpCode::Print as u8, line);
+				#[cfg(not(feature = "std"))]
+				chunk.write(OpCode::Pop as u8, line);
+			}
+			StmtKind::Yield => chunk.write(OpCode::Yield as u8, line),
+		}
+		Ok(())
+	}
+
+	#[allow(clippy::too_many_lines)]
+	fn expression(&self, expr: &Expr, chunk: &mut Chunk) -> CompilationResult<()> {
+		let line = expr.span.line;
+		let end_line = expr.span.end_line;
+		match &expr.kind {
+			ExprKind::Literal(literal) => match literal {
+				Literal::Nil => chunk.write(OpCode::Nil as u8, line),
+				Literal::Boolean(true) => chunk.write(OpCode::True as u8, line),
+				Literal::Boolean(false) => chunk.write(OpCode::False as u8, line),
+				Literal::Int(value) => Self::constant(ScriptingValue::Int64(*value), line, chunk)?,
+				Literal::Float(value) => Self::constant(ScriptingValue::Float64(*value), line, chunk)?,
+				Literal::String(value) => Self::constant(ScriptingValue::String(value.clone()), line, chunk)?,
+			},
+			ExprKind::Enum { value, .. } => Self::constant(ScriptingValue::Int64(i64::from(*value)), line, chunk)?,
+			ExprKind::Variable(name) => {
+				if let Some(value) = self.library.constant(name) {
+					Self::constant(value.clone(), line, chunk)?;
+				} else {
+					let name = chunk.add_constant(ScriptingValue::String(name.clone()))?;
+					Self::emit_bytes(OpCode::GetExternal as u8, name, line, chunk);
+				}
+			}
+			ExprKind::Template(parts) => self.template(expr, parts, chunk)?,
+			ExprKind::Grouping(inner) => self.expression(inner, chunk)?,
+			ExprKind::Unary { op, operand } => {
+				self.expression(operand, chunk)?;
+				match op {
+					UnaryOp::Not => chunk.write(OpCode::Not as u8, end_line),
+					UnaryOp::Negate => chunk.write(OpCode::Negate as u8, end_line),
+					UnaryOp::Plus => {}
+					UnaryOp::BitwiseNot => chunk.write(OpCode::BitwiseNot as u8, end_line),
+				}
+			}
+			ExprKind::Binary { op, left, right, .. } => {
+				self.expression(left, chunk)?;
+				self.expression(right, chunk)?;
+				match op {
+					BinaryOp::Add => chunk.write(OpCode::Add as u8, end_line),
+					BinaryOp::Subtract => chunk.write(OpCode::Subtract as u8, end_line),
+					BinaryOp::Multiply => chunk.write(OpCode::Multiply as u8, end_line),
+					BinaryOp::Divide => chunk.write(OpCode::Divide as u8, end_line),
+					BinaryOp::Equal => chunk.write(OpCode::Equal as u8, end_line),
+					BinaryOp::NotEqual => Self::emit_bytes(OpCode::Equal as u8, OpCode::Not as u8, end_line, chunk),
+					BinaryOp::Greater => chunk.write(OpCode::Greater as u8, end_line),
+					BinaryOp::GreaterEqual => Self::emit_bytes(OpCode::Less as u8, OpCode::Not as u8, end_line, chunk),
+					BinaryOp::Less => chunk.write(OpCode::Less as u8, end_line),
+					BinaryOp::LessEqual => Self::emit_bytes(OpCode::Greater as u8, OpCode::Not as u8, end_line, chunk),
+					BinaryOp::BitwiseAnd => chunk.write(OpCode::BitwiseAnd as u8, end_line),
+					BinaryOp::BitwiseOr => chunk.write(OpCode::BitwiseOr as u8, end_line),
+					BinaryOp::BitwiseXor => chunk.write(OpCode::BitwiseXor as u8, end_line),
+				}
+			}
+			ExprKind::Logical {
+				op,
+				operator,
+				left,
+				right,
+			} => {
+				self.expression(left, chunk)?;
+				let jump = match op {
+					LogicalOp::And => OpCode::JmpIfFalse,
+					LogicalOp::Or => OpCode::JmpIfTrue,
+				};
+				let line = operator.line;
+				let target_pos = Self::emit_jump(jump as u8, line, chunk);
+				chunk.write(OpCode::Pop as u8, line);
+				self.expression(right, chunk)?;
+				Self::patch_jump(target_pos, chunk);
+			}
+			ExprKind::Ternary {
+				condition,
+				then,
+				otherwise,
+				question,
+				colon,
+			} => {
+				self.expression(condition, chunk)?;
+				let else_pos = Self::emit_jump(OpCode::JmpIfFalse as u8, question.line, chunk);
+				// remove the decision value
+				chunk.write(OpCode::Pop as u8, question.line);
+				self.expression(then, chunk)?;
+				let end_pos = Self::emit_jump(OpCode::Jmp as u8, then.span.end_line, chunk);
+				Self::patch_jump(else_pos, chunk);
+				// remove the decision value
+				chunk.write(OpCode::Pop as u8, colon.line);
+				self.expression(otherwise, chunk)?;
+				Self::patch_jump(end_pos, chunk);
+			}
+			ExprKind::Assign { op, name, value } => self.assignment(expr, *op, name, value, chunk)?,
+			ExprKind::Call { name, args, .. } => {
+				let function = self.function(name, line)?;
+				for arg in args {
+					self.expression(arg, chunk)?;
+				}
+				let wrong_count = || CompilationError::WrongArgumentCount {
+					name: name.clone(),
+					found: args.len(),
+					pos: line,
+				};
+				if !function.accepts(args.len()) {
+					return Err(wrong_count());
+				}
+				let count = u8::try_from(args.len()).map_err(|_| wrong_count())?;
+				Self::emit_call(function, count, end_line, chunk)?;
+			}
+			ExprKind::Cast { value, type_name, .. } => {
+				self.expression(value, chunk)?;
+				let Some(name) = conversion_function(type_name) else {
+					return Err(CompilationError::UnknownType {
+						name: type_name.clone(),
+						pos: end_line,
+					});
+				};
+				let function = self.function(name, end_line)?;
+				Self::emit_call(function, 1, end_line, chunk)?;
+			}
+		}
+		Ok(())
+	}
+
+	fn assignment(
+		&self,
+		expr: &Expr,
+		op: AssignOp,
+		name: &ConstString,
+		value: &Expr,
+		chunk: &mut Chunk,
+	) -> CompilationResult<()> {
+		let line = expr.span.line;
+		let end_line = expr.span.end_line;
+		// constants of the library can not be changed
+		if self.library.constant(name).is_some() {
+			return Err(CompilationError::ConstantAssignment {
+				name: name.clone(),
+				pos: line,
+			});
+		}
+		let operator = match op {
+			AssignOp::Define | AssignOp::Assign => {
+				self.expression(value, chunk)?;
+				let name = chunk.add_constant(ScriptingValue::String(name.clone()))?;
+				let op_code = if op == AssignOp::Define {
+					OpCode::DefineExternal
+				} else {
+					OpCode::SetExternal
+				};
+				Self::emit_bytes(op_code as u8, name, end_line, chunk);
+				return Ok(());
+			}
+			AssignOp::Add => OpCode::Add,
+			AssignOp::Subtract => OpCode::Subtract,
+			AssignOp::Multiply => OpCode::Multiply,
+			AssignOp::Divide => OpCode::Divide,
+		};
+		let name = chunk.add_constant(ScriptingValue::String(name.clone()))?;
+		Self::emit_bytes(OpCode::GetExternal as u8, name, line, chunk);
+		self.expression(value, chunk)?;
+		chunk.write(operator as u8, end_line);
+		Self::emit_bytes(OpCode::SetExternal as u8, name, end_line, chunk);
+		Ok(())
+	}
+
+	/// A template is split into its constant and expression pieces, which are joined by an [`OpCode::Concat`].
+	/// A template with more pieces than fit on the stack, keeping a slot free for evaluating them,
+	/// is joined in batches, each including the string of the batch before.
+	/// So a template fitting on the stack creates a single string.
+	fn template(&self, expr: &Expr, parts: &[TemplatePart], chunk: &mut Chunk) -> CompilationResult<()> {
+		let line = expr.span.line;
+		// a template without any expression is just a string
+		if !parts
+			.iter()
+			.any(|part| matches!(part, TemplatePart::Expr(_)))
+		{
+			let text = parts
+				.iter()
+				.find_map(|part| match part {
+					TemplatePart::Text(text) => Some(text.clone()),
+					TemplatePart::Expr(_) => None,
+				})
+				.unwrap_or_else(|| "".into());
+			return Self::constant(ScriptingValue::String(text), line, chunk);
+		}
+		let batch = u8::try_from(STACK_SIZE - 1).unwrap_or(u8::MAX);
+		// values on the stack to join, a single piece is just converted into a string
+		let mut count = 0;
+		for (index, part) in parts.iter().enumerate() {
+			match part {
+				TemplatePart::Text(text) => Self::constant(ScriptingValue::String(text.clone()), line, chunk)?,
+				TemplatePart::Expr(expr) => self.expression(expr, chunk)?,
+			}
+			count += 1;
+			if count == batch || index + 1 == parts.len() {
+				Self::emit_bytes(OpCode::Concat as u8, count, line, chunk);
+				count = 1;
+			}
+		}
+		Ok(())
+	}
+
+	fn function(&self, name: &str, line: usize) -> CompilationResult<&NativeFunction> {
+		self.library
+			.function(name)
+			.ok_or_else(|| CompilationError::FunctionNotFound {
+				name: name.into(),
+				pos: line,
+			})
+	}
+
+	fn constant(value: ScriptingValue, line: usize, chunk: &mut Chunk) -> CompilationResult<()> {
+		let offset = chunk.add_constant(value)?;
+		Self::emit_bytes(OpCode::Constant as u8, offset, line, chunk);
+		Ok(())
+	}
+
+	fn emit_bytes(byte1: u8, byte2: u8, line: usize, chunk: &mut Chunk) {
+		chunk.write(byte1, line);
+		chunk.write(byte2, line);
+	}
+
+	/// Emit the call of a native function with `count` arguments.
+	/// # Errors
+	/// - if the function storage of the [`Chunk`] overflows
+	fn emit_call(function: &NativeFunction, count: u8, line: usize, chunk: &mut Chunk) -> CompilationResult<()> {
+		let pos = chunk.add_function(function)?;
+		Self::emit_bytes(OpCode::Call as u8, pos, line, chunk);
+		chunk.write(count, line);
+		Ok(())
+	}
+
+	fn emit_jump(instruction: u8, line: usize, chunk: &mut Chunk) -> usize {
+		chunk.write(instruction, line);
+		let target_pos = chunk.code().len();
+		// the dummy address bytes
+		chunk.write(0xFF, line);
+		chunk.write(0xFF, line);
+		target_pos
+	}
+
+	#[allow(clippy::cast_possible_truncation)]
+	fn patch_jump(patch_pos: usize, chunk: &mut Chunk) {
+		let target = chunk.code().len();
+		let byte1 = (target >> 8) as u8;
+		let byte2 = target as u8;
+		chunk.patch(byte1, patch_pos);
+		chunk.patch(byte2, patch_pos + 1);
+	}
+}
+// endregion:	--- Compiler
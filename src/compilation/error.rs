@@ -3,6 +3,8 @@
 
 use crate::ConstString;
 
+use super::type_checker::TypeError;
+
 /// Shortcut for tinyscript's compile Result<T, E> type
 pub type CompilationResult<T> = core::result::Result<T, CompilationError>;
 
@@ -23,6 +25,34 @@ pub enum CompilationError {
 		/// Position(line) in code.
 		pos: usize,
 	},
+	/// Jump to a target that is not an instruction.
+	InvalidJumpTarget {
+		/// The faulty target.
+		target: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// Malformed `{expression}` within a template string.
+	InvalidInterpolation {
+		/// The faulty template.
+		token: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// Malformed or superfluous operand of an instruction.
+	InvalidOperand {
+		/// The faulty token.
+		token: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// Instruction is missing its operands.
+	OperandExpected {
+		/// Name of the instruction.
+		name: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
 	/// Not a hex number.
 	ParseHex {
 		/// The faulty token.
@@ -44,8 +74,59 @@ pub enum CompilationError {
 		/// Position(line) in code.
 		pos: usize,
 	},
+	/// Tried to assign a value to a constant.
+	ConstantAssignment {
+		/// Name of the constant.
+		name: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// Code does not end with a return instruction.
+	ReturnExpected {
+		/// Position(line) in code of the last instruction.
+		pos: usize,
+	},
 	/// Stack of values exceeded.
 	ConstantStorageOverflow,
+	/// Instruction puts more values onto the stack than it can hold.
+	StackOverflow {
+		/// Name of the instruction.
+		name: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// Instruction takes more values from the stack than there are.
+	StackUnderflow {
+		/// Name of the instruction.
+		name: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// Label is defined more than once.
+	DuplicateLabel {
+		/// Name of the label.
+		name: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// Function is not registered.
+	FunctionNotFound {
+		/// Name of the function.
+		name: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// To many different functions called.
+	FunctionStorageOverflow,
+	/// Function called with a wrong number of arguments.
+	WrongArgumentCount {
+		/// Name of the function.
+		name: ConstString,
+		/// Number of given arguments.
+		found: usize,
+		/// Position(line) in code.
+		pos: usize,
+	},
 	/// Did not get the expected `Token`.
 	TokenExpected {
 		/// The expected token.
@@ -55,6 +136,27 @@ pub enum CompilationError {
 		/// Position(line) in code.
 		pos: usize,
 	},
+	/// Jump to a label that is not defined.
+	UnknownLabel {
+		/// Name of the label.
+		name: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// Not a known instruction.
+	UnknownOpCode {
+		/// Name of the instruction.
+		name: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
+	/// Not a known type name.
+	UnknownType {
+		/// The faulty type name.
+		name: ConstString,
+		/// Position(line) in code.
+		pos: usize,
+	},
 	/// This char should not be here.
 	UnexpectedChar {
 		/// The faulty character.
@@ -62,6 +164,16 @@ pub enum CompilationError {
 		/// Position(line) in code.
 		pos: usize,
 	},
+	/// Type error found by the [`TypeChecker`](crate::compilation::TypeChecker).
+	TypeCheck {
+		/// The original error.
+		source: TypeError,
+	},
+	/// Missing block comment termination.
+	UnterminatedComment {
+		/// Position(line) in code where the comment starts.
+		pos: usize,
+	},
 	/// Missing string termination.
 	UnterminatedString {
 		/// The unterminated sequence.
@@ -79,24 +191,85 @@ pub enum CompilationError {
 	},
 }
 
+impl CompilationError {
+	/// Position(line) in code of the error, if it is related to the code.
+	#[must_use]
+	pub const fn line(&self) -> Option<usize> {
+		match self {
+			Self::EnumValNotFound { pos, .. }
+			| Self::ExpressionExpected { pos, .. }
+			| Self::InvalidJumpTarget { pos, .. }
+			| Self::InvalidInterpolation { pos, .. }
+			| Self::InvalidOperand { pos, .. }
+			| Self::OperandExpected { pos, .. }
+			| Self::ParseHex { pos, .. }
+			| Self::ParseInt { pos, .. }
+			| Self::ParseNumber { pos, .. }
+			| Self::ConstantAssignment { pos, .. }
+			| Self::ReturnExpected { pos }
+			| Self::StackOverflow { pos, .. }
+			| Self::StackUnderflow { pos, .. }
+			| Self::DuplicateLabel { pos, .. }
+			| Self::FunctionNotFound { pos, .. }
+			| Self::WrongArgumentCount { pos, .. }
+			| Self::TokenExpected { pos, .. }
+			| Self::UnknownLabel { pos, .. }
+			| Self::UnknownOpCode { pos, .. }
+			| Self::UnknownType { pos, .. }
+			| Self::UnexpectedChar { pos, .. }
+			| Self::UnterminatedComment { pos }
+			| Self::UnterminatedString { pos, .. } => Some(*pos),
+			Self::TypeCheck { source } => Some(source.span.line),
+			Self::ConstantStorageOverflow | Self::FunctionStorageOverflow | Self::Unreachable { .. } => None,
+		}
+	}
+}
+
 /// Currently the default implementation is sufficient.
 impl core::error::Error for CompilationError {}
 
 impl core::fmt::Debug for CompilationError {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		match self {
+			Self::ConstantAssignment { name, pos } => write!(f, "the constant {name} at line {pos} can not be assigned"),
 			Self::ConstantStorageOverflow => write!(f, "to many constant values defined: storage overflow"),
+			Self::FunctionNotFound { name, pos } => write!(f, "the function {name} at line {pos} is not defined"),
+			Self::FunctionStorageOverflow => write!(f, "to many functions called: storage overflow"),
+			Self::WrongArgumentCount { name, found, pos } => {
+				write!(f, "the function {name} at line {pos} does not take {found} arguments")
+			}
+			Self::DuplicateLabel { name, pos } => write!(f, "the label {name} at line {pos} is already defined"),
 			Self::EnumValNotFound { value, pos } => write!(f, "the ScriptEnum value {value} at line {pos} is not defined"),
 			Self::ExpressionExpected { token, pos } => {
 				write!(f, "expression expected at line {pos}, got {token}")
 			}
+			Self::InvalidInterpolation { token, pos } => {
+				write!(f, "invalid interpolation in template {token} at line {pos}")
+			}
+			Self::InvalidJumpTarget { target, pos } => {
+				write!(f, "the jump target {target} at line {pos} is not an instruction")
+			}
+			Self::InvalidOperand { token, pos } => write!(f, "invalid operand {token} at line {pos}"),
+			Self::OperandExpected { name, pos } => write!(f, "the instruction {name} at line {pos} expects operands"),
 			Self::ParseHex { token, pos } => write!(f, "could not parse {token} at line {pos} as Hex value"),
 			Self::ParseInt { token, pos } => write!(f, "could not parse {token} at line {pos} as Integer value"),
 			Self::ParseNumber { token, pos } => write!(f, "could not parse {token} at line {pos} as Double value"),
+			Self::ReturnExpected { pos } => write!(f, "expected OP_RETURN as last instruction at line {pos}"),
+			Self::StackOverflow { name, pos } => {
+				write!(f, "the instruction {name} at line {pos} exceeds the stack size")
+			}
+			Self::StackUnderflow { name, pos } => {
+				write!(f, "the instruction {name} at line {pos} takes values from an empty stack")
+			}
 			Self::TokenExpected { expected, found, pos } => {
 				write!(f, "expected token {expected}, found Token {found} at line {pos}")
 			}
+			Self::TypeCheck { source } => write!(f, "{source}"),
+			Self::UnknownLabel { name, pos } => write!(f, "the label {name} at line {pos} is not defined"),
+			Self::UnknownOpCode { name, pos } => write!(f, "unknown instruction {name} at line {pos}"),
+			Self::UnknownType { name, pos } => write!(f, "unknown type {name} at line {pos}"),
 			Self::UnexpectedChar { c, pos } => write!(f, "unexpected character {c} at line {pos}"),
+			Self::UnterminatedComment { pos } => write!(f, "unterminated block comment starting at line {pos}"),
 			Self::UnterminatedString { str, pos } => write!(f, "unterminated string {str} at line {pos}"),
 			Self::Unreachable { file, line } => write!(f, "{file} at line {line} should be unreachable"),
 		}
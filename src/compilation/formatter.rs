@@ -0,0 +1,393 @@
+// Copyright © 2026 Stephan Kunz
+//! [`Formatter`] re-emitting a script in canonical form.
+//!
+//! The script is parsed into its [abstract syntax tree](crate::compilation::ast) and printed again:
+//! - one statement per line, each terminated with a `;`
+//! - a single space around infix operators, `?`, `:` and `as` and after a `,`
+//! - no space after prefix operators, within parentheses and before `(` of a call
+//! - at most one empty line between statements
+//!
+//! Literals, names and template strings are kept as written.
+//! Comments are kept at their position, a comment behind a statement stays on its line.
+//! Within a statement spanning multiple lines due to a line comment, the continuation is indented by a tab.
+//!
+//! The formatted script consists of the same tokens as the original one,
+//! so it compiles to the same bytecode, only the source lines of the instructions differ.
+
+// region:		--- modules
+use alloc::{collections::btree_map::BTreeMap, string::String, vec::Vec};
+
+use super::{
+	Lexer, Parser,
+	ast::{Expr, ExprKind, Stmt, StmtKind},
+	error::{CompilationError, CompilationResult},
+	token::TokenKind,
+};
+// endregion:	--- modules
+
+// region:		--- helpers
+/// A comment within the source code.
+struct Comment {
+	/// Byte offset of the start.
+	start: usize,
+	/// Byte offset behind the end, a line comment does not include the `'\n'`.
+	end: usize,
+	/// Line of the start.
+	line: usize,
+}
+
+/// Find the comments in the source code, skipping strings and templates the same way the [`Lexer`] does.
+fn comments(source: &str) -> Vec<Comment> {
+	let mut comments = Vec::new();
+	let mut line = 1;
+	let mut pos = 0;
+	while let Some(c) = source[pos..].chars().next() {
+		let rest = &source[pos..];
+		let len = match c {
+			'\'' | '`' => rest[1..]
+				.find(c)
+				.map_or(rest.len(), |end| end + 2),
+			'/' if rest.starts_with("//") => {
+				let end = rest.find('\n').unwrap_or(rest.len());
+				comments.push(Comment {
+					start: pos,
+					end: pos + end,
+					line,
+				});
+				end
+			}
+			'/' if rest.starts_with("/*") => {
+				let end = rest[2..]
+					.find("*/")
+					.map_or(rest.len(), |end| end + 4);
+				comments.push(Comment {
+					start: pos,
+					end: pos + end,
+					line,
+				});
+				end
+			}
+			_ => c.len_utf8(),
+		};
+		line += rest[..len].matches('\n').count();
+		pos += len;
+	}
+	comments
+}
+
+/// The tokens of a script which have to be kept by formatting.
+fn tokens(source: &str) -> CompilationResult<Vec<(TokenKind, String)>> {
+	let enums = BTreeMap::new();
+	let mut tokens = Vec::new();
+	for token in Lexer::new(&enums, source) {
+		let token = token?;
+		let kind = token.kind();
+		let origin = match kind {
+			// operators may contain whitespace, e.g. `: =`
+			TokenKind::Ident
+			| TokenKind::Enum
+			| TokenKind::IntNumber
+			| TokenKind::HexNumber
+			| TokenKind::FloatNumber
+			| TokenKind::String
+			| TokenKind::Template => token.origin,
+			_ => String::new(),
+		};
+		tokens.push((kind, origin));
+	}
+	// the `;` behind the last statement is optional
+	if tokens
+		.last()
+		.is_some_and(|(kind, _)| *kind == TokenKind::Semicolon)
+	{
+		tokens.pop();
+	}
+	Ok(tokens)
+}
+// endregion:	--- helpers
+
+// region:		--- Formatter
+/// Formatter implementation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Formatter;
+
+impl Formatter {
+	/// Create a Formatter.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self
+	}
+
+	/// Format a script.
+	/// # Errors
+	/// - if the script can not be parsed
+	pub fn format(&self, source: &str) -> CompilationResult<String> {
+		// enums are formatted like variables, so they need not be known
+		let stmts = Parser::new().parse_ast(&BTreeMap::new(), source)?;
+		let mut printer = Printer::new(source);
+		for (index, stmt) in stmts.iter().enumerate() {
+			let next = stmts
+				.get(index + 1)
+				.map_or(source.len(), |next| next.span.start);
+			printer.stmt(stmt, next);
+		}
+		printer.leading(source.len());
+		let formatted = printer.out;
+
+		// the formatted script must compile to the same bytecode
+		if tokens(source)? != tokens(&formatted)? {
+			return Err(CompilationError::Unreachable {
+				file: file!().into(),
+				line: line!(),
+			});
+		}
+		Ok(formatted)
+	}
+}
+// endregion:	--- Formatter
+
+// region:		--- Printer
+/// The state while printing a script.
+struct Printer<'a> {
+	source: &'a str,
+	comments: Vec<Comment>,
+	/// Index of the next comment to print.
+	next: usize,
+	out: String,
+	/// Whether a space is due before the next word.
+	space: bool,
+	/// Whether a statement is being printed.
+	in_stmt: bool,
+	/// Whether the statement continues on a new line.
+	continued: bool,
+	/// Byte offset behind the last printed statement or comment.
+	last_end: usize,
+}
+
+impl<'a> Printer<'a> {
+	fn new(source: &'a str) -> Self {
+		Self {
+			source,
+			comments: comments(source),
+			next: 0,
+			out: String::new(),
+			space: false,
+			in_stmt: false,
+			continued: false,
+			last_end: 0,
+		}
+	}
+
+	/// Start of the next comment, if it starts before `offset`.
+	fn comment_before(&self, offset: usize) -> Option<usize> {
+		self.comments
+			.get(self.next)
+			.map(|comment| comment.start)
+			.filter(|start| *start < offset)
+	}
+
+	/// Whether the output is at the start of a line.
+	fn line_start(&self) -> bool {
+		self.out.is_empty() || self.out.ends_with('\n')
+	}
+
+	fn newline(&mut self) {
+		self.out.push('\n');
+		self.space = false;
+		self.continued = self.in_stmt;
+	}
+
+	/// Separate the next output from the previous one.
+	fn separate(&mut self, space: bool) {
+		if self.line_start() {
+			if self.continued {
+				self.out.push('\t');
+			}
+		} else if space {
+			self.out.push(' ');
+		}
+		self.space = false;
+	}
+
+	/// Print the next comment, a line comment ends the line.
+	fn comment(&mut self) {
+		let Some(comment) = self.comments.get(self.next) else {
+			return;
+		};
+		let (start, end) = (comment.start, comment.end);
+		self.next += 1;
+		self.separate(true);
+		self.out.push_str(&self.source[start..end]);
+		self.last_end = end;
+		if self.source[start..].starts_with("//") {
+			self.newline();
+		} else {
+			self.space = true;
+		}
+	}
+
+	/// Print all comments before `offset` within a statement.
+	fn flush(&mut self, offset: usize) {
+		while self.comment_before(offset).is_some() {
+			self.comment();
+		}
+	}
+
+	/// Print a word of the script, which starts at `offset` in the source code.
+	fn word(&mut self, offset: usize, text: &str) {
+		self.flush(offset);
+		self.separate(self.space);
+		self.out.push_str(text);
+	}
+
+	/// Print punctuation, which is never preceded by a space.
+	fn punct(&mut self, offset: usize, text: &str) {
+		self.flush(offset);
+		self.separate(false);
+		self.out.push_str(text);
+	}
+
+	/// Print the comments before `offset` on lines of their own, keeping an empty line in between.
+	fn leading(&mut self, offset: usize) {
+		loop {
+			let next = self.comment_before(offset);
+			let end = next.unwrap_or(offset);
+			// no empty line at the start and the end of the script
+			if !self.out.is_empty()
+				&& end < self.source.len()
+				&& self.source[self.last_end..end]
+					.matches('\n')
+					.count() > 1
+			{
+				self.out.push('\n');
+			}
+			if next.is_none() {
+				break;
+			}
+			self.comment();
+			if !self.line_start() {
+				self.newline();
+			}
+		}
+	}
+
+	/// Print a statement, the next one starts at `next`.
+	fn stmt(&mut self, stmt: &Stmt, next: usize) {
+		self.leading(stmt.span.start);
+		self.in_stmt = true;
+		match &stmt.kind {
+			StmtKind::Expression(expr) => self.expr(expr),
+			StmtKind::Print(expr) => {
+				self.word(stmt.span.start, "print");
+				self.space = true;
+				self.expr(expr);
+			}
+			StmtKind::Yield => self.word(stmt.span.start, "yield"),
+		}
+		self.separate(false);
+		self.out.push(';');
+		self.in_stmt = false;
+		self.continued = false;
+		self.last_end = stmt.span.end;
+		// comments behind the last word and on the same line stay behind the statement
+		while let Some(comment) = self.comments.get(self.next) {
+			if comment.start >= stmt.span.end && (comment.line != stmt.span.end_line || comment.start >= next) {
+				break;
+			}
+			self.comment();
+		}
+		if !self.line_start() {
+			self.newline();
+		}
+	}
+
+	/// Print an infix operator surrounded by spaces.
+	fn infix(&mut self, offset: usize, symbol: &str) {
+		self.space = true;
+		self.word(offset, symbol);
+		self.space = true;
+	}
+
+	fn expr(&mut self, expr: &Expr) {
+		let source = self.source;
+		let span = expr.span;
+		match &expr.kind {
+			ExprKind::Literal(_) | ExprKind::Enum { .. } | ExprKind::Variable(_) | ExprKind::Template(_) => {
+				self.word(span.start, &source[span.start..span.end]);
+			}
+			ExprKind::Grouping(inner) => {
+				self.word(span.start, "(");
+				self.expr(inner);
+				self.punct(span.end - 1, ")");
+			}
+			ExprKind::Unary { op, operand } => {
+				self.word(span.start, op.symbol());
+				self.expr(operand);
+			}
+			ExprKind::Binary {
+				op,
+				operator,
+				left,
+				right,
+			} => {
+				self.expr(left);
+				self.infix(operator.start, op.symbol());
+				self.expr(right);
+			}
+			ExprKind::Logical {
+				op,
+				operator,
+				left,
+				right,
+			} => {
+				self.expr(left);
+				self.infix(operator.start, op.symbol());
+				self.expr(right);
+			}
+			ExprKind::Ternary {
+				condition,
+				then,
+				otherwise,
+				question,
+				colon,
+			} => {
+				self.expr(condition);
+				self.infix(question.start, "?");
+				self.expr(then);
+				self.infix(colon.start, ":");
+				self.expr(otherwise);
+			}
+			ExprKind::Assign { op, name, value } => {
+				self.word(span.start, name);
+				self.infix(span.start, op.symbol());
+				self.expr(value);
+			}
+			ExprKind::Call { name, args, commas } => {
+				self.word(span.start, name);
+				self.punct(span.start, "(");
+				for (index, arg) in args.iter().enumerate() {
+					if let Some(comma) = index
+						.checked_sub(1)
+						.and_then(|index| commas.get(index))
+					{
+						self.punct(comma.start, ",");
+						self.space = true;
+					}
+					self.expr(arg);
+				}
+				self.punct(span.end - 1, ")");
+			}
+			ExprKind::Cast {
+				value,
+				operator,
+				type_name,
+			} => {
+				self.expr(value);
+				self.infix(operator.start, "as");
+				// the type name ends the cast
+				self.word(span.end - type_name.len(), type_name);
+			}
+		}
+	}
+}
+// endregion:	--- Printer
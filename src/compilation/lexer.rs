@@ -18,6 +18,7 @@ use super::{
 /// Enum to handle multi charakter tokens
 enum Started {
 	String,
+	Template,
 	Number, // may be hex or not
 	Ident,
 	IfEqualElse(TokenKind, TokenKind),
@@ -57,6 +58,17 @@ impl<'a> Lexer<'a> {
 		self.enums
 	}
 
+	/// The current line.
+	pub(crate) const fn line(&self) -> usize {
+		self.line
+	}
+
+	/// Set the line the input starts at.
+	/// Used for source code embedded in other tokens.
+	pub(crate) const fn set_line(&mut self, line: usize) {
+		self.line = line;
+	}
+
 	/// Set a new input str (source code).
 	pub const fn set_input(&mut self, source_code: &'a str) {
 		self.whole = source_code;
@@ -99,6 +111,7 @@ impl Iterator for Lexer<'_> {
 				'(' => return create(TokenKind::LeftParen),
 				')' => return create(TokenKind::RightParen),
 				';' => return create(TokenKind::Semicolon),
+				',' => return create(TokenKind::Comma),
 				'^' => return create(TokenKind::Caret),
 				'~' => return create(TokenKind::Tilde),
 				'?' => return create(TokenKind::QMark),
@@ -109,6 +122,25 @@ impl Iterator for Lexer<'_> {
 				'+' => Started::IfEqualElse(TokenKind::PlusEqual, TokenKind::Plus),
 				'-' => Started::IfEqualElse(TokenKind::MinusEqual, TokenKind::Minus),
 				'*' => Started::IfEqualElse(TokenKind::StarEqual, TokenKind::Star),
+				// line comment, the '\n' is left for line counting
+				'/' if self.rest.starts_with('/') => {
+					let end = self.rest.find('\n').unwrap_or(self.rest.len());
+					self.pos += end;
+					self.rest = &self.rest[end..];
+					continue;
+				}
+				// block comment
+				'/' if self.rest.starts_with('*') => {
+					let Some(end) = self.rest[1..].find("*/") else {
+						return Some(Err(CompilationError::UnterminatedComment { pos: self.line }));
+					};
+					// skip the leading '*' and the trailing '*/'
+					let end = end + 3;
+					self.line += self.rest[..end].matches('\n').count();
+					self.pos += end;
+					self.rest = &self.rest[end..];
+					continue;
+				}
 				'/' => Started::IfEqualElse(TokenKind::SlashEqual, TokenKind::Slash),
 				'<' => Started::IfEqualElse(TokenKind::LessEqual, TokenKind::Less),
 				'>' => Started::IfEqualElse(TokenKind::GreaterEqual, TokenKind::Greater),
@@ -117,6 +149,7 @@ impl Iterator for Lexer<'_> {
 				'|' => Started::IfSameElse(TokenKind::Or, TokenKind::Pipe),
 				// multi character token
 				'\'' => Started::String,
+				'`' => Started::Template,
 				'0'..='9' => Started::Number,
 				'a'..='z' | 'A'..='Z' | '_' | '@' => Started::Ident,
 				// count lines
@@ -136,6 +169,8 @@ impl Iterator for Lexer<'_> {
 					self.rest = self.rest.trim_start();
 					let trimmed = c_onwards.len() - self.rest.len() - 1;
 					self.pos += trimmed;
+					// the skipped whitespace may contain line breaks
+					self.line += c_onwards[1..=trimmed].matches('\n').count();
 					if self.rest.starts_with('=') {
 						let span = &c_onwards[..=c.len_utf8() + trimmed];
 						self.rest = &self.rest[1..];
@@ -143,14 +178,14 @@ impl Iterator for Lexer<'_> {
 						Some(Ok(Token {
 							origin: span.to_string(),
 							offset: c_at,
-							line: self.line,
+							line,
 							kind: yes,
 						}))
 					} else {
 						Some(Ok(Token {
 							origin: c_str.to_string(),
 							offset: c_at,
-							line: self.line,
+							line,
 							kind: no,
 						}))
 					}
@@ -159,6 +194,8 @@ impl Iterator for Lexer<'_> {
 					self.rest = self.rest.trim_start();
 					let trimmed = c_onwards.len() - self.rest.len() - 1;
 					self.pos += trimmed;
+					// the skipped whitespace may contain line breaks
+					self.line += c_onwards[1..=trimmed].matches('\n').count();
 					if self.rest.starts_with(c) {
 						let span = &c_onwards[..=c.len_utf8() + trimmed];
 						self.rest = &self.rest[1..];
@@ -166,14 +203,14 @@ impl Iterator for Lexer<'_> {
 						Some(Ok(Token {
 							origin: span.to_string(),
 							offset: c_at,
-							line: self.line,
+							line,
 							kind: yes,
 						}))
 					} else {
 						Some(Ok(Token {
 							origin: c_str.to_string(),
 							offset: c_at,
-							line: self.line,
+							line,
 							kind: no,
 						}))
 					}
@@ -193,10 +230,12 @@ impl Iterator for Lexer<'_> {
 
 					// distinguish keywords and enum values (aka int numbers) from idents
 					let kind = match literal {
+						"as" => TokenKind::As,
 						"false" => TokenKind::False,
 						"nil" => TokenKind::Nil,
 						"print" => TokenKind::Print,
 						"true" => TokenKind::True,
+						"yield" => TokenKind::Yield,
 						_ => {
 							// extern crate std;
 							// std::dbg!(&self.enums, &literal);
@@ -294,6 +333,28 @@ impl Iterator for Lexer<'_> {
 						}));
 					}
 				}
+				Started::Template => {
+					if let Some(end) = self.rest.find('`') {
+						// we do not want the leading and trailing '`' included
+						let literal = &c_onwards[1..=(end)];
+						self.pos += end + 1;
+						self.rest = &self.rest[end + 1..];
+						let token = Token {
+							origin: literal.to_string(),
+							offset: c_at,
+							line: self.line,
+							kind: TokenKind::Template,
+						};
+						// a template may span multiple lines
+						self.line += literal.matches('\n').count();
+						Some(Ok(token))
+					} else {
+						return Some(Err(CompilationError::UnterminatedString {
+							str: self.whole[c_at..].into(),
+							pos: self.line,
+						}));
+					}
+				}
 			};
 		}
 	}
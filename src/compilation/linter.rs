@@ -0,0 +1,320 @@
+// Copyright © 2026 Stephan Kunz
+//! [`Linter`] warning about common mistakes in scripts.
+//!
+//! The lints work on the [abstract syntax tree](crate::compilation::ast), a script is not executed.
+//! Variables are tracked in the order of execution:
+//! - an assignment `=`, `+=`, ... to a variable which is neither defined before with `:=`
+//!   nor registered as defined by the host
+//! - a comparison with `==` or `!=` of a float, which the [`VM`](crate::execution::VM)
+//!   considers equal within a difference of `2e-15`
+//! - a ternary condition which is always `true` or always `false`
+//! - a definition with `:=` which is neither read nor assigned afterwards
+//! - a definition or assignment of a variable named like a registered enum value
+
+// region:		--- modules
+use alloc::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec::Vec,
+};
+
+use crate::ConstString;
+
+use super::{
+	Parser,
+	ast::{AssignOp, BinaryOp, Expr, ExprKind, Literal, LogicalOp, Span, Stmt, StmtKind, TemplatePart, UnaryOp},
+	error::CompilationResult,
+};
+// endregion:	--- modules
+
+// region:		--- Warning
+/// The kinds of warnings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WarningKind {
+	/// Assignment to a variable before its definition.
+	UndefinedAssignment {
+		/// Name of the variable.
+		name: ConstString,
+	},
+	/// Comparison of floats for equality.
+	FloatEquality {
+		/// The comparison.
+		op: BinaryOp,
+	},
+	/// Ternary condition with a constant value.
+	ConstantCondition {
+		/// The value of the condition.
+		value: bool,
+	},
+	/// Definition which is never used.
+	UnusedDefinition {
+		/// Name of the variable.
+		name: ConstString,
+	},
+	/// Variable named like an enum value.
+	ShadowedEnum {
+		/// Name of the variable.
+		name: ConstString,
+	},
+}
+
+/// A warning about a part of a script.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+	/// What is suspicious.
+	pub kind: WarningKind,
+	/// Location of the suspicious code.
+	pub span: Span,
+}
+
+impl core::fmt::Display for Warning {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		let pos = self.span.line;
+		match &self.kind {
+			WarningKind::UndefinedAssignment { name } => {
+				write!(f, "the variable {name} at line {pos} is assigned before it is defined")
+			}
+			WarningKind::FloatEquality { op } => write!(
+				f,
+				"the floats compared with {} at line {pos} are equal within a difference of 2e-15",
+				op.symbol()
+			),
+			WarningKind::ConstantCondition { value } => write!(f, "the condition at line {pos} is always {value}"),
+			WarningKind::UnusedDefinition { name } => write!(f, "the variable {name} defined at line {pos} is never used"),
+			WarningKind::ShadowedEnum { name } => write!(f, "the variable {name} at line {pos} shadows an enum value"),
+		}
+	}
+}
+// endregion:	--- Warning
+
+// region:		--- Linter
+/// Linter implementation.
+#[derive(Clone, Debug, Default)]
+pub struct Linter {
+	/// Names of the enum values.
+	enums: BTreeSet<ConstString>,
+	/// Names of the variables defined by the host.
+	variables: BTreeSet<ConstString>,
+}
+
+impl Linter {
+	/// Create a Linter without enum values and variables.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register the name of an enum value, which should not be used for variables.
+	pub fn register_enum(&mut self, name: &str) {
+		self.enums.insert(name.into());
+	}
+
+	/// Register the name of a variable the host defines in the environment.
+	pub fn register_variable(&mut self, name: &str) {
+		self.variables.insert(name.into());
+	}
+
+	/// Lint a script, the warnings are ordered by their position.
+	/// # Errors
+	/// - if the script can not be parsed
+	pub fn lint(&self, source: &str) -> CompilationResult<Vec<Warning>> {
+		// enums are parsed like variables, so a variable shadowing an enum value can be detected
+		let stmts = Parser::new().parse_ast(&BTreeMap::new(), source)?;
+		Ok(self.lint_ast(&stmts))
+	}
+
+	/// Lint the abstract syntax tree of a script, the warnings are ordered by their position.
+	#[must_use]
+	pub fn lint_ast(&self, stmts: &[Stmt]) -> Vec<Warning> {
+		let mut state = State {
+			linter: self,
+			defined: BTreeSet::new(),
+			unused: BTreeMap::new(),
+			floats: BTreeSet::new(),
+			warnings: Vec::new(),
+		};
+		for stmt in stmts {
+			match &stmt.kind {
+				StmtKind::Expression(expr) | StmtKind::Print(expr) => state.expr(expr),
+				StmtKind::Yield => {}
+			}
+		}
+		let State {
+			unused, mut warnings, ..
+		} = state;
+		for (name, span) in unused {
+			warnings.push(Warning {
+				kind: WarningKind::UnusedDefinition { name },
+				span,
+			});
+		}
+		warnings.sort_by_key(|warning| warning.span.start);
+		warnings
+	}
+}
+// endregion:	--- Linter
+
+// region:		--- State
+/// The state while linting a script.
+struct State<'a> {
+	linter: &'a Linter,
+	/// Variables defined with `:=`.
+	defined: BTreeSet<ConstString>,
+	/// Definitions not used yet.
+	unused: BTreeMap<ConstString, Span>,
+	/// Variables holding a float.
+	floats: BTreeSet<ConstString>,
+	warnings: Vec<Warning>,
+}
+
+impl State<'_> {
+	fn warn(&mut self, kind: WarningKind, span: Span) {
+		self.warnings.push(Warning { kind, span });
+	}
+
+	fn expr(&mut self, expr: &Expr) {
+		match &expr.kind {
+			ExprKind::Literal(_) | ExprKind::Enum { .. } => {}
+			ExprKind::Variable(name) => {
+				self.unused.remove(name);
+			}
+			ExprKind::Template(parts) => {
+				for part in parts {
+					if let TemplatePart::Expr(expr) = part {
+						self.expr(expr);
+					}
+				}
+			}
+			ExprKind::Grouping(inner) => self.expr(inner),
+			ExprKind::Unary { operand, .. } => self.expr(operand),
+			ExprKind::Cast { value, .. } => self.expr(value),
+			ExprKind::Binary { op, left, right, .. } => {
+				self.expr(left);
+				self.expr(right);
+				if matches!(op, BinaryOp::Equal | BinaryOp::NotEqual) && (self.is_float(left) || self.is_float(right)) {
+					self.warn(WarningKind::FloatEquality { op: *op }, expr.span);
+				}
+			}
+			ExprKind::Logical { left, right, .. } => {
+				self.expr(left);
+				self.expr(right);
+			}
+			ExprKind::Ternary {
+				condition,
+				then,
+				otherwise,
+				..
+			} => {
+				if let Some(value) = constant(condition) {
+					self.warn(WarningKind::ConstantCondition { value }, condition.span);
+				}
+				self.expr(condition);
+				self.expr(then);
+				self.expr(otherwise);
+			}
+			ExprKind::Assign { op, name, value } => {
+				let float = self.is_float(value);
+				self.expr(value);
+				self.assign(*op, name, float, expr.span);
+			}
+			ExprKind::Call { args, .. } => {
+				for arg in args {
+					self.expr(arg);
+				}
+			}
+		}
+	}
+
+	fn assign(&mut self, op: AssignOp, name: &ConstString, float: bool, span: Span) {
+		let enum_name = self.linter.enums.contains(name);
+		if enum_name {
+			self.warn(WarningKind::ShadowedEnum { name: name.clone() }, span);
+		}
+		if op == AssignOp::Define {
+			if let Some(old) = self.unused.insert(name.clone(), span) {
+				self.warn(WarningKind::UnusedDefinition { name: name.clone() }, old);
+			}
+			self.defined.insert(name.clone());
+		} else {
+			if !(enum_name || self.defined.contains(name) || self.linter.variables.contains(name)) {
+				self.warn(WarningKind::UndefinedAssignment { name: name.clone() }, span);
+			}
+			self.unused.remove(name);
+		}
+		// a compound assignment of an int keeps a float
+		if float {
+			self.floats.insert(name.clone());
+		} else if matches!(op, AssignOp::Define | AssignOp::Assign) {
+			self.floats.remove(name);
+		}
+	}
+
+	/// Whether the expression evaluates to a float.
+	fn is_float(&self, expr: &Expr) -> bool {
+		match &expr.kind {
+			ExprKind::Literal(literal) => matches!(literal, Literal::Float(_)),
+			ExprKind::Variable(name) => self.floats.contains(name),
+			ExprKind::Grouping(inner) => self.is_float(inner),
+			ExprKind::Unary {
+				op: UnaryOp::Negate | UnaryOp::Plus,
+				operand,
+			} => self.is_float(operand),
+			ExprKind::Binary {
+				op: BinaryOp::Add | BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Divide,
+				left,
+				right,
+				..
+			} => self.is_float(left) || self.is_float(right),
+			ExprKind::Ternary { then, otherwise, .. } => self.is_float(then) || self.is_float(otherwise),
+			ExprKind::Assign { value, .. } => self.is_float(value),
+			ExprKind::Call { name, .. } => &**name == "float",
+			ExprKind::Cast { type_name, .. } => &**type_name == "float",
+			_ => false,
+		}
+	}
+}
+
+/// The value of a condition, if it does not depend on variables.
+fn constant(expr: &Expr) -> Option<bool> {
+	match &expr.kind {
+		ExprKind::Literal(Literal::Boolean(value)) => Some(*value),
+		ExprKind::Grouping(inner) => constant(inner),
+		ExprKind::Unary {
+			op: UnaryOp::Not,
+			operand,
+		} => constant(operand).map(|value| !value),
+		ExprKind::Logical { op, left, right, .. } => match (op, constant(left), constant(right)) {
+			(LogicalOp::And, Some(false), _) => Some(false),
+			(LogicalOp::Or, Some(true), _) => Some(true),
+			(_, Some(_), right) => right,
+			_ => None,
+		},
+		ExprKind::Binary { op, left, right, .. } => {
+			let (ExprKind::Literal(left), ExprKind::Literal(right)) = (&left.kind, &right.kind) else {
+				return None;
+			};
+			match (op, left, right) {
+				(BinaryOp::Equal, left, right) if same_type(left, right) => Some(left == right),
+				(BinaryOp::NotEqual, left, right) if same_type(left, right) => Some(left != right),
+				(BinaryOp::Greater, Literal::Int(left), Literal::Int(right)) => Some(left > right),
+				(BinaryOp::GreaterEqual, Literal::Int(left), Literal::Int(right)) => Some(left >= right),
+				(BinaryOp::Less, Literal::Int(left), Literal::Int(right)) => Some(left < right),
+				(BinaryOp::LessEqual, Literal::Int(left), Literal::Int(right)) => Some(left <= right),
+				_ => None,
+			}
+		}
+		_ => None,
+	}
+}
+
+/// Whether two literals are compared by value, floats are compared with an epsilon.
+const fn same_type(left: &Literal, right: &Literal) -> bool {
+	matches!(
+		(left, right),
+		(Literal::Nil, Literal::Nil)
+			| (Literal::Boolean(_), Literal::Boolean(_))
+			| (Literal::Int(_), Literal::Int(_))
+			| (Literal::String(_), Literal::String(_))
+	)
+}
+// endregion:	--- State
@@ -1,15 +1,28 @@
 // Copyright © 2025 Stephan Kunz
 //! Bytecode compiler implementation.
 
+mod assembler;
+pub mod ast;
+mod compiler;
 mod error;
+mod formatter;
 mod lexer;
+mod linter;
+mod optimizer;
 mod parselets;
 mod parser;
 mod precedence;
 mod token;
+mod type_checker;
 
 // flatten
+pub use assembler::Assembler;
+pub use ast::{Expr, Span, Stmt};
+pub use compiler::Compiler;
 pub use error::{CompilationError, CompilationResult};
+pub use formatter::Formatter;
 pub use lexer::Lexer;
+pub use linter::{Linter, Warning, WarningKind};
 pub use parser::Parser;
-pub use token::TokenKind;
+pub use token::{Token, TokenKind};
+pub use type_checker::{Schema, Type, TypeChecker, TypeError, TypeErrorKind};
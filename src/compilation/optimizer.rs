@@ -0,0 +1,281 @@
+// Copyright © 2026 Stephan Kunz
+//! Peephole optimizer for bytecode [`Chunk`]s.
+//!
+//! The optimizations work on the lowered [`Instruction`]s and are repeated until nothing changes anymore,
+//! afterwards the bytecode is encoded again.
+//! Applied optimizations:
+//! - folding of operators with constant operands, the operators are evaluated by the [`VM`]
+//! - resolving conditional jumps with constant conditions
+//! - removing unreachable code and jumps to the next instruction
+//! - removing constants that are immediately popped
+//!
+//! Instructions that are a jump target are never merged with their predecessors.
+
+// region:		--- modules
+use alloc::vec::Vec;
+
+use crate::{
+	execution::{Chunk, Instruction, VM},
+	scripting_value::ScriptingValue,
+};
+// endregion:	--- modules
+
+/// An instruction with its source line.
+#[derive(Clone, Copy)]
+struct Op {
+	instruction: Instruction,
+	line: usize,
+}
+
+/// Optimizes the lowered [`Instruction`]s of a [`Chunk`].
+pub fn optimize(chunk: &mut Chunk) {
+	let mut code: Vec<Op> = chunk
+		.instructions()
+		.iter()
+		.zip(chunk.instruction_lines())
+		.map(|(instruction, line)| Op {
+			instruction: *instruction,
+			line: *line,
+		})
+		.collect();
+	loop {
+		let mut changed = fold_constants(&mut code, chunk);
+		changed |= fold_conditions(&mut code, chunk);
+		changed |= remove_unreachable(&mut code);
+		changed |= remove_jumps_to_next(&mut code);
+		changed |= remove_pops(&mut code, chunk);
+		if !changed {
+			break;
+		}
+	}
+	let (instructions, lines) = code
+		.into_iter()
+		.map(|op| (op.instruction, op.line))
+		.unzip();
+	chunk.replace_instructions(instructions, lines);
+}
+
+/// Marks all instructions that are the target of a jump.
+/// The result has an additional entry for the end of the code.
+fn jump_targets(code: &[Op]) -> Vec<bool> {
+	let mut targets = Vec::new();
+	targets.resize(code.len() + 1, false);
+	for op in code {
+		if let Some(target) = op.instruction.target() {
+			targets[usize::from(target)] = true;
+		}
+	}
+	targets
+}
+
+/// Removes the marked instructions and corrects the jump targets.
+/// A jump to a removed instruction continues with the next remaining one.
+/// As nothing is added, the new indices always fit into the old range.
+#[allow(clippy::cast_possible_truncation)]
+fn compact(code: &mut Vec<Op>, removed: &[bool]) {
+	let mut index: Vec<usize> = Vec::with_capacity(code.len() + 1);
+	let mut kept = 0;
+	for is_removed in removed.iter().take(code.len()) {
+		index.push(kept);
+		if !is_removed {
+			kept += 1;
+		}
+	}
+	index.push(kept);
+
+	let mut pos = 0;
+	code.retain(|_| {
+		pos += 1;
+		!removed[pos - 1]
+	});
+	for op in code {
+		if let Some(target) = op.instruction.target() {
+			op.instruction = op
+				.instruction
+				.with_target(index[usize::from(target)] as u16);
+		}
+	}
+}
+
+/// The value an instruction pushes if it is a constant.
+fn constant(op: &Op, chunk: &Chunk) -> Option<ScriptingValue> {
+	match op.instruction {
+		Instruction::False => Some(ScriptingValue::Boolean(false)),
+		Instruction::Nil => Some(ScriptingValue::nil()),
+		Instruction::True => Some(ScriptingValue::Boolean(true)),
+		Instruction::Constant(pos) => Some(chunk.read_constant(pos).clone()),
+		_ => None,
+	}
+}
+
+/// The instruction pushing a constant value.
+fn push(value: ScriptingValue, chunk: &mut Chunk) -> Option<Instruction> {
+	match value {
+		ScriptingValue::Boolean(false) => Some(Instruction::False),
+		ScriptingValue::Boolean(true) => Some(Instruction::True),
+		ScriptingValue::Nil() => Some(Instruction::Nil),
+		value => chunk
+			.add_constant(value)
+			.ok()
+			.map(Instruction::Constant),
+	}
+}
+
+/// Evaluates operators with constant operands.
+/// Operators which would fail at runtime are left untouched.
+fn fold_constants(code: &mut Vec<Op>, chunk: &mut Chunk) -> bool {
+	let targets = jump_targets(code);
+	let mut removed = Vec::new();
+	removed.resize(code.len(), false);
+	let mut changed = false;
+
+	let mut i = 0;
+	while i < code.len() {
+		let operator = code[i].instruction;
+		let count = match operator {
+			Instruction::Add
+			| Instruction::BitwiseAnd
+			| Instruction::BitwiseOr
+			| Instruction::BitwiseXor
+			| Instruction::Divide
+			| Instruction::Equal
+			| Instruction::Greater
+			| Instruction::Less
+			| Instruction::Multiply
+			| Instruction::Subtract => 2,
+			Instruction::BitwiseNot | Instruction::Negate | Instruction::Not => 1,
+			Instruction::Concat(count) => usize::from(count),
+			_ => {
+				i += 1;
+				continue;
+			}
+		};
+		// the operands must directly precede the operator within the same block
+		if count == 0 || count > i || (i - count + 1..=i).any(|j| targets[j] || removed[j - 1]) {
+			i += 1;
+			continue;
+		}
+		let operands: Option<Vec<ScriptingValue>> = code[i - count..i]
+			.iter()
+			.map(|op| constant(op, chunk))
+			.collect();
+		if let Some(operands) = operands
+			&& let Ok(value) = VM::evaluate(operator, &operands)
+			&& let Some(instruction) = push(value, chunk)
+		{
+			code[i - count].instruction = instruction;
+			code[i - count].line = code[i].line;
+			for is_removed in &mut removed[i - count + 1..=i] {
+				*is_removed = true;
+			}
+			changed = true;
+		}
+		i += 1;
+	}
+	if changed {
+		compact(code, &removed);
+	}
+	changed
+}
+
+/// Resolves conditional jumps depending on a constant boolean.
+/// Taken jumps become unconditional, jumps never taken are removed.
+fn fold_conditions(code: &mut Vec<Op>, chunk: &Chunk) -> bool {
+	let targets = jump_targets(code);
+	let mut removed = Vec::new();
+	removed.resize(code.len(), false);
+	let mut changed = false;
+
+	for i in 1..code.len() {
+		let (taken_if, target) = match code[i].instruction {
+			Instruction::JmpIfFalse(target) => (false, target),
+			Instruction::JmpIfTrue(target) => (true, target),
+			_ => continue,
+		};
+		if targets[i] || removed[i - 1] {
+			continue;
+		}
+		let Some(ScriptingValue::Boolean(condition)) = constant(&code[i - 1], chunk) else {
+			continue;
+		};
+		if condition == taken_if {
+			code[i].instruction = Instruction::Jmp(target);
+		} else {
+			removed[i] = true;
+		}
+		changed = true;
+	}
+	if changed {
+		compact(code, &removed);
+	}
+	changed
+}
+
+/// Removes instructions following an unconditional jump or a return that are no jump target.
+fn remove_unreachable(code: &mut Vec<Op>) -> bool {
+	let targets = jump_targets(code);
+	let mut removed = Vec::new();
+	removed.resize(code.len(), false);
+	let mut changed = false;
+
+	let mut reachable = true;
+	for (i, op) in code.iter().enumerate() {
+		if targets[i] {
+			reachable = true;
+		}
+		if !reachable {
+			removed[i] = true;
+			changed = true;
+		}
+		if matches!(op.instruction, Instruction::Jmp(_) | Instruction::Return) {
+			reachable = false;
+		}
+	}
+	if changed {
+		compact(code, &removed);
+	}
+	changed
+}
+
+/// Removes unconditional jumps to the next instruction.
+/// Conditional jumps are kept, as they check their condition to be a boolean.
+fn remove_jumps_to_next(code: &mut Vec<Op>) -> bool {
+	let mut removed = Vec::new();
+	removed.resize(code.len(), false);
+	let mut changed = false;
+
+	for (i, op) in code.iter().enumerate() {
+		if matches!(op.instruction, Instruction::Jmp(target) if usize::from(target) == i + 1) {
+			removed[i] = true;
+			changed = true;
+		}
+	}
+	if changed {
+		compact(code, &removed);
+	}
+	changed
+}
+
+/// Removes constants that are immediately removed from the stack again.
+fn remove_pops(code: &mut Vec<Op>, chunk: &Chunk) -> bool {
+	let targets = jump_targets(code);
+	let mut removed = Vec::new();
+	removed.resize(code.len(), false);
+	let mut changed = false;
+
+	for i in 1..code.len() {
+		if code[i].instruction == Instruction::Pop
+			&& !targets[i]
+			&& !removed[i - 1]
+			&& constant(&code[i - 1], chunk).is_some()
+		{
+			removed[i - 1] = true;
+			removed[i] = true;
+			changed = true;
+		}
+	}
+	if changed {
+		compact(code, &removed);
+	}
+	changed
+}
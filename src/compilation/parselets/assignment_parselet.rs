@@ -2,14 +2,13 @@
 //! [`AssignmentParselet`] handles all kinds of assignments.
 
 // region:   	--- modules
-use crate::{
-	compilation::{
-		Lexer, Parser,
-		error::CompilationResult,
-		token::{Token, TokenKind},
-	},
-	execution::{Chunk, op_code::OpCode},
-	scripting_value::ScriptingValue,
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::compilation::{
+	Lexer, Parser,
+	ast::{AssignOp, Expr, ExprKind},
+	error::CompilationResult,
+	token::{Token, TokenKind},
 };
 
 use super::PrefixParselet;
@@ -17,59 +16,67 @@ use super::PrefixParselet;
 
 pub struct AssignmentParselet;
 
-impl PrefixParselet for AssignmentParselet {
-	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, chunk: &mut Chunk, token: Token) -> CompilationResult<()> {
-		let next = parser.next();
-		match next.kind {
-			TokenKind::ColonEqual => {
-				parser.advance(lexer)?;
-				parser.expression(lexer, chunk)?;
-				let name = chunk.add_constant(ScriptingValue::String(token.origin))?;
-				parser.emit_bytes(OpCode::DefineExternal as u8, name, chunk);
-			}
-			TokenKind::PlusEqual => {
-				let name = chunk.add_constant(ScriptingValue::String(token.origin))?;
-				parser.emit_bytes(OpCode::GetExternal as u8, name, chunk);
-				parser.advance(lexer)?;
-				parser.expression(lexer, chunk)?;
-				parser.emit_byte(OpCode::Add as u8, chunk);
-				parser.emit_bytes(OpCode::SetExternal as u8, name, chunk);
-			}
-			TokenKind::MinusEqual => {
-				let name = chunk.add_constant(ScriptingValue::String(token.origin))?;
-				parser.emit_bytes(OpCode::GetExternal as u8, name, chunk);
-				parser.advance(lexer)?;
-				parser.expression(lexer, chunk)?;
-				parser.emit_byte(OpCode::Subtract as u8, chunk);
-				parser.emit_bytes(OpCode::SetExternal as u8, name, chunk);
-			}
-			TokenKind::StarEqual => {
-				let name = chunk.add_constant(ScriptingValue::String(token.origin))?;
-				parser.emit_bytes(OpCode::GetExternal as u8, name, chunk);
-				parser.advance(lexer)?;
-				parser.expression(lexer, chunk)?;
-				parser.emit_byte(OpCode::Multiply as u8, chunk);
-				parser.emit_bytes(OpCode::SetExternal as u8, name, chunk);
-			}
-			TokenKind::SlashEqual => {
-				let name = chunk.add_constant(ScriptingValue::String(token.origin))?;
-				parser.emit_bytes(OpCode::GetExternal as u8, name, chunk);
+impl AssignmentParselet {
+	/// Parses the call of a native function.
+	fn call(lexer: &mut Lexer, parser: &mut Parser, token: &Token) -> CompilationResult<Expr> {
+		// consume the '('
+		parser.advance(lexer)?;
+		let mut args = Vec::new();
+		let mut commas = Vec::new();
+		if !parser.check_next(TokenKind::RightParen) {
+			loop {
+				args.push(parser.expression(lexer)?);
+				if !parser.check_next(TokenKind::Comma) {
+					break;
+				}
 				parser.advance(lexer)?;
-				parser.expression(lexer, chunk)?;
-				parser.emit_byte(OpCode::Divide as u8, chunk);
-				parser.emit_bytes(OpCode::SetExternal as u8, name, chunk);
-			}
-			TokenKind::Equal => {
-				parser.advance(lexer)?;
-				parser.expression(lexer, chunk)?;
-				let name = chunk.add_constant(ScriptingValue::String(token.origin))?;
-				parser.emit_bytes(OpCode::SetExternal as u8, name, chunk);
+				commas.push(parser.span(&parser.current()));
 			}
+		}
+		parser.consume(lexer, TokenKind::RightParen)?;
+
+		let span = parser
+			.span(token)
+			.to(parser.span(&parser.current()));
+		Ok(Expr::new(
+			ExprKind::Call {
+				name: token.origin.as_str().into(),
+				args,
+				commas,
+			},
+			span,
+		))
+	}
+}
+
+impl PrefixParselet for AssignmentParselet {
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, token: Token) -> CompilationResult<Expr> {
+		let op = match parser.next().kind {
+			TokenKind::ColonEqual => AssignOp::Define,
+			TokenKind::Equal => AssignOp::Assign,
+			TokenKind::PlusEqual => AssignOp::Add,
+			TokenKind::MinusEqual => AssignOp::Subtract,
+			TokenKind::StarEqual => AssignOp::Multiply,
+			TokenKind::SlashEqual => AssignOp::Divide,
+			TokenKind::LeftParen => return Self::call(lexer, parser, &token),
 			_ => {
-				let name = chunk.add_constant(ScriptingValue::String(token.origin))?;
-				parser.emit_bytes(OpCode::GetExternal as u8, name, chunk);
+				return Ok(Expr::new(
+					ExprKind::Variable(token.origin.as_str().into()),
+					parser.span(&token),
+				));
 			}
-		}
-		Ok(())
+		};
+		// consume the operator
+		parser.advance(lexer)?;
+		let value = parser.expression(lexer)?;
+		let span = parser.span(&token).to(value.span);
+		Ok(Expr::new(
+			ExprKind::Assign {
+				op,
+				name: token.origin.as_str().into(),
+				value: Box::new(value),
+			},
+			span,
+		))
 	}
 }
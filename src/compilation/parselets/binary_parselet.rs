@@ -1,14 +1,14 @@
 // Copyright © 2025 Stephan Kunz
 //! [`BinaryParselet`] analyzses and handles the binary expressions.
 
-use crate::{
-	compilation::{
-		Lexer, Parser,
-		error::{CompilationError, CompilationResult},
-		precedence::Precedence,
-		token::{Token, TokenKind},
-	},
-	execution::{Chunk, op_code::OpCode},
+use alloc::boxed::Box;
+
+use crate::compilation::{
+	Lexer, Parser,
+	ast::{BinaryOp, Expr, ExprKind},
+	error::{CompilationError, CompilationResult},
+	precedence::Precedence,
+	token::{Token, TokenKind},
 };
 
 use super::InfixParselet;
@@ -24,9 +24,10 @@ impl BinaryParselet {
 }
 
 impl InfixParselet for BinaryParselet {
-	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, chunk: &mut Chunk, _token: Token) -> CompilationResult<()> {
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, left: Expr, token: Token) -> CompilationResult<Expr> {
 		let kind = parser.current().kind;
-		parser.with_precedence(
+		let operator = parser.span(&token);
+		let right = parser.with_precedence(
 			lexer,
 			self.precedence
 				.next_higher()
@@ -34,54 +35,35 @@ impl InfixParselet for BinaryParselet {
 					file: file!().into(),
 					line: line!(),
 				})?,
-			chunk,
 		)?;
-		match kind {
-			TokenKind::BangEqual => {
-				parser.emit_bytes(OpCode::Equal as u8, OpCode::Not as u8, chunk);
-				Ok(())
-			}
-			TokenKind::EqualEqual => {
-				parser.emit_byte(OpCode::Equal as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Greater => {
-				parser.emit_byte(OpCode::Greater as u8, chunk);
-				Ok(())
-			}
-			TokenKind::GreaterEqual => {
-				parser.emit_bytes(OpCode::Less as u8, OpCode::Not as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Less => {
-				parser.emit_byte(OpCode::Less as u8, chunk);
-				Ok(())
-			}
-			TokenKind::LessEqual => {
-				parser.emit_bytes(OpCode::Greater as u8, OpCode::Not as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Plus => {
-				parser.emit_byte(OpCode::Add as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Minus => {
-				parser.emit_byte(OpCode::Subtract as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Star => {
-				parser.emit_byte(OpCode::Multiply as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Slash => {
-				parser.emit_byte(OpCode::Divide as u8, chunk);
-				Ok(())
+		let op = match kind {
+			TokenKind::BangEqual => BinaryOp::NotEqual,
+			TokenKind::EqualEqual => BinaryOp::Equal,
+			TokenKind::Greater => BinaryOp::Greater,
+			TokenKind::GreaterEqual => BinaryOp::GreaterEqual,
+			TokenKind::Less => BinaryOp::Less,
+			TokenKind::LessEqual => BinaryOp::LessEqual,
+			TokenKind::Plus => BinaryOp::Add,
+			TokenKind::Minus => BinaryOp::Subtract,
+			TokenKind::Star => BinaryOp::Multiply,
+			TokenKind::Slash => BinaryOp::Divide,
+			_ => {
+				return Err(CompilationError::Unreachable {
+					file: file!().into(),
+					line: line!(),
+				});
 			}
-			_ => Err(CompilationError::Unreachable {
-				file: file!().into(),
-				line: line!(),
-			}),
-		}
+		};
+		let span = left.span.to(right.span);
+		Ok(Expr::new(
+			ExprKind::Binary {
+				op,
+				operator,
+				left: Box::new(left),
+				right: Box::new(right),
+			},
+			span,
+		))
 	}
 
 	fn get_precedence(&self) -> Precedence {
@@ -0,0 +1,38 @@
+// Copyright © 2026 Stephan Kunz
+//! [`CastParselet`] handles type conversions with `as`.
+
+use alloc::boxed::Box;
+
+use crate::compilation::{
+	Lexer, Parser,
+	ast::{Expr, ExprKind},
+	error::CompilationResult,
+	precedence::Precedence,
+	token::{Token, TokenKind},
+};
+
+use super::InfixParselet;
+
+/// `value as int` is compiled into a call of the conversion function `int(value)`.
+pub struct CastParselet;
+
+impl InfixParselet for CastParselet {
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, left: Expr, token: Token) -> CompilationResult<Expr> {
+		let operator = parser.span(&token);
+		parser.consume(lexer, TokenKind::Ident)?;
+		let type_name = parser.current();
+		let span = left.span.to(parser.span(&type_name));
+		Ok(Expr::new(
+			ExprKind::Cast {
+				value: Box::new(left),
+				operator,
+				type_name: type_name.origin.as_str().into(),
+			},
+			span,
+		))
+	}
+
+	fn get_precedence(&self) -> Precedence {
+		Precedence::Cast
+	}
+}
@@ -1,13 +1,13 @@
 // Copyright © 2025 Stephan Kunz
 //! [`GroupingParselet`] handles parentheses.
 
-use crate::{
-	compilation::{
-		Lexer, Parser,
-		error::CompilationResult,
-		token::{Token, TokenKind},
-	},
-	execution::Chunk,
+use alloc::boxed::Box;
+
+use crate::compilation::{
+	Lexer, Parser,
+	ast::{Expr, ExprKind},
+	error::CompilationResult,
+	token::{Token, TokenKind},
 };
 
 use super::PrefixParselet;
@@ -15,8 +15,12 @@ use super::PrefixParselet;
 pub struct GroupingParselet;
 
 impl PrefixParselet for GroupingParselet {
-	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, chunk: &mut Chunk, _token: Token) -> CompilationResult<()> {
-		parser.expression(lexer, chunk)?;
-		parser.consume(lexer, TokenKind::RightParen)
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, token: Token) -> CompilationResult<Expr> {
+		let inner = parser.expression(lexer)?;
+		parser.consume(lexer, TokenKind::RightParen)?;
+		let span = parser
+			.span(&token)
+			.to(parser.span(&parser.current()));
+		Ok(Expr::new(ExprKind::Grouping(Box::new(inner)), span))
 	}
 }
@@ -1,13 +1,11 @@
 // Copyright © 2025 Stephan Kunz
 //! [`LiteralParselet`] handles any language literal like 'true' and 'false'
 
-use crate::{
-	compilation::{
-		Lexer, Parser,
-		error::{CompilationError, CompilationResult},
-		token::{Token, TokenKind},
-	},
-	execution::{Chunk, op_code::OpCode},
+use crate::compilation::{
+	Lexer, Parser,
+	ast::{Expr, ExprKind, Literal},
+	error::{CompilationError, CompilationResult},
+	token::{Token, TokenKind},
 };
 
 use super::PrefixParselet;
@@ -15,26 +13,18 @@ use super::PrefixParselet;
 pub struct LiteralParselet;
 
 impl PrefixParselet for LiteralParselet {
-	fn parse(&self, _lexer: &mut Lexer, parser: &mut Parser, chunk: &mut Chunk, _token: Token) -> CompilationResult<()> {
-		let kind = parser.current().kind;
-
-		match kind {
-			TokenKind::False => {
-				parser.emit_byte(OpCode::False as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Nil => {
-				parser.emit_byte(OpCode::Nil as u8, chunk);
-				Ok(())
-			}
-			TokenKind::True => {
-				parser.emit_byte(OpCode::True as u8, chunk);
-				Ok(())
+	fn parse(&self, _lexer: &mut Lexer, parser: &mut Parser, token: Token) -> CompilationResult<Expr> {
+		let literal = match token.kind {
+			TokenKind::False => Literal::Boolean(false),
+			TokenKind::Nil => Literal::Nil,
+			TokenKind::True => Literal::Boolean(true),
+			_ => {
+				return Err(CompilationError::Unreachable {
+					file: file!().into(),
+					line: line!(),
+				});
 			}
-			_ => Err(CompilationError::Unreachable {
-				file: file!().into(),
-				line: line!(),
-			}),
-		}
+		};
+		Ok(Expr::new(ExprKind::Literal(literal), parser.span(&token)))
 	}
 }
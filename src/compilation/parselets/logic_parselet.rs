@@ -1,14 +1,14 @@
 // Copyright © 2025 Stephan Kunz
 //! [`LogicParselet`] analyzes and handles logical expressions.
 
-use crate::{
-	compilation::{
-		Lexer, Parser,
-		error::{CompilationError, CompilationResult},
-		precedence::Precedence,
-		token::{Token, TokenKind},
-	},
-	execution::{Chunk, op_code::OpCode},
+use alloc::boxed::Box;
+
+use crate::compilation::{
+	Lexer, Parser,
+	ast::{BinaryOp, Expr, ExprKind, LogicalOp},
+	error::{CompilationError, CompilationResult},
+	precedence::Precedence,
+	token::{Token, TokenKind},
 };
 
 use super::InfixParselet;
@@ -21,130 +21,91 @@ impl LogicParselet {
 	pub const fn new(precedence: Precedence) -> Self {
 		Self { precedence }
 	}
+
+	/// Parse the right hand side with the next higher precedence.
+	fn operand(&self, lexer: &mut Lexer, parser: &mut Parser) -> CompilationResult<Expr> {
+		parser.with_precedence(
+			lexer,
+			self.precedence
+				.next_higher()
+				.ok_or_else(|| CompilationError::Unreachable {
+					file: file!().into(),
+					line: line!(),
+				})?,
+		)
+	}
 }
 
 impl InfixParselet for LogicParselet {
-	#[allow(clippy::too_many_lines)]
-	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, chunk: &mut Chunk, _token: Token) -> CompilationResult<()> {
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, left: Expr, token: Token) -> CompilationResult<Expr> {
 		// The bitwise logic does not return a boolean result but an integer
 		// and resembles therefore more how arithmetic operations work.
 		// The QMark Colon expression is special again.
 		let kind = parser.current().kind;
-		match kind {
-			TokenKind::Ampersand => {
-				parser.with_precedence(
-					lexer,
-					self.precedence
-						.next_higher()
-						.ok_or_else(|| CompilationError::Unreachable {
-							file: file!().into(),
-							line: line!(),
-						})?,
-					chunk,
-				)?;
-				parser.emit_byte(OpCode::BitwiseAnd as u8, chunk);
-				Ok(())
-			}
-			TokenKind::And => {
-				let target_pos = parser.emit_jump(OpCode::JmpIfFalse as u8, chunk);
-				parser.emit_byte(OpCode::Pop as u8, chunk);
-				parser.with_precedence(
-					lexer,
-					self.precedence
-						.next_higher()
-						.ok_or_else(|| CompilationError::Unreachable {
-							file: file!().into(),
-							line: line!(),
-						})?,
-					chunk,
-				)?;
-				Parser::patch_jump(target_pos, chunk);
-				Ok(())
-			}
-			TokenKind::Caret => {
-				parser.with_precedence(
-					lexer,
-					self.precedence
-						.next_higher()
-						.ok_or_else(|| CompilationError::Unreachable {
-							file: file!().into(),
-							line: line!(),
-						})?,
-					chunk,
-				)?;
-				parser.emit_byte(OpCode::BitwiseXor as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Or => {
-				let target_pos = parser.emit_jump(OpCode::JmpIfTrue as u8, chunk);
-				parser.emit_byte(OpCode::Pop as u8, chunk);
-				parser.with_precedence(
-					lexer,
-					self.precedence
-						.next_higher()
-						.ok_or_else(|| CompilationError::Unreachable {
-							file: file!().into(),
-							line: line!(),
-						})?,
-					chunk,
-				)?;
-				Parser::patch_jump(target_pos, chunk);
-				Ok(())
-			}
-			TokenKind::Pipe => {
-				parser.with_precedence(
-					lexer,
-					self.precedence
-						.next_higher()
-						.ok_or_else(|| CompilationError::Unreachable {
-							file: file!().into(),
-							line: line!(),
-						})?,
-					chunk,
-				)?;
-				parser.emit_byte(OpCode::BitwiseOr as u8, chunk);
-				Ok(())
-			}
-			TokenKind::QMark => {
-				let else_pos = parser.emit_jump(OpCode::JmpIfFalse as u8, chunk);
-				// remove the decision value
-				parser.emit_byte(OpCode::Pop as u8, chunk);
-				// run the "true" expression
-				parser.with_precedence(
-					lexer,
-					self.precedence
-						.next_higher()
-						.ok_or_else(|| CompilationError::Unreachable {
-							file: file!().into(),
-							line: line!(),
-						})?,
-					chunk,
-				)?;
-				let end_pos = parser.emit_jump(OpCode::Jmp as u8, chunk);
-				Parser::patch_jump(else_pos, chunk);
-				// consume the ':'
-				parser.consume(lexer, TokenKind::Colon)?;
-				// remove the decision value
-				parser.emit_byte(OpCode::Pop as u8, chunk);
-				// run the "false" expression
-				parser.with_precedence(
-					lexer,
-					self.precedence
-						.next_higher()
-						.ok_or_else(|| CompilationError::Unreachable {
-							file: file!().into(),
-							line: line!(),
-						})?,
-					chunk,
-				)?;
-				Parser::patch_jump(end_pos, chunk);
-				Ok(())
-			}
-			_ => Err(CompilationError::Unreachable {
-				file: file!().into(),
-				line: line!(),
-			}),
+		let operator = parser.span(&token);
+		if kind == TokenKind::QMark {
+			// the "true" expression
+			let then = self.operand(lexer, parser)?;
+			// consume the ':'
+			parser.consume(lexer, TokenKind::Colon)?;
+			let colon = parser.span(&parser.current());
+			// the "false" expression
+			let otherwise = self.operand(lexer, parser)?;
+			let span = left.span.to(otherwise.span);
+			return Ok(Expr::new(
+				ExprKind::Ternary {
+					condition: Box::new(left),
+					then: Box::new(then),
+					otherwise: Box::new(otherwise),
+					question: operator,
+					colon,
+				},
+				span,
+			));
 		}
+
+		let right = self.operand(lexer, parser)?;
+		let span = left.span.to(right.span);
+		let (left, right) = (Box::new(left), Box::new(right));
+		let kind = match kind {
+			TokenKind::Ampersand => ExprKind::Binary {
+				op: BinaryOp::BitwiseAnd,
+				operator,
+				left,
+				right,
+			},
+			TokenKind::And => ExprKind::Logical {
+				op: LogicalOp::And,
+				operator,
+				left,
+				right,
+			},
+			TokenKind::Caret => ExprKind::Binary {
+				op: BinaryOp::BitwiseXor,
+				operator,
+				left,
+				right,
+			},
+			TokenKind::Or => ExprKind::Logical {
+				op: LogicalOp::Or,
+				operator,
+				left,
+				right,
+			},
+			TokenKind::Pipe => ExprKind::Binary {
+				op: BinaryOp::BitwiseOr,
+				operator,
+				left,
+				right,
+			},
+			_ => {
+				return Err(CompilationError::Unreachable {
+					file: file!().into(),
+					line: line!(),
+				});
+			}
+		};
+		Ok(Expr::new(kind, span))
 	}
 
 	fn get_precedence(&self) -> Precedence {
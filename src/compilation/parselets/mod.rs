@@ -3,25 +3,26 @@
 
 mod assignment_parselet;
 mod binary_parselet;
+mod cast_parselet;
 mod grouping_parselet;
 mod literal_parselet;
 mod logic_parselet;
+mod template_parselet;
 mod unary_parselet;
 mod value_parselet;
 
 // flatten
 pub use assignment_parselet::AssignmentParselet;
 pub use binary_parselet::BinaryParselet;
+pub use cast_parselet::CastParselet;
 pub use grouping_parselet::GroupingParselet;
 pub use literal_parselet::LiteralParselet;
 pub use logic_parselet::LogicParselet;
+pub use template_parselet::TemplateParselet;
 pub use unary_parselet::UnaryParselet;
 pub use value_parselet::ValueParselet;
 
-use crate::{
-	compilation::{Parser, error::CompilationResult},
-	execution::Chunk,
-};
+use crate::compilation::{Parser, ast::Expr, error::CompilationResult};
 
 use super::{Lexer, precedence::Precedence, token::Token};
 
@@ -32,8 +33,8 @@ use super::{Lexer, precedence::Precedence, token::Token};
 /// This interface is also used for single-token expressions like variables, in
 /// which case `parse()` simply doesn't consume any more tokens.
 pub trait PrefixParselet: Send + Sync {
-	/// Parse the token
-	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, chunk: &mut Chunk, token: Token) -> CompilationResult<()>;
+	/// Parse the token into an [`Expr`]
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, token: Token) -> CompilationResult<Expr>;
 }
 
 /// Interfaces used by the Pratt parser. An `InfixParselet` is
@@ -43,8 +44,8 @@ pub trait PrefixParselet: Send + Sync {
 /// This interface is also used for postfix expressions, in
 /// which case `parse()` simply doesn't consume any more tokens.
 pub trait InfixParselet: Send + Sync {
-	/// Parse the token together with the left hand expression
-	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, chunk: &mut Chunk, token: Token) -> CompilationResult<()>;
+	/// Parse the token together with the left hand expression into an [`Expr`]
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, left: Expr, token: Token) -> CompilationResult<Expr>;
 
 	/// Get the precedence the parselet is executed with.
 	fn get_precedence(&self) -> Precedence;
@@ -0,0 +1,88 @@
+// Copyright © 2026 Stephan Kunz
+//! [`TemplateParselet`] handles template strings with interpolated expressions.
+
+// region:   	--- modules
+use alloc::{string::String, vec::Vec};
+
+use crate::compilation::{
+	Lexer, Parser,
+	ast::{Expr, ExprKind, TemplatePart},
+	error::{CompilationError, CompilationResult},
+	token::Token,
+};
+
+use super::PrefixParselet;
+// endregion:  	--- modules
+
+/// A template like `` `pos: {x}, {y}` `` is split into its constant and expression pieces,
+/// which are joined by [`OpCode::Concat`](crate::execution::op_code::OpCode::Concat).
+/// Literal braces are written as `{{` and `}}`, braces within strings of an expression are part of the expression.
+pub struct TemplateParselet;
+
+impl TemplateParselet {
+	fn invalid(token: &Token) -> CompilationError {
+		CompilationError::InvalidInterpolation {
+			token: token.origin.as_str().into(),
+			pos: token.line,
+		}
+	}
+
+	/// The offset of the `}` closing an embedded expression,
+	/// braces within strings and nested braces are skipped.
+	fn expression_end(expression: &str) -> Option<usize> {
+		let mut depth = 0_usize;
+		let mut in_string = false;
+		for (idx, c) in expression.char_indices() {
+			match c {
+				'\'' => in_string = !in_string,
+				_ if in_string => {}
+				'{' => depth += 1,
+				'}' if depth == 0 => return Some(idx),
+				'}' => depth -= 1,
+				_ => {}
+			}
+		}
+		None
+	}
+}
+
+impl PrefixParselet for TemplateParselet {
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, token: Token) -> CompilationResult<Expr> {
+		let template = token.origin.as_str();
+		let span = parser.span(&token);
+		let mut literal = String::new();
+		let mut parts = Vec::new();
+
+		let mut chars = template.char_indices().peekable();
+		while let Some((idx, c)) = chars.next() {
+			match c {
+				'{' if chars.next_if(|(_, c)| *c == '{').is_some() => literal.push('{'),
+				'}' if chars.next_if(|(_, c)| *c == '}').is_some() => literal.push('}'),
+				'{' => {
+					let start = idx + 1;
+					let Some(len) = Self::expression_end(&template[start..]) else {
+						return Err(Self::invalid(&token));
+					};
+					let end = start + len;
+					// skip the embedded expression including the closing brace
+					while chars.next_if(|(i, _)| *i <= end).is_some() {}
+
+					if !literal.is_empty() {
+						parts.push(TemplatePart::Text(core::mem::take(&mut literal).into()));
+					}
+					let mut embedded = Lexer::new(lexer.enums(), &template[start..end]);
+					embedded.set_line(token.line + template[..start].matches('\n').count());
+					// the expression starts behind the leading '`' and the '{'
+					let expr = parser.embedded_expression(&mut embedded, span.start + 1 + start)?;
+					parts.push(TemplatePart::Expr(expr));
+				}
+				'}' => return Err(Self::invalid(&token)),
+				c => literal.push(c),
+			}
+		}
+		if !literal.is_empty() {
+			parts.push(TemplatePart::Text(literal.into()));
+		}
+		Ok(Expr::new(ExprKind::Template(parts), span))
+	}
+}
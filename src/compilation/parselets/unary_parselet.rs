@@ -1,14 +1,14 @@
 // Copyright © 2025 Stephan Kunz
 //! [`UnaryParselet`] analyzes and handles the prefix (aka unary) expressions.
 
-use crate::{
-	compilation::{
-		Lexer, Parser,
-		error::{CompilationError, CompilationResult},
-		precedence::Precedence,
-		token::{Token, TokenKind},
-	},
-	execution::{Chunk, op_code::OpCode},
+use alloc::{boxed::Box, string::ToString};
+
+use crate::compilation::{
+	Lexer, Parser,
+	ast::{Expr, ExprKind, UnaryOp},
+	error::{CompilationError, CompilationResult},
+	precedence::Precedence,
+	token::{Token, TokenKind},
 };
 
 use super::PrefixParselet;
@@ -16,41 +16,40 @@ use super::PrefixParselet;
 pub struct UnaryParselet;
 
 impl PrefixParselet for UnaryParselet {
-	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, chunk: &mut Chunk, _token: Token) -> CompilationResult<()> {
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, _token: Token) -> CompilationResult<Expr> {
 		let token = parser.current();
 		// there must be a current token
 		if parser.next().kind == TokenKind::None {
 			return Err(CompilationError::ExpressionExpected {
-				token: "None".into(),
+				token: TokenKind::None.to_string().into(),
 				pos: parser.next().line,
 			});
 		}
-		// compile the operand
-		parser.with_precedence(lexer, Precedence::Unary, chunk)?;
-		match token.kind {
-			TokenKind::Bang => {
-				// add the logical not
-				parser.emit_byte(OpCode::Not as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Minus => {
-				// add the negation
-				parser.emit_byte(OpCode::Negate as u8, chunk);
-				Ok(())
-			}
-			TokenKind::Plus => {
-				// do nothing
-				Ok(())
+		let op = match token.kind {
+			// logical not
+			TokenKind::Bang => UnaryOp::Not,
+			// negation
+			TokenKind::Minus => UnaryOp::Negate,
+			// does nothing
+			TokenKind::Plus => UnaryOp::Plus,
+			// binary not
+			TokenKind::Tilde => UnaryOp::BitwiseNot,
+			_ => {
+				return Err(CompilationError::Unreachable {
+					file: file!().into(),
+					line: line!(),
+				});
 			}
-			TokenKind::Tilde => {
-				// add the binary not
-				parser.emit_byte(OpCode::BitwiseNot as u8, chunk);
-				Ok(())
-			}
-			_ => Err(CompilationError::Unreachable {
-				file: file!().into(),
-				line: line!(),
-			}),
-		}
+		};
+		// parse the operand
+		let operand = parser.with_precedence(lexer, Precedence::Unary)?;
+		let span = parser.span(&token).to(operand.span);
+		Ok(Expr::new(
+			ExprKind::Unary {
+				op,
+				operand: Box::new(operand),
+			},
+			span,
+		))
 	}
 }
@@ -2,14 +2,11 @@
 //! [`ValueParselet`] analyzes and handles value tokens like numbers.
 
 // region:   	--- modules
-use crate::{
-	compilation::{
-		Lexer, Parser,
-		error::{CompilationError, CompilationResult},
-		token::{Token, TokenKind},
-	},
-	execution::{Chunk, op_code::OpCode},
-	scripting_value::ScriptingValue,
+use crate::compilation::{
+	Lexer, Parser,
+	ast::{Expr, ExprKind, Literal},
+	error::{CompilationError, CompilationResult},
+	token::{Token, TokenKind},
 };
 
 use super::PrefixParselet;
@@ -18,8 +15,8 @@ use super::PrefixParselet;
 pub struct ValueParselet;
 
 impl PrefixParselet for ValueParselet {
-	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, chunk: &mut Chunk, token: Token) -> CompilationResult<()> {
-		match token.kind {
+	fn parse(&self, lexer: &mut Lexer, parser: &mut Parser, token: Token) -> CompilationResult<Expr> {
+		let kind = match token.kind {
 			TokenKind::Enum => {
 				let Some(value) = lexer.enums().get(&token.origin) else {
 					return Err(CompilationError::EnumValNotFound {
@@ -27,9 +24,10 @@ impl PrefixParselet for ValueParselet {
 						pos: token.line,
 					});
 				};
-				let offset = chunk.add_constant(ScriptingValue::Int64(i64::from(*value)))?;
-				parser.emit_bytes(OpCode::Constant as u8, offset, chunk);
-				Ok(())
+				ExprKind::Enum {
+					name: token.origin.as_str().into(),
+					value: *value,
+				}
 			}
 			TokenKind::FloatNumber => {
 				let double: f64 = match token.origin.parse() {
@@ -41,10 +39,7 @@ impl PrefixParselet for ValueParselet {
 						});
 					}
 				};
-
-				let offset = chunk.add_constant(ScriptingValue::Float64(double))?;
-				parser.emit_bytes(OpCode::Constant as u8, offset, chunk);
-				Ok(())
+				ExprKind::Literal(Literal::Float(double))
 			}
 			TokenKind::HexNumber => {
 				// remove the '0x' before parsing
@@ -55,9 +50,7 @@ impl PrefixParselet for ValueParselet {
 						pos: token.line,
 					});
 				};
-				let offset = chunk.add_constant(ScriptingValue::Int64(value))?;
-				parser.emit_bytes(OpCode::Constant as u8, offset, chunk);
-				Ok(())
+				ExprKind::Literal(Literal::Int(value))
 			}
 			TokenKind::IntNumber => {
 				let Ok(value) = token.origin.parse::<i64>() else {
@@ -66,19 +59,16 @@ impl PrefixParselet for ValueParselet {
 						pos: token.line,
 					});
 				};
-				let offset = chunk.add_constant(ScriptingValue::Int64(value))?;
-				parser.emit_bytes(OpCode::Constant as u8, offset, chunk);
-				Ok(())
+				ExprKind::Literal(Literal::Int(value))
 			}
-			TokenKind::String => {
-				let offset = chunk.add_constant(ScriptingValue::String(token.origin))?;
-				parser.emit_bytes(OpCode::Constant as u8, offset, chunk);
-				Ok(())
+			TokenKind::String => ExprKind::Literal(Literal::String(token.origin.as_str().into())),
+			_ => {
+				return Err(CompilationError::Unreachable {
+					file: file!().into(),
+					line: line!(),
+				});
 			}
-			_ => Err(CompilationError::Unreachable {
-				file: file!().into(),
-				line: line!(),
-			}),
-		}
+		};
+		Ok(Expr::new(kind, parser.span(&token)))
 	}
 }
@@ -22,17 +22,21 @@
 //! equality    → comparison ( ( "!=" | "==" ) comparison )* ;
 //! comparison  → term ( ( ">" | ">=" | "<" | "<=" ) term )* ;
 //! term        → factor ( ( "-" | "+" ) factor )* ;
-//! factor      → unary ( ( "/" | "*" ) unary )* ;
+//! factor      → cast ( ( "/" | "*" ) cast )* ;
+//! cast        → unary ( "as" TYPE )* ;
 //! unary       → ( "!" | "-" | "~") unary | primary ;
-//! primary     → "true" | "false" | FLOATNUMBER | HEXNUMBER| INTNUMBER  | STRING | IDENTIFIER | "(" expression ")" ;
+//! primary     → "true" | "false" | FLOATNUMBER | HEXNUMBER| INTNUMBER  | STRING | TEMPLATE | IDENTIFIER | call | "(" expression ")" ;
+//! call        → IDENTIFIER "(" ( expression ( "," expression )* )? ")" ;
 //!
 //! FLOATNUMBER → DIGIT+ ( "." DIGIT+ ) ;
 //! HEXNUMBER   → (0x | 0X) + (DIGIT+ | "a" ... "f"+ | "A" ... "F"+ );
 //! INTNUMBER   → ( DIGIT+ ) ;
 //! STRING      → "\'" <any char except "\'">* "\'" ;
+//! TEMPLATE    → "`" ( <any char except "`", "{" and "}"> | "{{" | "}}" | "{" expression "}" )* "`" ;
 //! IDENTIFIER  → ALPHA ( ALPHA | DIGIT )* ;
 //! ALPHA       → "a" ... "z" | "A" ... "Z" | "_" ;
 //! DIGIT       → "0" ... "9" ;
+//! TYPE        → "bool" | "float" | "int" | "str" | "string" ;
 //! ```
 
 #[doc(hidden)]
@@ -44,31 +48,38 @@ use alloc::{
 	collections::btree_map::BTreeMap,
 	string::{String, ToString},
 	sync::Arc,
+	vec::Vec,
 };
 
-use crate::{
-	compilation::Lexer,
-	execution::{Chunk, op_code::OpCode},
-};
+use crate::{compilation::Lexer, execution::Chunk, library::Library};
 
 use super::{
+	ast::{Expr, Span, Stmt, StmtKind},
+	compiler::Compiler,
 	error::{CompilationError, CompilationResult},
 	parselets::{
-		AssignmentParselet, BinaryParselet, GroupingParselet, InfixParselet, LiteralParselet, LogicParselet, PrefixParselet,
-		UnaryParselet, ValueParselet,
+		AssignmentParselet, BinaryParselet, CastParselet, GroupingParselet, InfixParselet, LiteralParselet, LogicParselet,
+		PrefixParselet, TemplateParselet, UnaryParselet, ValueParselet,
 	},
 	precedence::Precedence,
 	token::{Token, TokenKind},
+	type_checker::Schema,
 };
 // endregion:	--- modules
 
 // region:		--- Parser
 /// Parser implementation.
+///
+/// The parselets create the abstract syntax tree, which the [`Compiler`] translates into a [`Chunk`].
 pub struct Parser {
 	/// Map of prefix parselets.
 	prefix_parselets: BTreeMap<TokenKind, Arc<dyn PrefixParselet>>,
 	/// Map of infix parselets.
 	infix_parselets: BTreeMap<TokenKind, Arc<dyn InfixParselet>>,
+	/// Compiler for the created abstract syntax trees.
+	compiler: Compiler,
+	/// Byte offset of the parsed source within the script, used for embedded expressions.
+	base: usize,
 	/// Current handled Token.
 	current: Token,
 	/// Preview on next Token.
@@ -95,12 +106,22 @@ impl Default for Parser {
 
 impl Parser {
 	/// Create a Parser with all the necessary ingredients
+	/// and the default [`Library`].
 	#[must_use]
-	#[allow(clippy::too_many_lines)]
 	pub fn new() -> Self {
+		Self::with_library(Arc::new(Library::default()))
+	}
+
+	/// Create a Parser with all the necessary ingredients
+	/// and the given [`Library`].
+	#[must_use]
+	#[allow(clippy::too_many_lines)]
+	pub fn with_library(library: Arc<Library>) -> Self {
 		let mut parser = Self {
 			prefix_parselets: BTreeMap::default(),
 			infix_parselets: BTreeMap::default(),
+			compiler: Compiler::with_library(library),
+			base: 0,
 			current: Token::none(),
 			next: Token::none(),
 		};
@@ -109,6 +130,9 @@ impl Parser {
 		parser
 			.infix_parselets
 			.insert(TokenKind::Ampersand, Arc::from(LogicParselet::new(Precedence::BitAnd)));
+		parser
+			.infix_parselets
+			.insert(TokenKind::As, Arc::from(CastParselet));
 		parser
 			.infix_parselets
 			.insert(TokenKind::And, Arc::from(LogicParselet::new(Precedence::And)));
@@ -190,6 +214,9 @@ impl Parser {
 		parser
 			.prefix_parselets
 			.insert(TokenKind::String, Arc::from(ValueParselet));
+		parser
+			.prefix_parselets
+			.insert(TokenKind::Template, Arc::from(TemplateParselet));
 		parser
 			.prefix_parselets
 			.insert(TokenKind::Tilde, Arc::from(UnaryParselet));
@@ -206,26 +233,80 @@ impl Parser {
 	/// - passes [`Lexer`] errors through
 	/// - if it could not create a proper [`Chunk`]
 	pub fn parse(&mut self, enums: &BTreeMap<String, i8>, source_code: &str) -> CompilationResult<Chunk> {
-		let mut chunk = Chunk::default();
+		let statements = self.parse_ast(enums, source_code)?;
+		self.compiler.compile(&statements)
+	}
+
+	/// Create the abstract syntax tree from source
+	/// # Errors
+	/// - passes [`Lexer`] errors through
+	/// - if the source is not a valid script, the first error is returned
+	pub fn parse_ast(&mut self, enums: &BTreeMap<String, i8>, source_code: &str) -> CompilationResult<Vec<Stmt>> {
 		let mut lexer = Lexer::new(enums, source_code);
+		self.base = 0;
 
 		self.advance(&mut lexer)?;
-		while !self.check_next(TokenKind::None) {
+		let mut statements = Vec::new();
+		let mut first_error = None;
+		'statements: while !self.check_next(TokenKind::None) {
 			// in case of error try to synchronize to next statement
-			if let Err(_error) = self.statement(&mut lexer, &mut chunk) {
-				while !(self.check_next(TokenKind::Semicolon)
-					|| self.check_next(TokenKind::Print)
-					|| self.check_next(TokenKind::None))
-				{
-					self.advance(&mut lexer)?;
+			match self.statement(&mut lexer) {
+				Ok(statement) => statements.push(statement),
+				Err(error) => {
+					first_error.get_or_insert(error);
+					while !(self.check_next(TokenKind::Semicolon)
+						|| self.check_next(TokenKind::Print)
+						|| self.check_next(TokenKind::Yield)
+						|| self.check_next(TokenKind::None))
+					{
+						// a lexer error while synchronizing is a consequence of the first error
+						if self.advance(&mut lexer).is_err() {
+							break 'statements;
+						}
+					}
+					if self.check_next(TokenKind::Semicolon) && self.advance(&mut lexer).is_err() {
+						break 'statements;
+					}
 				}
 			}
 		}
+		if let Some(error) = first_error {
+			return Err(error);
+		}
+		Ok(statements)
+	}
+
+	/// Enable or disable the optimization of the created [`Chunk`]s, it is enabled by default.
+	pub const fn set_optimization(&mut self, enabled: bool) {
+		self.compiler.set_optimization(enabled);
+	}
+
+	/// Check whether the created [`Chunk`]s are optimized.
+	#[must_use]
+	pub const fn optimization(&self) -> bool {
+		self.compiler.optimization()
+	}
 
-		// end compiler
-		self.emit_byte(OpCode::Return as u8, &mut chunk);
-		chunk.finalize();
-		Ok(chunk)
+	/// Set the [`Schema`] to check the types of parsed scripts against, see [`Compiler::set_schema`].
+	pub fn set_schema(&mut self, schema: Option<Schema>) {
+		self.compiler.set_schema(schema);
+	}
+
+	/// Access the [`Schema`] the types are checked against.
+	#[must_use]
+	pub fn schema(&self) -> Option<&Schema> {
+		self.compiler.schema()
+	}
+
+	/// Access the [`Library`].
+	#[must_use]
+	pub const fn library(&self) -> &Arc<Library> {
+		self.compiler.library()
+	}
+
+	/// Mutable access to the [`Library`].
+	pub fn library_mut(&mut self) -> &mut Library {
+		self.compiler.library_mut()
 	}
 
 	pub(super) fn current(&self) -> Token {
@@ -246,7 +327,11 @@ impl Parser {
 			// passthrough of lexer errors
 			self.next = token?;
 		} else {
-			self.next = Token::none();
+			// the end of the source is on its last line
+			self.next = Token {
+				line: lexer.line(),
+				..Token::none()
+			};
 		}
 		//std::println!("{}", self.current.kind);
 		Ok(())
@@ -272,97 +357,94 @@ impl Parser {
 		self.next.kind == kind
 	}
 
-	pub(super) fn emit_byte(&self, byte: u8, chunk: &mut Chunk) {
-		chunk.write(byte, self.current.line);
-	}
-
-	pub(super) fn emit_bytes(&self, byte1: u8, byte2: u8, chunk: &mut Chunk) {
-		chunk.write(byte1, self.current.line);
-		chunk.write(byte2, self.current.line);
-	}
-
-	pub(super) fn emit_jump(&self, instruction: u8, chunk: &mut Chunk) -> usize {
-		chunk.write(instruction, self.current.line);
-		let target_pos = chunk.code().len();
-		// the dummy address bytes
-		chunk.write(0xFF, self.current.line);
-		chunk.write(0xFF, self.current.line);
-		target_pos
+	/// The [`Span`] of a token.
+	pub(super) fn span(&self, token: &Token) -> Span {
+		let lines = if token.kind == TokenKind::Template {
+			token.origin.matches('\n').count()
+		} else {
+			0
+		};
+		Span {
+			start: self.base + token.offset,
+			end: self.base + token.end(),
+			line: token.line,
+			end_line: token.line + lines,
+		}
 	}
 
-	#[allow(clippy::cast_possible_truncation)]
-	pub(super) fn patch_jump(patch_pos: usize, chunk: &mut Chunk) {
-		let target = chunk.code().len();
-		let byte1 = (target >> 8) as u8;
-		let byte2 = target as u8;
-		chunk.patch(byte1, patch_pos);
-		chunk.patch(byte2, patch_pos + 1);
+	/// Consume the `;` finishing a statement, which may also be finished by EOF.
+	fn finish_statement(&mut self, lexer: &mut Lexer) -> CompilationResult<()> {
+		if !self.check_next(TokenKind::None) {
+			self.consume(lexer, TokenKind::Semicolon)?;
+		}
+		Ok(())
 	}
 
-	pub(super) fn statement(&mut self, lexer: &mut Lexer, chunk: &mut Chunk) -> CompilationResult<()> {
-		if self.next.kind == TokenKind::Print {
+	pub(super) fn statement(&mut self, lexer: &mut Lexer) -> CompilationResult<Stmt> {
+		let (kind, start) = if self.next.kind == TokenKind::Print {
 			self.advance(lexer)?;
-			self.expression(lexer, chunk)?;
-			// a statement my also be finished by EOF
-			if !self.check_next(TokenKind::None) {
-				self.consume(lexer, TokenKind::Semicolon)?;
-			}
-			#[cfg(feature = "std")]
-			self.emit_byte(OpCode::Print as u8, chunk);
-			#[cfg(not(feature = "std"))]
-			self.emit_byte(OpCode::Pop as u8, chunk);
+			let start = self.span(&self.current);
+			(StmtKind::Print(self.expression(lexer)?), start)
+		} else if self.next.kind == TokenKind::Yield {
+			self.advance(lexer)?;
+			(StmtKind::Yield, self.span(&self.current))
 		} else {
-			self.expression(lexer, chunk)?;
-			if !self.check_next(TokenKind::None) {
-				self.consume(lexer, TokenKind::Semicolon)?;
-			}
-			//self.emit_byte(OP_POP, chunk);
-		}
-		Ok(())
+			let expr = self.expression(lexer)?;
+			let start = expr.span;
+			(StmtKind::Expression(expr), start)
+		};
+		self.finish_statement(lexer)?;
+		Ok(Stmt::new(kind, start.to(self.span(&self.current))))
 	}
 
-	pub(super) fn expression(&mut self, lexer: &mut Lexer, chunk: &mut Chunk) -> CompilationResult<()> {
-		self.with_precedence(lexer, Precedence::Assignment, chunk)
+	pub(super) fn expression(&mut self, lexer: &mut Lexer) -> CompilationResult<Expr> {
+		self.with_precedence(lexer, Precedence::Assignment)
+	}
+
+	/// Parse a complete expression from a separate [`Lexer`], e.g. one embedded in a template string,
+	/// which starts at byte offset `base` within the script.
+	/// The token state of the surrounding source is restored afterwards.
+	/// # Errors
+	/// - if the source is not exactly one expression
+	pub(super) fn embedded_expression(&mut self, lexer: &mut Lexer, base: usize) -> CompilationResult<Expr> {
+		let current = core::mem::replace(&mut self.current, Token::none());
+		let next = core::mem::replace(&mut self.next, Token::none());
+		let outer_base = core::mem::replace(&mut self.base, base);
+		let res = self
+			.advance(lexer)
+			.and_then(|()| self.expression(lexer))
+			.and_then(|expr| {
+				self.consume(lexer, TokenKind::None)?;
+				Ok(expr)
+			});
+		self.current = current;
+		self.next = next;
+		self.base = outer_base;
+		res
 	}
 
-	#[allow(clippy::expect_used)]
-	pub(super) fn with_precedence(
-		&mut self,
-		lexer: &mut Lexer,
-		precedence: Precedence,
-		chunk: &mut Chunk,
-	) -> CompilationResult<()> {
+	pub(super) fn with_precedence(&mut self, lexer: &mut Lexer, precedence: Precedence) -> CompilationResult<Expr> {
 		self.advance(lexer)?;
 
 		let token = self.current();
-		let prefix_opt = self.prefix_parselets.get(&token.kind);
-		if prefix_opt.is_none() {
+		let Some(prefix_parselet) = self.prefix_parselets.get(&token.kind).cloned() else {
 			return Err(CompilationError::ExpressionExpected {
 				token: token.kind.to_string().into(),
 				pos: token.line,
 			});
-		}
-		let prefix_parselet = prefix_opt.expect("should not fail").clone();
-		prefix_parselet.parse(lexer, self, chunk, token)?;
+		};
+		let mut left = prefix_parselet.parse(lexer, self, token)?;
 
 		while precedence <= self.get_precedence() {
 			self.advance(lexer)?;
 			let token = self.current();
-			let infix_opt = self.infix_parselets.get(&token.kind);
-			if let Some(infix) = infix_opt {
-				infix.clone().parse(lexer, self, chunk, token)?;
-			} else {
-				let _prefix_opt = self.prefix_parselets.get(&token.kind);
-				match infix_opt {
-					Some(prefix) => prefix.clone().parse(lexer, self, chunk, token)?,
-					None => {
-						break;
-					}
-				}
-			}
+			let Some(infix_parselet) = self.infix_parselets.get(&token.kind).cloned() else {
+				break;
+			};
+			left = infix_parselet.parse(lexer, self, left, token)?;
 		}
 
-		Ok(())
+		Ok(left)
 	}
 
 	fn get_precedence(&self) -> Precedence {
@@ -23,6 +23,7 @@ pub enum Precedence {
 	Comparison,
 	Term,
 	Factor,
+	Cast,
 	Unary,
 	Primary,
 }
@@ -42,7 +43,8 @@ impl Precedence {
 			Self::Equality => Some(Self::Comparison),
 			Self::Comparison => Some(Self::Term),
 			Self::Term => Some(Self::Factor),
-			Self::Factor => Some(Self::Unary),
+			Self::Factor => Some(Self::Cast),
+			Self::Cast => Some(Self::Unary),
 			Self::Unary => Some(Self::Primary),
 			Self::Primary => None,
 		}
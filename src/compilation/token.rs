@@ -34,6 +34,8 @@ pub enum TokenKind {
 	SlashEqual,
 	/// ;
 	Semicolon,
+	/// ,
+	Comma,
 	/// & -> binary and
 	Ampersand,
 	/// | -> binary or
@@ -66,6 +68,8 @@ pub enum TokenKind {
 	LeftParen,
 	/// )
 	RightParen,
+	/// Keyword 'as'
+	As,
 	/// keyword 'nil'
 	Nil,
 	/// Keyword boolean 'true'
@@ -74,6 +78,8 @@ pub enum TokenKind {
 	False,
 	/// Keyword 'print'
 	Print,
+	/// Keyword 'yield'
+	Yield,
 	/// An Identifier
 	Ident,
 	/// Any Number either f64 or i64
@@ -84,6 +90,8 @@ pub enum TokenKind {
 	IntNumber,
 	/// Any String
 	String,
+	/// A String containing `{expression}`s to interpolate
+	Template,
 	/// An Enum value
 	Enum,
 }
@@ -104,6 +112,7 @@ impl Display for TokenKind {
 			Self::Slash => write!(f, "/"),
 			Self::SlashEqual => write!(f, "/="),
 			Self::Semicolon => write!(f, ";"),
+			Self::Comma => write!(f, ","),
 			Self::Ampersand => write!(f, "&"),
 			Self::Pipe => write!(f, "|"),
 			Self::Caret => write!(f, "^"),
@@ -120,15 +129,18 @@ impl Display for TokenKind {
 			Self::QMark => write!(f, "?"),
 			Self::LeftParen => write!(f, "("),
 			Self::RightParen => write!(f, ")"),
+			Self::As => write!(f, "'as'"),
 			Self::Nil => write!(f, "'nil'"),
 			Self::True => write!(f, "'true'"),
 			Self::Print => write!(f, "'print'"),
+			Self::Yield => write!(f, "'yield'"),
 			Self::False => write!(f, "'false'"),
 			Self::Ident => write!(f, "an 'Ident'"),
 			Self::FloatNumber => write!(f, "a 'FloatNumber'"),
 			Self::HexNumber => write!(f, "a 'HexNumber'"),
 			Self::IntNumber => write!(f, "a 'IntNumber'"),
 			Self::String => write!(f, "a 'String'"),
+			Self::Template => write!(f, "a 'Template'"),
 			Self::Enum => write!(f, "an 'Enum'"),
 		}
 	}
@@ -139,7 +151,7 @@ impl Display for TokenKind {
 pub struct Token {
 	/// Reference to the underlying location
 	pub(crate) origin: String,
-	/// Position of the token in the line.
+	/// Byte offset of the token in the source code.
 	pub(crate) offset: usize,
 	/// Line of the token.
 	pub(crate) line: usize,
@@ -150,10 +162,43 @@ pub struct Token {
 impl Token {
 	/// Get the token type. Needed for testing purposes.
 	#[inline]
+	#[must_use]
 	pub const fn kind(&self) -> TokenKind {
 		self.kind
 	}
 
+	/// Get the source code of the token, strings and templates without their quotes.
+	#[inline]
+	#[must_use]
+	pub fn origin(&self) -> &str {
+		&self.origin
+	}
+
+	/// Get the byte offset of the token in the source code.
+	#[inline]
+	#[must_use]
+	pub const fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Get the byte offset behind the token in the source code, including the quotes of strings and templates.
+	#[must_use]
+	pub const fn end(&self) -> usize {
+		match self.kind {
+			TokenKind::String | TokenKind::Template => self.offset + self.origin.len() + 2,
+			_ => self.offset + self.origin.len(),
+		}
+	}
+
+	/// Get the line of the token.
+	#[inline]
+	#[must_use]
+	pub const fn line(&self) -> usize {
+		self.line
+	}
+
+	/// Create a dummy token of kind [`TokenKind::None`].
+	#[must_use]
 	pub fn none() -> Self {
 		Self {
 			origin: String::default(),
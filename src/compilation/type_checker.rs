@@ -0,0 +1,504 @@
+// Copyright © 2026 Stephan Kunz
+//! [`TypeChecker`] inferring the types of expressions and reporting type errors before execution.
+//!
+//! The types of the environment variables and the results of native functions are given by a [`Schema`],
+//! variables defined by the script get the type of their first definition.
+//! Using a variable which is neither in the schema, nor defined before, nor a library constant is an error.
+//! The [`VM`] accepts values of any type for a variable, so a variable assigned a value of another type
+//! is not checked afterwards. As a policy beyond the rules of the [`VM`], [`Schema::set_fixed_types`]
+//! makes assigning a value of another type an error instead.
+//!
+//! The type rules are not duplicated: each operator is applied by the [`VM`] to a sample value
+//! of each operand type, so an operation is rejected exactly if the [`VM`] rejects it at runtime.
+//! Conditions of `&&`, `||` and `?:` have to be booleans the same way.
+//! Where a type is not known, e.g. the result of a function not in the schema, nothing is checked.
+
+// region:		--- modules
+use alloc::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec::Vec,
+};
+
+use crate::{
+	ConstString,
+	execution::{Instruction, VM},
+	library::Library,
+	scripting_value::ScriptingValue,
+};
+
+use super::ast::{AssignOp, BinaryOp, Expr, ExprKind, Literal, Span, Stmt, StmtKind, TemplatePart, UnaryOp};
+// endregion:	--- modules
+
+// region:		--- Type
+/// The type of a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Type {
+	/// `nil`
+	Nil,
+	/// A boolean.
+	Bool,
+	/// An integer number.
+	Int,
+	/// A floating point number.
+	Float,
+	/// A string.
+	String,
+	/// Not known before execution.
+	Any,
+}
+
+impl Type {
+	/// Name of the type, as returned by the script function `typeof` for known types.
+	#[must_use]
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Nil => "nil",
+			Self::Bool => "bool",
+			Self::Int => "int",
+			Self::Float => "float",
+			Self::String => "string",
+			Self::Any => "any",
+		}
+	}
+
+	/// The type converted to by a conversion function or the `as` operator.
+	fn conversion(name: &str) -> Option<Self> {
+		match name {
+			"bool" => Some(Self::Bool),
+			"float" => Some(Self::Float),
+			"int" => Some(Self::Int),
+			"str" | "string" => Some(Self::String),
+			_ => None,
+		}
+	}
+
+	/// A value of the type.
+	fn sample(self) -> Option<ScriptingValue> {
+		match self {
+			Self::Nil => Some(ScriptingValue::nil()),
+			Self::Bool => Some(ScriptingValue::Boolean(true)),
+			Self::Int => Some(ScriptingValue::Int64(1)),
+			Self::Float => Some(ScriptingValue::Float64(1.0)),
+			Self::String => Some(ScriptingValue::String("s".into())),
+			Self::Any => None,
+		}
+	}
+
+	/// The common type of two alternatives.
+	fn join(self, other: Self) -> Self {
+		if self == other { self } else { Self::Any }
+	}
+}
+
+impl From<&ScriptingValue> for Type {
+	fn from(value: &ScriptingValue) -> Self {
+		match value {
+			ScriptingValue::Nil() => Self::Nil,
+			ScriptingValue::Boolean(_) => Self::Bool,
+			ScriptingValue::Float64(_) => Self::Float,
+			ScriptingValue::Int64(_) => Self::Int,
+			ScriptingValue::String(_) => Self::String,
+		}
+	}
+}
+
+impl core::fmt::Display for Type {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{}", self.name())
+	}
+}
+// endregion:	--- Type
+
+// region:		--- Schema
+/// The types of the environment variables and the results of native functions.
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+	variables: BTreeMap<ConstString, Type>,
+	functions: BTreeMap<ConstString, Type>,
+	fixed_types: bool,
+}
+
+impl Schema {
+	/// Create an empty Schema.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register the type of an environment variable.
+	pub fn register_variable(&mut self, name: &str, var_type: Type) {
+		self.variables.insert(name.into(), var_type);
+	}
+
+	/// Register the result type of a native function.
+	/// The conversion functions `bool`, `float`, `int`, `str` and `typeof` are known without registration.
+	pub fn register_function(&mut self, name: &str, result_type: Type) {
+		self.functions.insert(name.into(), result_type);
+	}
+
+	/// Let variables keep their type, so assigning a value of another type is an error.
+	/// The [`VM`] itself accepts such assignments, so this is off by default.
+	pub const fn set_fixed_types(&mut self, fixed: bool) {
+		self.fixed_types = fixed;
+	}
+
+	/// Whether variables keep their type.
+	#[must_use]
+	pub const fn fixed_types(&self) -> bool {
+		self.fixed_types
+	}
+
+	/// Get the type of an environment variable.
+	#[must_use]
+	pub fn variable(&self, name: &str) -> Option<Type> {
+		self.variables.get(name).copied()
+	}
+
+	/// Get the result type of a native function.
+	#[must_use]
+	pub fn function(&self, name: &str) -> Option<Type> {
+		self.functions.get(name).copied()
+	}
+}
+// endregion:	--- Schema
+
+// region:		--- TypeError
+/// The kinds of type errors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TypeErrorKind {
+	/// An operation the [`VM`] rejects for the types of its operands.
+	Operation {
+		/// The operator.
+		op: &'static str,
+		/// Type of the left operand, `None` for a prefix operator.
+		left: Option<Type>,
+		/// Type of the (right) operand.
+		right: Type,
+		/// The error of the [`VM`].
+		reason: ConstString,
+	},
+	/// A condition which is not a boolean.
+	Condition {
+		/// Type of the condition.
+		found: Type,
+	},
+	/// A value of another type assigned to a variable, only with [`Schema::set_fixed_types`].
+	Assignment {
+		/// Name of the variable.
+		name: ConstString,
+		/// Type of the variable.
+		expected: Type,
+		/// Type of the value.
+		found: Type,
+	},
+	/// A variable which is not defined.
+	UnknownVariable {
+		/// Name of the variable.
+		name: ConstString,
+	},
+}
+
+/// A type error in a script.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeError {
+	/// What is wrong.
+	pub kind: TypeErrorKind,
+	/// Location of the faulty code.
+	pub span: Span,
+}
+
+impl core::fmt::Display for TypeError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		let pos = self.span.line;
+		match &self.kind {
+			TypeErrorKind::Operation {
+				op,
+				left: Some(left),
+				right,
+				reason,
+			} => write!(f, "invalid operation {left} {op} {right} at line {pos}: {reason}"),
+			TypeErrorKind::Operation {
+				op,
+				left: None,
+				right,
+				reason,
+			} => write!(f, "invalid operation {op}{right} at line {pos}: {reason}"),
+			TypeErrorKind::Condition { found } => write!(f, "the condition at line {pos} is {found} instead of bool"),
+			TypeErrorKind::Assignment { name, expected, found } => {
+				write!(
+					f,
+					"the variable {name} at line {pos} has type {expected}, but is assigned {found}"
+				)
+			}
+			TypeErrorKind::UnknownVariable { name } => write!(f, "the variable {name} at line {pos} is not defined"),
+		}
+	}
+}
+
+impl core::error::Error for TypeError {}
+// endregion:	--- TypeError
+
+// region:		--- TypeChecker
+/// `TypeChecker` implementation.
+#[derive(Clone, Copy, Debug)]
+pub struct TypeChecker<'a> {
+	schema: &'a Schema,
+	library: &'a Library,
+}
+
+impl<'a> TypeChecker<'a> {
+	/// Create a `TypeChecker` for the [`Schema`] and the constants of the [`Library`].
+	#[must_use]
+	pub const fn new(schema: &'a Schema, library: &'a Library) -> Self {
+		Self { schema, library }
+	}
+
+	/// Check the abstract syntax tree of a script, the errors are ordered by their execution.
+	#[must_use]
+	pub fn check(&self, stmts: &[Stmt]) -> Vec<TypeError> {
+		self.run(stmts).errors
+	}
+
+	/// The types of the variables defined by a script, given by their first definition.
+	#[must_use]
+	pub fn variables(&self, stmts: &[Stmt]) -> BTreeMap<ConstString, Type> {
+		self.run(stmts).variables
+	}
+
+	fn run(&self, stmts: &[Stmt]) -> State<'_> {
+		let mut state = State {
+			checker: self,
+			variables: BTreeMap::new(),
+			retyped: BTreeSet::new(),
+			errors: Vec::new(),
+		};
+		for stmt in stmts {
+			match &stmt.kind {
+				StmtKind::Expression(expr) | StmtKind::Print(expr) => {
+					state.expr(expr);
+				}
+				StmtKind::Yield => {}
+			}
+		}
+		state
+	}
+}
+// endregion:	--- TypeChecker
+
+// region:		--- State
+/// The state while checking a script.
+struct State<'a> {
+	checker: &'a TypeChecker<'a>,
+	/// Variables defined by the script.
+	variables: BTreeMap<ConstString, Type>,
+	/// Variables assigned a value of another type, their type is not known any more.
+	retyped: BTreeSet<ConstString>,
+	errors: Vec<TypeError>,
+}
+
+impl State<'_> {
+	fn error(&mut self, kind: TypeErrorKind, span: Span) {
+		self.errors.push(TypeError { kind, span });
+	}
+
+	/// The type of an existing variable, constants of the library take precedence like in the compiler.
+	fn variable(&self, name: &str) -> Option<Type> {
+		self.checker
+			.library
+			.constant(name)
+			.map(Type::from)
+			.or_else(|| self.retyped.contains(name).then_some(Type::Any))
+			.or_else(|| self.variables.get(name).copied())
+			.or_else(|| self.checker.schema.variable(name))
+	}
+
+	/// The type of a variable which has to exist.
+	fn existing(&mut self, name: &ConstString, span: Span) -> Type {
+		self.variable(name).unwrap_or_else(|| {
+			self.error(TypeErrorKind::UnknownVariable { name: name.clone() }, span);
+			Type::Any
+		})
+	}
+
+	/// Check that a condition is a boolean the way the jump instructions do.
+	fn condition(&mut self, found: Type, span: Span) {
+		if let Some(value) = found.sample()
+			&& value.as_bool().is_err()
+		{
+			self.error(TypeErrorKind::Condition { found }, span);
+		}
+	}
+
+	/// Apply an operator to the operand types, the result type is known from the sample values.
+	fn operate(&mut self, operator: Instruction, op: &'static str, left: Option<Type>, right: Type, span: Span) -> Type {
+		let samples: Option<Vec<ScriptingValue>> = left
+			.into_iter()
+			.chain([right])
+			.map(Type::sample)
+			.collect();
+		let Some(samples) = samples else {
+			// the result type is known for some operators even for unknown operand types
+			return match operator {
+				Instruction::Equal | Instruction::Greater | Instruction::Less | Instruction::Not => Type::Bool,
+				Instruction::BitwiseAnd | Instruction::BitwiseOr | Instruction::BitwiseXor | Instruction::BitwiseNot => {
+					Type::Int
+				}
+				_ => Type::Any,
+			};
+		};
+		match VM::evaluate(operator, &samples) {
+			Ok(value) => Type::from(&value),
+			Err(error) => {
+				self.error(
+					TypeErrorKind::Operation {
+						op,
+						left,
+						right,
+						reason: alloc::format!("{error}").into(),
+					},
+					span,
+				);
+				Type::Any
+			}
+		}
+	}
+
+	fn expr(&mut self, expr: &Expr) -> Type {
+		let span = expr.span;
+		match &expr.kind {
+			ExprKind::Literal(literal) => match literal {
+				Literal::Nil => Type::Nil,
+				Literal::Boolean(_) => Type::Bool,
+				Literal::Int(_) => Type::Int,
+				Literal::Float(_) => Type::Float,
+				Literal::String(_) => Type::String,
+			},
+			ExprKind::Enum { .. } => Type::Int,
+			ExprKind::Variable(name) => self.existing(name, span),
+			ExprKind::Template(parts) => {
+				for part in parts {
+					if let TemplatePart::Expr(expr) = part {
+						self.expr(expr);
+					}
+				}
+				Type::String
+			}
+			ExprKind::Grouping(inner) => self.expr(inner),
+			ExprKind::Unary { op, operand } => {
+				let operand_type = self.expr(operand);
+				let operator = match op {
+					// nothing is executed
+					UnaryOp::Plus => return operand_type,
+					UnaryOp::Not => Instruction::Not,
+					UnaryOp::Negate => Instruction::Negate,
+					UnaryOp::BitwiseNot => Instruction::BitwiseNot,
+				};
+				self.operate(operator, op.symbol(), None, operand_type, span)
+			}
+			ExprKind::Binary { op, left, right, .. } => {
+				let left_type = self.expr(left);
+				let right_type = self.expr(right);
+				// the same instructions as emitted by the compiler, the negation can not fail
+				let (operator, negated) = match op {
+					BinaryOp::Add => (Instruction::Add, false),
+					BinaryOp::Subtract => (Instruction::Subtract, false),
+					BinaryOp::Multiply => (Instruction::Multiply, false),
+					BinaryOp::Divide => (Instruction::Divide, false),
+					BinaryOp::Equal => (Instruction::Equal, false),
+					BinaryOp::NotEqual => (Instruction::Equal, true),
+					BinaryOp::Greater => (Instruction::Greater, false),
+					BinaryOp::GreaterEqual => (Instruction::Less, true),
+					BinaryOp::Less => (Instruction::Less, false),
+					BinaryOp::LessEqual => (Instruction::Greater, true),
+					BinaryOp::BitwiseAnd => (Instruction::BitwiseAnd, false),
+					BinaryOp::BitwiseOr => (Instruction::BitwiseOr, false),
+					BinaryOp::BitwiseXor => (Instruction::BitwiseXor, false),
+				};
+				let result = self.operate(operator, op.symbol(), Some(left_type), right_type, span);
+				if negated { Type::Bool } else { result }
+			}
+			ExprKind::Logical { left, right, .. } => {
+				let left_type = self.expr(left);
+				self.condition(left_type, left.span);
+				// either the left boolean or the right value is the result
+				Type::Bool.join(self.expr(right))
+			}
+			ExprKind::Ternary {
+				condition,
+				then,
+				otherwise,
+				..
+			} => {
+				let condition_type = self.expr(condition);
+				self.condition(condition_type, condition.span);
+				let then_type = self.expr(then);
+				then_type.join(self.expr(otherwise))
+			}
+			ExprKind::Assign { op, name, value } => self.assign(*op, name, value, span),
+			ExprKind::Call { name, args, .. } => {
+				for arg in args {
+					self.expr(arg);
+				}
+				self.checker
+					.schema
+					.function(name)
+					.or_else(|| match &**name {
+						"typeof" => Some(Type::String),
+						name => Type::conversion(name),
+					})
+					.unwrap_or(Type::Any)
+			}
+			ExprKind::Cast { value, type_name, .. } => {
+				self.expr(value);
+				Type::conversion(type_name).unwrap_or(Type::Any)
+			}
+		}
+	}
+
+	fn assign(&mut self, op: AssignOp, name: &ConstString, value: &Expr, span: Span) -> Type {
+		let operator = match op {
+			AssignOp::Define | AssignOp::Assign => None,
+			AssignOp::Add => Some(Instruction::Add),
+			AssignOp::Subtract => Some(Instruction::Subtract),
+			AssignOp::Multiply => Some(Instruction::Multiply),
+			AssignOp::Divide => Some(Instruction::Divide),
+		};
+		// a compound assignment reads the variable before evaluating the value
+		let current = if op == AssignOp::Define {
+			self.variable(name)
+		} else {
+			Some(self.existing(name, span))
+		};
+		let value_type = self.expr(value);
+		let assigned = match (operator, current) {
+			(Some(operator), Some(current)) => self.operate(operator, op.symbol(), Some(current), value_type, span),
+			_ => value_type,
+		};
+		match current {
+			Some(expected) if expected == assigned => {}
+			Some(expected) if self.checker.schema.fixed_types() => {
+				if expected != Type::Any && assigned != Type::Any {
+					self.error(
+						TypeErrorKind::Assignment {
+							name: name.clone(),
+							expected,
+							found: assigned,
+						},
+						span,
+					);
+				}
+			}
+			// the VM accepts any value, the type of the variable is not known any more
+			Some(_) if self.variable(name).is_some() => {
+				self.retyped.insert(name.clone());
+			}
+			Some(_) => {}
+			None => {
+				self.variables.insert(name.clone(), assigned);
+			}
+		}
+		assigned
+	}
+}
+// endregion:	--- State
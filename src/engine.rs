@@ -0,0 +1,291 @@
+// Copyright © 2026 Stephan Kunz
+//! The compiled state of tinyscript, shareable between threads.
+//!
+//! An [`Engine`] holds everything needed to compile scripts: the enums, the [`Library`](crate::Library),
+//! the cache of compiled scripts and the precompiled scripts.
+//! Compiled [`Chunk`]s are immutable and handed out as [`Arc<Chunk>`],
+//! so any number of [`Runtime`]s on different threads can execute them at the same time.
+//! Only compilation and the lookup of compiled scripts lock the engine.
+
+// region:      --- modules
+use alloc::{collections::btree_map::BTreeMap, string::String, sync::Arc, vec::Vec};
+use core::ops::RangeInclusive;
+use spin::Mutex;
+
+use crate::{
+	ConstString,
+	cache::ChunkCache,
+	compilation::{Assembler, Linter, Parser, Schema, Stmt, TypeChecker, TypeError, Warning},
+	error::Error,
+	execution::Chunk,
+	library::{AsyncNativeFn, NativeFn},
+	runtime::Runtime,
+	scripting_value::ScriptingValue,
+};
+// endregion:   --- modules
+
+// region:      --- types
+/// Defines a shared [`Engine`].
+pub type SharedEngine = Arc<Engine>;
+// endregion:   --- types
+
+// an engine is shared between threads, the chunks are executed on different threads
+const _: () = {
+	const fn assert_send_sync<T: Send + Sync>() {}
+	assert_send_sync::<Engine>();
+	assert_send_sync::<Chunk>();
+};
+
+// region:      --- Compiler
+/// The state protected by the lock of the [`Engine`].
+#[derive(Debug, Default)]
+struct Compiler {
+	parser: Parser,
+	enums: BTreeMap<String, i8>,
+	/// Compiled scripts keyed by their source.
+	cache: ChunkCache,
+	/// Precompiled scripts keyed by their name.
+	scripts: BTreeMap<ConstString, Arc<Chunk>>,
+}
+
+impl Compiler {
+	fn compile(&mut self, script: &str) -> Result<Arc<Chunk>, Error> {
+		if let Some(chunk) = self.cache.get(script) {
+			return Ok(chunk);
+		}
+		let chunk = Arc::new(self.parser.parse(&self.enums, script)?);
+		self.cache.insert(script, chunk.clone());
+		Ok(chunk)
+	}
+}
+// endregion:   --- Compiler
+
+// region:      --- Engine
+/// Shareable compiled state of tinyscript.
+#[derive(Debug, Default)]
+pub struct Engine {
+	compiler: Mutex<Compiler>,
+}
+
+/// Cloning an Engine is cloning the enums, the library, the optimization setting, the schema,
+/// the cache capacity and the precompiled scripts.
+/// Parser and cache are created new.
+impl Clone for Engine {
+	fn clone(&self) -> Self {
+		let compiler = self.compiler.lock();
+		let mut parser = Parser::with_library(compiler.parser.library().clone());
+		parser.set_optimization(compiler.parser.optimization());
+		parser.set_schema(compiler.parser.schema().cloned());
+		Self {
+			compiler: Mutex::new(Compiler {
+				parser,
+				enums: compiler.enums.clone(),
+				cache: ChunkCache::new(compiler.cache.capacity()),
+				scripts: compiler.scripts.clone(),
+			}),
+		}
+	}
+}
+
+impl Engine {
+	/// Create a [`Runtime`] executing scripts with this engine.
+	/// Each thread should use its own [`Runtime`].
+	#[must_use]
+	pub fn executor(self: &Arc<Self>) -> Runtime {
+		Runtime::with_engine(self.clone())
+	}
+
+	/// Inserts an enum value.
+	/// # Errors
+	/// - [`Error::DuplicateEnumVariant`] if en enum definition (key) already exists.
+	pub fn register_enum_tuple(&self, key: &str, value: i8) -> Result<(), Error> {
+		let mut compiler = self.compiler.lock();
+		if let Some(old_value) = compiler.enums.get(key) {
+			return Err(Error::DuplicateVariant {
+				name: key.into(),
+				old: *old_value,
+				new: value,
+			});
+		}
+		compiler.enums.insert(key.into(), value);
+		compiler.cache.clear();
+		Ok(())
+	}
+
+	/// Registers a native function callable from scripts.
+	/// # Errors
+	/// - [`Error::DuplicateFunction`] if a function with that name already exists.
+	pub fn register_function(&self, name: &str, arity: RangeInclusive<u8>, function: NativeFn) -> Result<(), Error> {
+		let mut compiler = self.compiler.lock();
+		compiler
+			.parser
+			.library_mut()
+			.register_function(name, arity, function)?;
+		compiler.cache.clear();
+		Ok(())
+	}
+
+	/// Registers an asynchronous native function callable from scripts executed asynchronously.
+	/// # Errors
+	/// - [`Error::DuplicateFunction`] if a function with that name already exists.
+	pub fn register_async_function(
+		&self,
+		name: &str,
+		arity: RangeInclusive<u8>,
+		function: AsyncNativeFn,
+	) -> Result<(), Error> {
+		let mut compiler = self.compiler.lock();
+		compiler
+			.parser
+			.library_mut()
+			.register_async_function(name, arity, function)?;
+		compiler.cache.clear();
+		Ok(())
+	}
+
+	/// Registers a named constant usable in scripts.
+	/// # Errors
+	/// - [`Error::DuplicateConstant`] if a constant with that name already exists.
+	pub fn register_constant(&self, name: &str, value: impl Into<ScriptingValue>) -> Result<(), Error> {
+		let mut compiler = self.compiler.lock();
+		compiler
+			.parser
+			.library_mut()
+			.register_constant(name, value)?;
+		compiler.cache.clear();
+		Ok(())
+	}
+
+	/// Enable or disable the optimization of compiled scripts, it is enabled by default.
+	pub fn set_optimization(&self, enabled: bool) {
+		let mut compiler = self.compiler.lock();
+		compiler.parser.set_optimization(enabled);
+		compiler.cache.clear();
+	}
+
+	/// Set the [`Schema`] to check the types of compiled scripts against, see [`TypeChecker`].
+	/// Without a schema, which is the default, the types are not checked.
+	pub fn set_schema(&self, schema: Option<Schema>) {
+		let mut compiler = self.compiler.lock();
+		compiler.parser.set_schema(schema);
+		compiler.cache.clear();
+	}
+
+	/// Get the discriminant of an enum value if it exists
+	#[must_use]
+	pub fn enum_discriminant(&self, name: &str) -> Option<i8> {
+		self.compiler.lock().enums.get(name).copied()
+	}
+
+	/// Parse a scripting source without using the cache.
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid
+	pub fn parse(&self, script: &str) -> Result<Chunk, Error> {
+		let mut compiler = self.compiler.lock();
+		let compiler = &mut *compiler;
+		let chunk = compiler.parser.parse(&compiler.enums, script)?;
+		Ok(chunk)
+	}
+
+	/// Parse a scripting source into its abstract syntax tree.
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid
+	pub fn parse_ast(&self, script: &str) -> Result<Vec<Stmt>, Error> {
+		let mut compiler = self.compiler.lock();
+		let compiler = &mut *compiler;
+		let statements = compiler
+			.parser
+			.parse_ast(&compiler.enums, script)?;
+		Ok(statements)
+	}
+
+	/// Assemble bytecode in the textual format of the [`Disassembler`](crate::execution::Disassembler)
+	/// with the registered functions, see [`Assembler`].
+	/// # Errors
+	/// - [`Error::Compilation`] if the text is invalid
+	pub fn assemble(&self, text: &str) -> Result<Chunk, Error> {
+		let library = self.compiler.lock().parser.library().clone();
+		let chunk = Assembler::with_library(library).assemble(text)?;
+		Ok(chunk)
+	}
+
+	/// Lint a script with the registered enum values, see [`Linter`].
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid
+	pub fn lint(&self, script: &str) -> Result<Vec<Warning>, Error> {
+		let mut linter = Linter::new();
+		for name in self.compiler.lock().enums.keys() {
+			linter.register_enum(name);
+		}
+		let warnings = linter.lint(script)?;
+		Ok(warnings)
+	}
+
+	/// Check the types of a script against the set [`Schema`] or an empty one, see [`TypeChecker`].
+	/// In contrast to compiling, all type errors are returned.
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid
+	pub fn check(&self, script: &str) -> Result<Vec<TypeError>, Error> {
+		let mut compiler = self.compiler.lock();
+		let compiler = &mut *compiler;
+		let statements = compiler
+			.parser
+			.parse_ast(&compiler.enums, script)?;
+		let empty = Schema::new();
+		let schema = compiler.parser.schema().unwrap_or(&empty);
+		Ok(TypeChecker::new(schema, compiler.parser.library()).check(&statements))
+	}
+
+	/// Compile a script, reusing the cached [`Chunk`] if the same source has been compiled before.
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid
+	pub fn compile(&self, script: &str) -> Result<Arc<Chunk>, Error> {
+		self.compiler.lock().compile(script)
+	}
+
+	/// Precompile a script and keep it under `name` until it is removed.
+	/// Precompiled scripts are not affected by the cache
+	/// and keep the enums and functions known at the time of compilation.
+	/// # Errors
+	/// - [`Error::DuplicateScript`] if a script with that name already exists.
+	/// - [`Error::Compilation`] if script is invalid.
+	pub fn precompile(&self, name: &str, script: &str) -> Result<Arc<Chunk>, Error> {
+		let mut compiler = self.compiler.lock();
+		if compiler.scripts.contains_key(name) {
+			return Err(Error::DuplicateScript { name: name.into() });
+		}
+		let chunk = compiler.compile(script)?;
+		compiler
+			.scripts
+			.insert(name.into(), chunk.clone());
+		Ok(chunk)
+	}
+
+	/// Get a precompiled script.
+	#[must_use]
+	pub fn script(&self, name: &str) -> Option<Arc<Chunk>> {
+		self.compiler.lock().scripts.get(name).cloned()
+	}
+
+	/// Remove a precompiled script, returning it if it existed.
+	pub fn remove_script(&self, name: &str) -> Option<Arc<Chunk>> {
+		self.compiler.lock().scripts.remove(name)
+	}
+
+	/// Number of compiled scripts in the cache.
+	#[must_use]
+	pub fn cache_len(&self) -> usize {
+		self.compiler.lock().cache.len()
+	}
+
+	/// Change the number of cached compiled scripts, `0` disables the cache.
+	pub fn set_cache_capacity(&self, capacity: usize) {
+		self.compiler.lock().cache.set_capacity(capacity);
+	}
+
+	/// Remove all compiled scripts from the cache.
+	pub fn clear_cache(&self) {
+		self.compiler.lock().cache.clear();
+	}
+}
+// endregion:   --- Engine
@@ -2,11 +2,8 @@
 //! A tratt to work with the outside world and a default implementation.
 
 // region:		--- modules
-use alloc::{
-	collections::btree_map::BTreeMap,
-	string::{String, ToString},
-};
-use spin::RwLock;
+use alloc::{collections::btree_map::BTreeMap, vec::Vec};
+use core::future::{Future, ready};
 
 use crate::{ConstString, scripting_value::ScriptingValue};
 // endregion:	--- modules
@@ -15,6 +12,10 @@ use crate::{ConstString, scripting_value::ScriptingValue};
 /// that stores the [`ScriptingValue`]s persistent and external available.
 ///
 /// An environment must be a key-value-store that can store [`ScriptingValue`]s.
+///
+/// Optionally an environment can resolve keys to stable [`EnvHandle`]s, so that a linked
+/// [`Chunk`](crate::execution::Chunk) accesses its variables by handle instead of by key.
+/// The default implementations of the handle methods do not support handles.
 pub trait Environment: Send + Sync {
 	/// Creates or updates the [`ScriptingValue`] behind `key`.
 	/// Value will be created if it does not already exist.
@@ -31,6 +32,93 @@ pub trait Environment: Send + Sync {
 	/// # Errors
 	/// if variable does not exist.
 	fn set_env(&mut self, key: &str, value: impl Into<ScriptingValue>) -> Result<(), Error>;
+
+	/// Resolves `key` to a stable [`EnvHandle`], the variable does not need to exist yet.
+	/// A handle must stay valid as long as the environment exists.
+	/// Returns `None` if handles are not supported, then variables are accessed by `key`.
+	fn resolve_env(&mut self, key: &str) -> Option<EnvHandle> {
+		let _ = key;
+		None
+	}
+
+	/// Creates or updates the [`ScriptingValue`] behind `handle`.
+	/// # Errors
+	/// - [`Error::EnvVarWrongType`] if the variable exists with a different type.
+	/// - [`Error::EnvHandleInvalid`] if the handle was not resolved by this environment.
+	fn define_env_handle(&mut self, handle: EnvHandle, value: ScriptingValue) -> Result<(), Error> {
+		let _ = value;
+		Err(Error::EnvHandleInvalid { handle: handle.index() })
+	}
+
+	/// Returns the [`ScriptingValue`] stored behind `handle`.
+	/// # Errors
+	/// - [`Error::EnvVarNotDefined`] if the variable does not exist
+	/// - [`Error::EnvHandleInvalid`] if the handle was not resolved by this environment.
+	fn get_env_handle(&self, handle: EnvHandle) -> Result<ScriptingValue, Error> {
+		Err(Error::EnvHandleInvalid { handle: handle.index() })
+	}
+
+	/// Set the variable behind `handle` to `value`.
+	/// # Errors
+	/// - [`Error::EnvVarNotDefined`] if the variable does not exist
+	/// - [`Error::EnvHandleInvalid`] if the handle was not resolved by this environment.
+	fn set_env_handle(&mut self, handle: EnvHandle, value: ScriptingValue) -> Result<(), Error> {
+		let _ = value;
+		Err(Error::EnvHandleInvalid { handle: handle.index() })
+	}
+}
+
+/// The asynchronous variant of an [`Environment`], e.g. for blackboards with an asynchronous backend.
+///
+/// It is used by the asynchronous execution [`VM::run_async`](crate::execution::VM::run_async).
+/// Every [`Environment`] is also an [`AsyncEnvironment`] whose futures are immediately ready.
+pub trait AsyncEnvironment: Send + Sync {
+	/// Creates or updates the [`ScriptingValue`] behind `key`.
+	/// # Errors
+	/// [`Error::EnvVarWrongType`] if the variable exists with a different type.
+	fn define_env_async(&mut self, key: &str, value: ScriptingValue) -> impl Future<Output = Result<(), Error>> + Send;
+
+	/// Returns the [`ScriptingValue`] stored behind `key`.
+	/// # Errors
+	/// [`Error::EnvVarNotDefined`] if the variable does not exist
+	fn get_env_async(&self, key: &str) -> impl Future<Output = Result<ScriptingValue, Error>> + Send;
+
+	/// Set the variable with `key` to `value`.
+	/// # Errors
+	/// if variable does not exist.
+	fn set_env_async(&mut self, key: &str, value: ScriptingValue) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+impl<T: Environment> AsyncEnvironment for T {
+	fn define_env_async(&mut self, key: &str, value: ScriptingValue) -> impl Future<Output = Result<(), Error>> + Send {
+		ready(self.define_env(key, value))
+	}
+
+	fn get_env_async(&self, key: &str) -> impl Future<Output = Result<ScriptingValue, Error>> + Send {
+		ready(self.get_env(key))
+	}
+
+	fn set_env_async(&mut self, key: &str, value: ScriptingValue) -> impl Future<Output = Result<(), Error>> + Send {
+		ready(self.set_env(key, value))
+	}
+}
+
+/// A stable handle to a variable of an [`Environment`], created by [`Environment::resolve_env`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EnvHandle(usize);
+
+impl EnvHandle {
+	/// Create a handle from an index chosen by the [`Environment`].
+	#[must_use]
+	pub const fn new(index: usize) -> Self {
+		Self(index)
+	}
+
+	/// The index of the handle.
+	#[must_use]
+	pub const fn index(self) -> usize {
+		self.0
+	}
 }
 
 /// Errors that can happen when interacting with an [`Environment`].
@@ -72,6 +160,12 @@ pub enum Error {
 		/// Expected type ofthe variable
 		var_type: ConstString,
 	},
+
+	/// A handle that has not been resolved by the [`Environment`].
+	EnvHandleInvalid {
+		/// Index of the handle
+		handle: usize,
+	},
 }
 
 /// Currently the default implementation is sufficient.
@@ -86,6 +180,7 @@ impl core::fmt::Debug for Error {
 			Self::EnvVarWrongType { name } => write!(f, "EnvVarWrongType({name})"),
 			Self::EnvVarSet { name, cause } => write!(f, "EnvVarSet({name}, {cause})"),
 			Self::EnvVarTypeCast { name, var_type } => write!(f, "EnvVarTypeCast({name}, {var_type})"),
+			Self::EnvHandleInvalid { handle } => write!(f, "EnvHandleInvalid({handle})"),
 		}
 	}
 }
@@ -108,39 +203,100 @@ impl core::fmt::Display for Error {
 				write!(f, "setting environment variable {name} failed: {cause}")
 			}
 			Self::EnvVarTypeCast { name, var_type } => write!(f, "cast of variable {name} to {var_type} failed"),
+			Self::EnvHandleInvalid { handle } => write!(f, "the environment handle {handle} is invalid"),
 		}
 	}
 }
 
 /// A very simple default Environment for testing purpose and the REPL.
+///
+/// Variables are stored in slots, which are also used as [`EnvHandle`]s.
 #[derive(Debug, Default)]
 pub struct DefaultEnvironment {
-	storage: RwLock<BTreeMap<String, ScriptingValue>>,
+	/// Slot of each known key
+	index: BTreeMap<ConstString, usize>,
+	/// Key and value of each slot, the value is `None` until the variable is defined
+	slots: Vec<(ConstString, Option<ScriptingValue>)>,
+}
+
+impl DefaultEnvironment {
+	/// Get the slot of `key`, creating it if it does not exist.
+	fn slot(&mut self, key: &str) -> usize {
+		if let Some(slot) = self.index.get(key) {
+			return *slot;
+		}
+		let key: ConstString = key.into();
+		self.slots.push((key.clone(), None));
+		self.index.insert(key, self.slots.len() - 1);
+		self.slots.len() - 1
+	}
+
+	/// Iterate over all defined variables ordered by their key.
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &ScriptingValue)> {
+		self.index.iter().filter_map(|(key, slot)| {
+			self.slots[*slot]
+				.1
+				.as_ref()
+				.map(|value| (key.as_ref(), value))
+		})
+	}
 }
 
 impl Environment for DefaultEnvironment {
 	fn define_env(&mut self, key: &str, value: impl Into<ScriptingValue>) -> Result<(), Error> {
-		self.storage
-			.write()
-			.insert(key.to_string(), value.into());
+		let slot = self.slot(key);
+		self.slots[slot].1 = Some(value.into());
 		Ok(())
 	}
 
 	fn get_env(&self, key: &str) -> Result<ScriptingValue, Error> {
-		self.storage.read().get(key).map_or_else(
-			|| Err(Error::EnvVarNotDefined { name: key.into() }),
-			|value| Ok(value.clone()),
-		)
+		self.index
+			.get(key)
+			.and_then(|slot| self.slots[*slot].1.clone())
+			.ok_or_else(|| Error::EnvVarNotDefined { name: key.into() })
 	}
 
 	fn set_env(&mut self, key: &str, value: impl Into<ScriptingValue>) -> Result<(), Error> {
-		if self.storage.read().contains_key(key) {
-			self.storage
-				.write()
-				.insert(key.to_string(), value.into());
-			Ok(())
-		} else {
-			Err(Error::EnvVarNotDefined { name: key.into() })
+		match self.index.get(key) {
+			Some(slot) if self.slots[*slot].1.is_some() => {
+				self.slots[*slot].1 = Some(value.into());
+				Ok(())
+			}
+			_ => Err(Error::EnvVarNotDefined { name: key.into() }),
 		}
 	}
+
+	fn resolve_env(&mut self, key: &str) -> Option<EnvHandle> {
+		Some(EnvHandle::new(self.slot(key)))
+	}
+
+	fn define_env_handle(&mut self, handle: EnvHandle, value: ScriptingValue) -> Result<(), Error> {
+		let (_, slot) = self
+			.slots
+			.get_mut(handle.index())
+			.ok_or_else(|| Error::EnvHandleInvalid { handle: handle.index() })?;
+		*slot = Some(value);
+		Ok(())
+	}
+
+	fn get_env_handle(&self, handle: EnvHandle) -> Result<ScriptingValue, Error> {
+		let (key, slot) = self
+			.slots
+			.get(handle.index())
+			.ok_or_else(|| Error::EnvHandleInvalid { handle: handle.index() })?;
+		slot.clone()
+			.ok_or_else(|| Error::EnvVarNotDefined { name: key.clone() })
+	}
+
+	fn set_env_handle(&mut self, handle: EnvHandle, value: ScriptingValue) -> Result<(), Error> {
+		let (key, slot) = self
+			.slots
+			.get_mut(handle.index())
+			.ok_or_else(|| Error::EnvHandleInvalid { handle: handle.index() })?;
+		let old = slot
+			.as_mut()
+			.ok_or_else(|| Error::EnvVarNotDefined { name: key.clone() })?;
+		*old = value;
+		Ok(())
+	}
 }
@@ -19,6 +19,21 @@ pub enum Error {
 		/// The original error.
 		source: ExecutionError,
 	},
+	/// Tried to redefine a constant.
+	DuplicateConstant {
+		/// Name of the constant.
+		name: ConstString,
+	},
+	/// Tried to redefine a precompiled script.
+	DuplicateScript {
+		/// Name of the script.
+		name: ConstString,
+	},
+	/// Tried to redefine a native function.
+	DuplicateFunction {
+		/// Name of the function.
+		name: ConstString,
+	},
 	/// Tried to redefine an enum value.
 	DuplicateVariant {
 		/// Name of the enum value.
@@ -28,6 +43,11 @@ pub enum Error {
 		/// Now defined value.
 		new: i8,
 	},
+	/// A precompiled script does not exist.
+	ScriptNotFound {
+		/// Name of the script.
+		name: ConstString,
+	},
 	/// Conversion failed.
 	TryConversion {
 		/// The faulty value.
@@ -52,10 +72,14 @@ impl core::fmt::Debug for Error {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		match self {
 			Self::Compilation { source } => write!(f, "compilation error: {source}"),
-			Self::Execution { source } => write!(f, "execution error:{source}"),
+			Self::Execution { source } => write!(f, "execution error: {source}"),
+			Self::DuplicateConstant { name } => write!(f, "constant {name} already exists"),
+			Self::DuplicateFunction { name } => write!(f, "function {name} already exists"),
+			Self::DuplicateScript { name } => write!(f, "script {name} already exists"),
 			Self::DuplicateVariant { name, old, new } => {
 				write!(f, "enum variant {name} already exists with value {old} new value: {new}")
 			}
+			Self::ScriptNotFound { name } => write!(f, "script {name} does not exist"),
 			Self::TryConversion { value, into } => write!(f, "conversion of value {value} into {into} is not possible"),
 		}
 	}
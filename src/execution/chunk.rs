@@ -7,14 +7,14 @@ extern crate std;
 
 // region:      --- modules
 use crate::compilation::{CompilationError, CompilationResult};
-#[cfg(feature = "std")]
-use crate::execution::op_code::OpCode;
-use crate::scripting_value::ScriptingValue;
-use alloc::{borrow::ToOwned, vec::Vec};
+use crate::environment::Environment;
+use crate::execution::{disassembler::Disassembler, instruction::Instruction, linkage::Linkage};
+use crate::{library::NativeFunction, scripting_value::ScriptingValue};
+use alloc::vec::Vec;
 // endregion:   --- modules
 
 /// A chunk of bytecode
-#[derive(Default)]
+#[derive(Debug, Default)]
 pub struct Chunk {
 	/// the code
 	code: Vec<u8>,
@@ -22,6 +22,12 @@ pub struct Chunk {
 	lines: Vec<usize>,
 	/// storage for Values
 	values: Vec<ScriptingValue>,
+	/// storage for the called native functions
+	functions: Vec<NativeFunction>,
+	/// the code lowered into instructions
+	instructions: Vec<Instruction>,
+	/// corresponding storage for the line number of the instructions
+	instruction_lines: Vec<usize>,
 }
 
 impl Chunk {
@@ -31,11 +37,119 @@ impl Chunk {
 		&self.code
 	}
 
+	/// Access the line numbers corresponding to the code.
+	#[must_use]
+	pub const fn lines(&self) -> &Vec<usize> {
+		&self.lines
+	}
+
+	/// Access the storage of constant values.
+	#[must_use]
+	pub fn constants(&self) -> &[ScriptingValue] {
+		&self.values
+	}
+
+	/// Access the storage of called [`NativeFunction`]s.
+	#[must_use]
+	pub fn functions(&self) -> &[NativeFunction] {
+		&self.functions
+	}
+
+	/// Access the code lowered into [`Instruction`]s.
+	/// Jump targets are indices into this list.
+	#[must_use]
+	pub const fn instructions(&self) -> &Vec<Instruction> {
+		&self.instructions
+	}
+
+	/// Access the line numbers corresponding to the [`Instruction`]s.
+	#[must_use]
+	pub const fn instruction_lines(&self) -> &Vec<usize> {
+		&self.instruction_lines
+	}
+
+	/// Lowers the code into [`Instruction`]s, translating jump targets from byte offsets into indices.
+	/// # Errors
+	/// - if the code is malformed
+	#[allow(clippy::cast_possible_truncation)]
+	pub(crate) fn lower(&mut self) -> CompilationResult<()> {
+		let malformed = || CompilationError::Unreachable {
+			file: file!().into(),
+			line: line!(),
+		};
+		let mut offsets = Vec::new();
+		let mut instructions = Vec::new();
+		let mut lines = Vec::new();
+		let mut offset = 0;
+		while offset < self.code.len() {
+			let instruction = Instruction::read(&self.code, offset).ok_or_else(malformed)?;
+			offsets.push(offset);
+			instructions.push(instruction);
+			lines.push(self.lines[offset]);
+			offset += instruction.size();
+		}
+		offsets.push(offset);
+
+		for instruction in &mut instructions {
+			if let Some(target) = instruction.target() {
+				// the number of instructions is less than the number of bytes, so the index fits into an u16
+				let index = offsets
+					.binary_search(&usize::from(target))
+					.map_err(|_| malformed())?;
+				*instruction = instruction.with_target(index as u16);
+			}
+		}
+		self.instructions = instructions;
+		self.instruction_lines = lines;
+		Ok(())
+	}
+
+	/// Replace the code by the given [`Instruction`]s and their line numbers,
+	/// the storages for values and functions are kept.
+	#[allow(clippy::cast_possible_truncation)]
+	pub(crate) fn replace_instructions(&mut self, instructions: Vec<Instruction>, lines: Vec<usize>) {
+		let mut offsets = Vec::with_capacity(instructions.len() + 1);
+		let mut offset = 0;
+		for instruction in &instructions {
+			offsets.push(offset);
+			offset += instruction.size();
+		}
+		offsets.push(offset);
+
+		let mut code = Vec::with_capacity(offset);
+		let mut code_lines = Vec::with_capacity(offset);
+		for (instruction, line) in instructions.iter().zip(&lines) {
+			// jump addresses are limited to 16 bit, see `Parser::patch_jump`
+			let instruction = instruction
+				.target()
+				.map_or(*instruction, |target| {
+					instruction.with_target(offsets[usize::from(target)] as u16)
+				});
+			instruction.write(&mut code);
+			code_lines.resize(code.len(), *line);
+		}
+		self.code = code;
+		self.lines = code_lines;
+		self.instructions = instructions;
+		self.instruction_lines = lines;
+	}
+
+	/// Links the [`Chunk`] to an [`Environment`] by resolving the names of all accessed variables once.
+	/// The resulting [`Linkage`] is used with [`VM::run_linked`](crate::execution::VM::run_linked)
+	/// and the same environment for repeated executions.
+	#[must_use]
+	pub fn link(&self, globals: &mut impl Environment) -> Linkage {
+		Linkage::new(self, globals)
+	}
+
 	/// Finalizes the [`Chunk`] by shrinking al [`Vec`]'s.
 	pub(crate) fn finalize(&mut self) {
 		self.code.shrink_to_fit();
 		self.lines.shrink_to_fit();
 		self.values.shrink_to_fit();
+		self.functions.shrink_to_fit();
+		self.instructions.shrink_to_fit();
+		self.instruction_lines.shrink_to_fit();
 	}
 
 	/// Add a byte to the chunk
@@ -63,109 +177,52 @@ impl Chunk {
 		}
 	}
 
-	/// Read a [`ScriptingValue`] from the [`ScriptingValue`] storage.
-	#[must_use]
-	pub(super) fn read_constant(&self, pos: u8) -> ScriptingValue {
-		let offset = usize::from(pos);
-		self.values
-			.get(offset)
-			.map_or_else(|| todo!("pos: {}", pos), ToOwned::to_owned)
-	}
-
-	/// Disassemble chunk.
-	#[cfg(feature = "std")]
-	pub fn disassemble(&self, name: &str) {
-		let mut offset = 0usize;
-		std::println!("== {name} ==");
-		while offset < self.code.len() {
-			offset = self.disassemble_instruction(offset);
+	/// Add a [`NativeFunction`] to the function storage returning its position in the storage.
+	/// A function is stored only once.
+	/// # Errors
+	/// - on storage overflow
+	#[allow(clippy::cast_possible_truncation)]
+	pub(crate) fn add_function(&mut self, function: &NativeFunction) -> CompilationResult<u8> {
+		if let Some(pos) = self
+			.functions
+			.iter()
+			.position(|f| f.name() == function.name())
+		{
+			return Ok(pos as u8);
 		}
-	}
-
-	/// Disassemble an instruction.
-	#[cfg(feature = "std")]
-	fn disassemble_instruction(&self, offset: usize) -> usize {
-		std::print!("{offset:04} ");
-		if offset > 0 && self.lines[offset] == self.lines[offset - 1] {
-			std::print!("   | ");
+		if self.functions.len() < u8::MAX as usize {
+			self.functions.push(function.clone());
+			Ok((self.functions.len() - 1) as u8)
 		} else {
-			std::print!("{:4} ", self.lines[offset]);
-		}
-		let instruction: OpCode = self.code[offset].into();
-		match instruction {
-			OpCode::Add => Self::simple_instruction("OP_ADD", offset),
-			OpCode::BitwiseAnd => Self::simple_instruction("OP_BITWISE_AND", offset),
-			OpCode::BitwiseNot => Self::simple_instruction("OP_BITWISE_NOT", offset),
-			OpCode::BitwiseOr => Self::simple_instruction("OP_BITWISE_OR", offset),
-			OpCode::BitwiseXor => Self::simple_instruction("OP_BITWISE_XOR", offset),
-			OpCode::Constant => self.constant_instruction("OP_CONSTANT", offset),
-			OpCode::DefineExternal => self.constant_instruction("OP_DEFINE_GLOBAL", offset),
-			OpCode::Divide => Self::simple_instruction("OP_DIVIDE", offset),
-			OpCode::Equal => Self::simple_instruction("OP_EQUAL", offset),
-			OpCode::False => Self::simple_instruction("OP_FALSE", offset),
-			OpCode::GetExternal => self.constant_instruction("OP_GET_GLOBAL", offset),
-			OpCode::Greater => Self::simple_instruction("OP_GREATER", offset),
-			OpCode::Jmp => self.jump_instruction("OP_JMP", offset),
-			OpCode::JmpIfFalse => self.jump_instruction("OP_JMP_IF_FALSE", offset),
-			OpCode::JmpIfTrue => self.jump_instruction("OP_JMP_IF_TRUE", offset),
-			OpCode::Less => Self::simple_instruction("OP_LESS", offset),
-			OpCode::Multiply => Self::simple_instruction("OP_MULTIPLY", offset),
-			OpCode::Negate => Self::simple_instruction("OP_NEGATE", offset),
-			OpCode::Nil => Self::simple_instruction("OP_NIL", offset),
-			OpCode::None => Self::simple_instruction("OP_NONE", offset),
-			OpCode::Not => Self::simple_instruction("OP_NOT", offset),
-			OpCode::Pop => Self::simple_instruction("OP_POP", offset),
-			OpCode::Print => Self::simple_instruction("OP_PRINT", offset),
-			OpCode::Return => Self::simple_instruction("OP_RETURN", offset),
-			OpCode::SetExternal => self.constant_instruction("OP_SET_GLOBAL", offset),
-			OpCode::Subtract => Self::simple_instruction("OP_SUBTRACT", offset),
-			OpCode::True => Self::simple_instruction("OP_TRUE", offset),
+			Err(CompilationError::FunctionStorageOverflow)
 		}
 	}
 
-	/// Single byte instruction.
-	#[cfg(feature = "std")]
-	fn simple_instruction(name: &str, offset: usize) -> usize {
-		std::println!("{name:16}");
-		offset + 1
+	/// Read a [`NativeFunction`] from the function storage.
+	#[must_use]
+	pub(super) fn read_function(&self, pos: u8) -> &NativeFunction {
+		&self.functions[usize::from(pos)]
 	}
 
-	/// Constant instruction.
-	#[cfg(feature = "std")]
-	fn constant_instruction(&self, name: &str, offset: usize) -> usize {
-		match self.code.get(offset + 1) {
-			Some(pos) => {
-				let value = self.read_constant(pos.to_owned());
-				match value {
-					ScriptingValue::Nil() => std::println!("{name:16} {pos:3} 'NIL'"),
-					ScriptingValue::Boolean(b) => std::println!("{name:16} {pos:3} {b}"),
-					ScriptingValue::Float64(f) => std::println!("{name:16} {pos:3} {f}"),
-					ScriptingValue::Int64(i) => std::println!("{name:16} {pos:3} {i}"),
-					ScriptingValue::String(s) => std::println!("{name:16} {pos:3} {s}"),
-				}
-			}
-			None => std::eprintln!("missing constant value"),
-		}
-		offset + 2
+	/// Read a [`ScriptingValue`] from the [`ScriptingValue`] storage.
+	#[must_use]
+	pub(crate) fn read_constant(&self, pos: u8) -> &ScriptingValue {
+		let offset = usize::from(pos);
+		self.values
+			.get(offset)
+			.map_or_else(|| todo!("pos: {}", pos), |value| value)
+	}
+
+	/// Create a [`Disassembler`] for the code.
+	#[must_use]
+	pub const fn disassembler(&self) -> Disassembler<'_> {
+		Disassembler::new(self)
 	}
 
-	/// Jump instruction.
+	/// Disassemble chunk to `stdout`.
 	#[cfg(feature = "std")]
-	#[allow(clippy::expect_used)]
-	fn jump_instruction(&self, name: &str, offset: usize) -> usize {
-		let target = (usize::from(
-			self.code
-				.get(offset + 1)
-				.expect("missing first byte of jump target")
-				.to_owned(),
-		) << 8) + usize::from(
-			self.code
-				.get(offset + 2)
-				.expect("missing second byte of jump target")
-				.to_owned(),
-		);
-
-		std::println!("{name:16} {offset:05} {target:05}");
-		offset + 3
+	pub fn disassemble(&self, name: &str) {
+		std::println!("== {name} ==");
+		std::print!("{}", self.disassembler());
 	}
 }
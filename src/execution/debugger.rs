@@ -0,0 +1,128 @@
+// Copyright © 2026 Stephan Kunz
+//! A step [`Debugger`] for the execution of a [`Chunk`](crate::execution::Chunk).
+//!
+//! The debugger holds breakpoints on source lines and the state of a paused execution.
+//! It is used with [`VM::debug_step`](crate::execution::VM::debug_step) to execute one instruction
+//! at a time and with [`VM::debug_continue`](crate::execution::VM::debug_continue) to run until the next breakpoint.
+//! While the execution is paused, the host has access to the [`Environment`](crate::Environment)
+//! to inspect the variables.
+
+// region:		--- modules
+use alloc::collections::btree_set::BTreeSet;
+use core::{
+	fmt::Write,
+	hash::{Hash, Hasher},
+};
+
+use super::{Chunk, ExecutionError, ExecutionResult, Suspended};
+// endregion:	--- modules
+
+// region:		--- ChunkHasher
+/// A FNV-1a hasher for the identity of a [`Chunk`], which is available without `std`.
+struct ChunkHasher(u64);
+
+impl ChunkHasher {
+	/// The identity of a [`Chunk`] computed from its content.
+	fn chunk_id(chunk: &Chunk) -> u64 {
+		let mut hasher = Self(0xcbf2_9ce4_8422_2325);
+		chunk.code().hash(&mut hasher);
+		chunk.lines().hash(&mut hasher);
+		for constant in chunk.constants() {
+			constant.type_name().hash(&mut hasher);
+			// writing into the hasher does not fail
+			let _ = write!(hasher, "{constant}");
+		}
+		hasher.finish()
+	}
+}
+
+impl Hasher for ChunkHasher {
+	fn finish(&self) -> u64 {
+		self.0
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		for byte in bytes {
+			self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+		}
+	}
+}
+
+impl Write for ChunkHasher {
+	fn write_str(&mut self, s: &str) -> core::fmt::Result {
+		Hasher::write(self, s.as_bytes());
+		Ok(())
+	}
+}
+// endregion:	--- ChunkHasher
+
+// region:		--- Debugger
+/// Breakpoints and the paused execution of a debugging session.
+#[derive(Clone, Debug, Default)]
+pub struct Debugger {
+	/// Source lines to stop at.
+	breakpoints: BTreeSet<usize>,
+	/// The paused execution.
+	paused: Option<Suspended>,
+	/// The identity of the [`Chunk`] of the paused execution.
+	chunk: u64,
+}
+
+impl Debugger {
+	/// Add a breakpoint at a source line, returns `false` if it already existed.
+	pub fn add_breakpoint(&mut self, line: usize) -> bool {
+		self.breakpoints.insert(line)
+	}
+
+	/// Remove the breakpoint at a source line, returns `false` if it did not exist.
+	pub fn remove_breakpoint(&mut self, line: usize) -> bool {
+		self.breakpoints.remove(&line)
+	}
+
+	/// Remove all breakpoints.
+	pub fn clear_breakpoints(&mut self) {
+		self.breakpoints.clear();
+	}
+
+	/// Check whether there is a breakpoint at a source line.
+	#[must_use]
+	pub fn has_breakpoint(&self, line: usize) -> bool {
+		self.breakpoints.contains(&line)
+	}
+
+	/// Iterate over the source lines with a breakpoint.
+	pub fn breakpoints(&self) -> impl Iterator<Item = usize> {
+		self.breakpoints.iter().copied()
+	}
+
+	/// The paused execution with instruction pointer, source line and stack.
+	#[must_use]
+	pub const fn paused(&self) -> Option<&Suspended> {
+		self.paused.as_ref()
+	}
+
+	/// Check whether an execution is paused.
+	#[must_use]
+	pub const fn is_paused(&self) -> bool {
+		self.paused.is_some()
+	}
+
+	/// Abort the paused execution, the next debug call starts a new one.
+	pub fn abort(&mut self) {
+		self.paused = None;
+	}
+
+	pub(super) fn pause(&mut self, chunk: &Chunk, suspended: Suspended) {
+		self.chunk = ChunkHasher::chunk_id(chunk);
+		self.paused = Some(suspended);
+	}
+
+	/// Takes the paused execution, which must belong to `chunk`, otherwise it is kept.
+	pub(super) fn take_paused(&mut self, chunk: &Chunk) -> ExecutionResult<Option<Suspended>> {
+		if self.paused.is_some() && self.chunk != ChunkHasher::chunk_id(chunk) {
+			return Err(ExecutionError::InvalidResume);
+		}
+		Ok(self.paused.take())
+	}
+}
+// endregion:	--- Debugger
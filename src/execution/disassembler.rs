@@ -0,0 +1,208 @@
+// Copyright © 2026 Stephan Kunz
+//! A [`Disassembler`] for the bytecode of a [`Chunk`].
+//!
+//! The disassembler yields a [`Record`] per instruction and implements [`Display`],
+//! so the bytecode can be written into any [`core::fmt::Write`] without `std`.
+//! Each line of the text contains the offset, the source line, the name of the [`OpCode`] and its operands:
+//! ```text
+//! 0000    1 OP_CONSTANT          0 "hello"
+//! 0002    | OP_DEFINE_EXTERNAL   1 "greeting"
+//! 0004    2 OP_JMP_IF_FALSE   0009
+//! 0007    | OP_CALL              0 count (1)
+//! ```
+//! A `|` instead of the source line means the same line as the instruction before.
+
+// region:		--- modules
+use core::fmt::{Display, Formatter};
+
+use super::{Chunk, Instruction, op_code::OpCode};
+use crate::scripting_value::ScriptingValue;
+// endregion:	--- modules
+
+// region:		--- Operands
+/// The operands of a disassembled instruction.
+#[derive(Clone, Copy, Debug)]
+pub enum Operands<'a> {
+	/// Instruction without operands.
+	None,
+	/// Position and value of a constant.
+	Constant {
+		/// Position in the constant storage.
+		pos: u8,
+		/// The value of the constant.
+		value: &'a ScriptingValue,
+	},
+	/// A number of values.
+	Count(u8),
+	/// Call of a native function.
+	Call {
+		/// Position in the function storage.
+		pos: u8,
+		/// Name of the function.
+		name: &'a str,
+		/// Number of arguments.
+		count: u8,
+	},
+	/// Offset of a jump target.
+	Jump(u16),
+	/// A byte that is not a valid instruction or references a missing constant or function.
+	Invalid(u8),
+}
+
+impl Display for Operands<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::None => Ok(()),
+			Self::Constant { pos, value } => {
+				write!(f, "{pos:3} ")?;
+				write_literal(f, value)
+			}
+			Self::Count(count) => write!(f, "{count:3}"),
+			Self::Call { pos, name, count } => write!(f, "{pos:3} {name} ({count})"),
+			Self::Jump(target) => write!(f, "{target:04}"),
+			Self::Invalid(byte) => write!(f, "{byte:#04x}"),
+		}
+	}
+}
+
+/// Writes a value in a form that distinguishes the types.
+fn write_literal(f: &mut Formatter<'_>, value: &ScriptingValue) -> core::fmt::Result {
+	match value {
+		ScriptingValue::Nil() => write!(f, "nil"),
+		ScriptingValue::Boolean(b) => write!(f, "{b}"),
+		ScriptingValue::Float64(v) => write!(f, "{v:?}"),
+		ScriptingValue::Int64(i) => write!(f, "{i}"),
+		ScriptingValue::String(s) => write!(f, "{:?}", s.as_ref()),
+	}
+}
+// endregion:	--- Operands
+
+// region:		--- Record
+/// A disassembled instruction.
+#[derive(Clone, Copy, Debug)]
+pub struct Record<'a> {
+	/// Offset of the instruction in the bytecode.
+	pub offset: usize,
+	/// Source line of the instruction.
+	pub line: usize,
+	/// The [`OpCode`], [`OpCode::None`] for an invalid byte.
+	pub op_code: OpCode,
+	/// The operands of the instruction.
+	pub operands: Operands<'a>,
+}
+
+impl Record<'_> {
+	/// Writes the record, with `same_line` the source line is replaced by `|`.
+	fn write(&self, f: &mut Formatter<'_>, same_line: bool) -> core::fmt::Result {
+		write!(f, "{:04} ", self.offset)?;
+		if same_line {
+			write!(f, "   | ")?;
+		} else {
+			write!(f, "{:4} ", self.line)?;
+		}
+		if matches!(self.operands, Operands::None) {
+			write!(f, "{}", self.op_code.name())
+		} else {
+			write!(f, "{:18} {}", self.op_code.name(), self.operands)
+		}
+	}
+}
+
+impl Display for Record<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		self.write(f, false)
+	}
+}
+// endregion:	--- Record
+
+// region:		--- Disassembler
+/// An iterator over the disassembled instructions of a [`Chunk`].
+#[derive(Clone, Debug)]
+pub struct Disassembler<'a> {
+	chunk: &'a Chunk,
+	offset: usize,
+}
+
+impl<'a> Disassembler<'a> {
+	/// Create a disassembler for the bytecode of a [`Chunk`].
+	#[must_use]
+	pub const fn new(chunk: &'a Chunk) -> Self {
+		Self { chunk, offset: 0 }
+	}
+
+	fn operands(&self, instruction: Instruction) -> Option<Operands<'a>> {
+		let operands = match instruction {
+			Instruction::Constant(pos)
+			| Instruction::DefineExternal(pos)
+			| Instruction::GetExternal(pos)
+			| Instruction::SetExternal(pos) => Operands::Constant {
+				pos,
+				value: self.chunk.constants().get(usize::from(pos))?,
+			},
+			Instruction::Concat(count) => Operands::Count(count),
+			Instruction::Call { function, count } => Operands::Call {
+				pos: function,
+				name: self
+					.chunk
+					.functions()
+					.get(usize::from(function))?
+					.name(),
+				count,
+			},
+			Instruction::Jmp(target) | Instruction::JmpIfFalse(target) | Instruction::JmpIfTrue(target) => {
+				Operands::Jump(target)
+			}
+			_ => Operands::None,
+		};
+		Some(operands)
+	}
+}
+
+impl<'a> Iterator for Disassembler<'a> {
+	type Item = Record<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let code = self.chunk.code();
+		let byte = *code.get(self.offset)?;
+		let offset = self.offset;
+		let line = self
+			.chunk
+			.lines()
+			.get(offset)
+			.copied()
+			.unwrap_or_default();
+		let decoded =
+			Instruction::read(code, offset).and_then(|instruction| Some((instruction, self.operands(instruction)?)));
+		let record = if let Some((instruction, operands)) = decoded {
+			self.offset += instruction.size();
+			Record {
+				offset,
+				line,
+				op_code: instruction.op_code(),
+				operands,
+			}
+		} else {
+			self.offset += 1;
+			Record {
+				offset,
+				line,
+				op_code: OpCode::None,
+				operands: Operands::Invalid(byte),
+			}
+		};
+		Some(record)
+	}
+}
+
+impl Display for Disassembler<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		let mut previous_line = None;
+		for record in self.clone() {
+			record.write(f, previous_line == Some(record.line))?;
+			writeln!(f)?;
+			previous_line = Some(record.line);
+		}
+		Ok(())
+	}
+}
+// endregion:	--- Disassembler
@@ -9,13 +9,49 @@ pub type ExecutionResult<T> = core::result::Result<T, ExecutionError>;
 /// Things that may go wrong during execution of a compiled script.
 #[non_exhaustive]
 pub enum ExecutionError {
+	/// An asynchronous function called by a synchronous execution.
+	AsyncFunction {
+		/// Name of the function.
+		name: ConstString,
+	},
+	/// The total size of created strings exceeds the limit.
+	AllocationLimit {
+		/// The limit in bytes.
+		limit: usize,
+	},
 	/// No arithemetic with boolean for now.
 	BoolNoArithmetic,
+	/// Conversion failed.
+	Conversion {
+		/// The faulty value.
+		value: ConstString,
+		/// The wanted conversion into.
+		into: ConstString,
+	},
+	/// Integer division by zero.
+	DivisionByZero,
+	/// The number of writes to the environment exceeds the limit.
+	EnvWriteLimit {
+		/// The limit.
+		limit: usize,
+	},
 	/// Passthrough environment errors.
 	Environment {
 		/// The original error.
 		source: crate::environment::Error,
 	},
+	/// The number of executed instructions exceeds the limit.
+	InstructionLimit {
+		/// The limit.
+		limit: usize,
+	},
+	/// A native function got an argument it cannot handle.
+	InvalidArgument {
+		/// Name of the function.
+		function: ConstString,
+		/// The faulty value.
+		value: ConstString,
+	},
 	/// Nil does not allow anything.
 	NilValue,
 	/// Expected Boolean, got something else.
@@ -35,8 +71,24 @@ pub enum ExecutionError {
 		/// The faulty value.
 		value: ConstString,
 	},
+	/// Expected a string value, got something else.
+	NoString {
+		/// The faulty value.
+		value: ConstString,
+	},
+	/// Integer arithmetic overflowed.
+	Overflow,
+	/// A suspended execution was resumed with a chunk it does not belong to.
+	InvalidResume,
 	/// Stack overflow.
 	StackOverflow,
+	/// A created string exceeds the length limit.
+	StringLengthLimit {
+		/// Length of the string.
+		length: usize,
+		/// The limit.
+		limit: usize,
+	},
 	/// Strings only allow additions.
 	OnlyAdd,
 
@@ -55,14 +107,27 @@ impl core::error::Error for ExecutionError {}
 impl core::fmt::Debug for ExecutionError {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		match self {
+			Self::AsyncFunction { name } => write!(f, "asynchronous function {name} needs an asynchronous execution"),
+			Self::AllocationLimit { limit } => write!(f, "created strings exceed the limit of {limit} bytes"),
 			Self::BoolNoArithmetic => write!(f, "boolean values do not allow arithmetic operations"),
+			Self::Conversion { value, into } => write!(f, "conversion of value {value} into {into} is not possible"),
+			Self::DivisionByZero => write!(f, "integer division by zero"),
+			Self::EnvWriteLimit { limit } => write!(f, "more than {limit} writes to the environment"),
 			Self::Environment { source } => write!(f, "environment error: {source}"),
+			Self::InstructionLimit { limit } => write!(f, "more than {limit} instructions executed"),
+			Self::InvalidArgument { function, value } => write!(f, "invalid argument {value} for function {function}"),
 			Self::NilValue => write!(f, "value is 'Nil' which does not allow any operation"),
 			Self::NoBoolean { value } => write!(f, "expected boolean ('true'/'false'), got {value}"),
 			Self::NoComparison => write!(f, "comparing values needs two numeric types"),
 			Self::NoInteger { value } => write!(f, "expected integer value, got {value}"),
 			Self::NoNumber { value } => write!(f, "expected numerical value, got {value}"),
+			Self::NoString { value } => write!(f, "expected string value, got {value}"),
+			Self::Overflow => write!(f, "integer overflow"),
+			Self::InvalidResume => write!(f, "suspended execution does not belong to the chunk"),
 			Self::StackOverflow => write!(f, "stack overflow, to many variables/values"),
+			Self::StringLengthLimit { length, limit } => {
+				write!(f, "string of length {length} exceeds the limit of {limit}")
+			}
 			Self::OnlyAdd => write!(f, "to Strings you can only 'ADD' something"),
 			Self::Unreachable { file, line } => write!(f, "{file} at line {line} should be unreachable"),
 		}
@@ -0,0 +1,242 @@
+// Copyright © 2026 Stephan Kunz
+//! Decoded [`Instruction`] implementation for the [`Chunk`](crate::execution::Chunk).
+//!
+//! A [`Chunk`](crate::execution::Chunk) is lowered once into a list of [`Instruction`]s with embedded operands,
+//! so the virtual machine does not need to decode bytes and read operands while running.
+//! Jump targets of lowered instructions are indices into the instruction list.
+
+// region:		--- modules
+use alloc::vec::Vec;
+
+use super::op_code::OpCode;
+// endregion:	--- modules
+
+/// An instruction for the virtual machine with its operands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+	/// Push the constant at the given position
+	Constant(u8),
+	/// Push nil
+	Nil,
+	/// Push true
+	True,
+	/// Push false
+	False,
+	/// Remove the topmost value
+	Pop,
+	/// Define the global/external variable named by the constant at the given position
+	DefineExternal(u8),
+	/// Get the global/external variable named by the constant at the given position
+	GetExternal(u8),
+	/// Set the global/external variable named by the constant at the given position
+	SetExternal(u8),
+	/// Equality
+	Equal,
+	/// Comparison greater
+	Greater,
+	/// Comparison less
+	Less,
+	/// Jump to target
+	Jmp(u16),
+	/// Jump to target if condition is fulfilled
+	JmpIfTrue(u16),
+	/// Jump to target if condition is not fulfilled
+	JmpIfFalse(u16),
+	/// Add two numbers
+	Add,
+	/// Subtract number
+	Subtract,
+	/// Multiply two numbers
+	Multiply,
+	/// Divide number
+	Divide,
+	/// Bitwise not
+	BitwiseNot,
+	/// Bitwise and
+	BitwiseAnd,
+	/// Bitwise or
+	BitwiseOr,
+	/// Bitwise exclusive or
+	BitwiseXor,
+	/// Not Equal
+	Not,
+	/// Negation
+	Negate,
+	/// Return value to caller
+	Return,
+	/// Concatenate the given number of values into a string
+	Concat(u8),
+	/// Call a native function
+	Call {
+		/// Position of the function in the function storage
+		function: u8,
+		/// Number of arguments
+		count: u8,
+	},
+	/// Suspend the execution, resumable executions return to the caller
+	Yield,
+	/// Print value to "stdout"
+	#[cfg(feature = "std")]
+	Print,
+}
+
+impl Instruction {
+	/// Number of bytes used in bytecode.
+	#[must_use]
+	pub const fn size(self) -> usize {
+		match self {
+			Self::Constant(_) | Self::DefineExternal(_) | Self::GetExternal(_) | Self::SetExternal(_) | Self::Concat(_) => 2,
+			Self::Jmp(_) | Self::JmpIfTrue(_) | Self::JmpIfFalse(_) | Self::Call { .. } => 3,
+			_ => 1,
+		}
+	}
+
+	/// Number of values taken from and put onto the stack.
+	/// [`Instruction::Return`] and [`Instruction::Print`] also work on an empty stack,
+	/// conditional jumps keep the condition on the stack.
+	#[must_use]
+	pub const fn stack_effect(self) -> (usize, usize) {
+		match self {
+			Self::Constant(_) | Self::Nil | Self::True | Self::False | Self::GetExternal(_) => (0, 1),
+			Self::Pop | Self::DefineExternal(_) | Self::SetExternal(_) | Self::Return => (1, 0),
+			#[cfg(feature = "std")]
+			Self::Print => (1, 0),
+			Self::Equal
+			| Self::Greater
+			| Self::Less
+			| Self::Add
+			| Self::Subtract
+			| Self::Multiply
+			| Self::Divide
+			| Self::BitwiseAnd
+			| Self::BitwiseOr
+			| Self::BitwiseXor => (2, 1),
+			Self::BitwiseNot | Self::Not | Self::Negate | Self::JmpIfTrue(_) | Self::JmpIfFalse(_) => (1, 1),
+			Self::Concat(count) | Self::Call { count, .. } => (count as usize, 1),
+			Self::Jmp(_) | Self::Yield => (0, 0),
+		}
+	}
+
+	/// The [`OpCode`] of the instruction.
+	#[must_use]
+	pub const fn op_code(self) -> OpCode {
+		match self {
+			Self::Constant(_) => OpCode::Constant,
+			Self::Nil => OpCode::Nil,
+			Self::True => OpCode::True,
+			Self::False => OpCode::False,
+			Self::Pop => OpCode::Pop,
+			Self::DefineExternal(_) => OpCode::DefineExternal,
+			Self::GetExternal(_) => OpCode::GetExternal,
+			Self::SetExternal(_) => OpCode::SetExternal,
+			Self::Equal => OpCode::Equal,
+			Self::Greater => OpCode::Greater,
+			Self::Less => OpCode::Less,
+			Self::Jmp(_) => OpCode::Jmp,
+			Self::JmpIfTrue(_) => OpCode::JmpIfTrue,
+			Self::JmpIfFalse(_) => OpCode::JmpIfFalse,
+			Self::Add => OpCode::Add,
+			Self::Subtract => OpCode::Subtract,
+			Self::Multiply => OpCode::Multiply,
+			Self::Divide => OpCode::Divide,
+			Self::BitwiseNot => OpCode::BitwiseNot,
+			Self::BitwiseAnd => OpCode::BitwiseAnd,
+			Self::BitwiseOr => OpCode::BitwiseOr,
+			Self::BitwiseXor => OpCode::BitwiseXor,
+			Self::Not => OpCode::Not,
+			Self::Negate => OpCode::Negate,
+			Self::Return => OpCode::Return,
+			Self::Concat(_) => OpCode::Concat,
+			Self::Call { .. } => OpCode::Call,
+			Self::Yield => OpCode::Yield,
+			#[cfg(feature = "std")]
+			Self::Print => OpCode::Print,
+		}
+	}
+
+	/// The jump target, if the instruction is a jump.
+	#[must_use]
+	pub const fn target(self) -> Option<u16> {
+		match self {
+			Self::Jmp(target) | Self::JmpIfTrue(target) | Self::JmpIfFalse(target) => Some(target),
+			_ => None,
+		}
+	}
+
+	/// The same instruction with a different jump target,
+	/// other instructions are returned unchanged.
+	#[must_use]
+	pub const fn with_target(self, target: u16) -> Self {
+		match self {
+			Self::Jmp(_) => Self::Jmp(target),
+			Self::JmpIfTrue(_) => Self::JmpIfTrue(target),
+			Self::JmpIfFalse(_) => Self::JmpIfFalse(target),
+			other => other,
+		}
+	}
+
+	/// Read the instruction at `offset` from bytecode.
+	/// Jump targets are kept as byte offsets.
+	/// Returns `None` for an unknown [`OpCode`] or missing operands.
+	#[must_use]
+	pub fn read(code: &[u8], offset: usize) -> Option<Self> {
+		let operand = |n: usize| code.get(offset + n).copied();
+		let target = || Some((u16::from(operand(1)?) << 8) + u16::from(operand(2)?));
+		let instruction = match OpCode::from_byte(*code.get(offset)?)? {
+			OpCode::None => return None,
+			OpCode::Constant => Self::Constant(operand(1)?),
+			OpCode::Nil => Self::Nil,
+			OpCode::True => Self::True,
+			OpCode::False => Self::False,
+			OpCode::Pop => Self::Pop,
+			OpCode::DefineExternal => Self::DefineExternal(operand(1)?),
+			OpCode::GetExternal => Self::GetExternal(operand(1)?),
+			OpCode::SetExternal => Self::SetExternal(operand(1)?),
+			OpCode::Equal => Self::Equal,
+			OpCode::Greater => Self::Greater,
+			OpCode::Less => Self::Less,
+			OpCode::Jmp => Self::Jmp(target()?),
+			OpCode::JmpIfTrue => Self::JmpIfTrue(target()?),
+			OpCode::JmpIfFalse => Self::JmpIfFalse(target()?),
+			OpCode::Add => Self::Add,
+			OpCode::Subtract => Self::Subtract,
+			OpCode::Multiply => Self::Multiply,
+			OpCode::Divide => Self::Divide,
+			OpCode::BitwiseNot => Self::BitwiseNot,
+			OpCode::BitwiseAnd => Self::BitwiseAnd,
+			OpCode::BitwiseOr => Self::BitwiseOr,
+			OpCode::BitwiseXor => Self::BitwiseXor,
+			OpCode::Not => Self::Not,
+			OpCode::Negate => Self::Negate,
+			OpCode::Return => Self::Return,
+			OpCode::Concat => Self::Concat(operand(1)?),
+			OpCode::Call => Self::Call {
+				function: operand(1)?,
+				count: operand(2)?,
+			},
+			OpCode::Yield => Self::Yield,
+			#[cfg(feature = "std")]
+			OpCode::Print => Self::Print,
+		};
+		Some(instruction)
+	}
+
+	/// Append the instruction to bytecode.
+	/// Jump targets are written as they are, so they must already be byte offsets.
+	pub fn write(self, code: &mut Vec<u8>) {
+		let op_code = self.op_code() as u8;
+		match self {
+			Self::Constant(operand)
+			| Self::DefineExternal(operand)
+			| Self::GetExternal(operand)
+			| Self::SetExternal(operand)
+			| Self::Concat(operand) => code.extend([op_code, operand]),
+			Self::Jmp(target) | Self::JmpIfTrue(target) | Self::JmpIfFalse(target) => {
+				code.push(op_code);
+				code.extend(target.to_be_bytes());
+			}
+			Self::Call { function, count } => code.extend([op_code, function, count]),
+			_ => code.push(op_code),
+		}
+	}
+}
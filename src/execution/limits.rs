@@ -0,0 +1,75 @@
+// Copyright © 2026 Stephan Kunz
+//! [`Limits`] restricting the resources a script may use during one run.
+
+// region:		--- Limits
+/// Limits for the execution of a script by the [`VM`](crate::execution::VM).
+///
+/// Each limit is counted per run, a resumed execution starts counting again.
+/// A limit of `None` means unlimited, which is the default.
+///
+/// Strings created by `+`, template strings and the string library are checked before they are created,
+/// so exceeding a limit does not allocate them. Strings returned by functions registered by the host
+/// can only be checked after they are returned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+	/// Maximum number of executed instructions.
+	pub max_instructions: Option<usize>,
+	/// Maximum length in bytes of a string created by `+`, a template string or a native function.
+	pub max_string_length: Option<usize>,
+	/// Maximum total number of bytes of all strings created.
+	pub max_allocated_bytes: Option<usize>,
+	/// Maximum number of variable definitions and assignments in the environment.
+	pub max_env_writes: Option<usize>,
+}
+
+impl Limits {
+	/// Limits without any restriction.
+	#[must_use]
+	pub const fn unlimited() -> Self {
+		Self {
+			max_instructions: None,
+			max_string_length: None,
+			max_allocated_bytes: None,
+			max_env_writes: None,
+		}
+	}
+
+	/// Set the maximum number of executed instructions.
+	#[must_use]
+	pub const fn with_max_instructions(mut self, limit: usize) -> Self {
+		self.max_instructions = Some(limit);
+		self
+	}
+
+	/// Set the maximum length of created strings.
+	#[must_use]
+	pub const fn with_max_string_length(mut self, limit: usize) -> Self {
+		self.max_string_length = Some(limit);
+		self
+	}
+
+	/// Set the maximum total number of bytes of created strings.
+	#[must_use]
+	pub const fn with_max_allocated_bytes(mut self, limit: usize) -> Self {
+		self.max_allocated_bytes = Some(limit);
+		self
+	}
+
+	/// Set the maximum number of writes to the environment.
+	#[must_use]
+	pub const fn with_max_env_writes(mut self, limit: usize) -> Self {
+		self.max_env_writes = Some(limit);
+		self
+	}
+}
+// endregion:	--- Limits
+
+// region:		--- Usage
+/// The resources used by the current run, checked against the [`Limits`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Usage {
+	pub instructions: usize,
+	pub allocated_bytes: usize,
+	pub env_writes: usize,
+}
+// endregion:	--- Usage
@@ -0,0 +1,51 @@
+// Copyright © 2026 Stephan Kunz
+//! [`Linkage`] of a [`Chunk`] to an [`Environment`].
+
+// region:		--- modules
+use alloc::vec::Vec;
+
+use super::{Chunk, Instruction};
+use crate::environment::{EnvHandle, Environment};
+// endregion:	--- modules
+
+/// The [`EnvHandle`]s of all variables a [`Chunk`] accesses, created by [`Chunk::link`].
+///
+/// A linkage is only valid for the [`Environment`] it was created with.
+/// Variables the environment could not resolve are accessed by their name.
+#[derive(Clone, Debug, Default)]
+pub struct Linkage {
+	/// The handles indexed by the position of the variables name in the constant storage
+	handles: Vec<Option<EnvHandle>>,
+}
+
+impl Linkage {
+	/// Resolves the names of all variables accessed by the [`Chunk`].
+	pub(super) fn new(chunk: &Chunk, globals: &mut impl Environment) -> Self {
+		let mut handles: Vec<Option<EnvHandle>> = Vec::new();
+		for instruction in chunk.instructions() {
+			if let Instruction::DefineExternal(pos) | Instruction::GetExternal(pos) | Instruction::SetExternal(pos) =
+				*instruction
+			{
+				let index = usize::from(pos);
+				if handles.len() <= index {
+					handles.resize(index + 1, None);
+				}
+				if handles[index].is_none()
+					&& let Ok(name) = chunk.read_constant(pos).as_str()
+				{
+					handles[index] = globals.resolve_env(name);
+				}
+			}
+		}
+		Self { handles }
+	}
+
+	/// The handle of the variable whose name is stored at `pos`, if resolved.
+	#[must_use]
+	pub fn handle(&self, pos: u8) -> Option<EnvHandle> {
+		self.handles
+			.get(usize::from(pos))
+			.copied()
+			.flatten()
+	}
+}
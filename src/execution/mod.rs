@@ -2,11 +2,28 @@
 //! Execution implementations.
 
 mod chunk;
+mod debugger;
+mod disassembler;
 mod error;
+mod instruction;
+mod limits;
+mod linkage;
 pub mod op_code;
+#[cfg(feature = "profiling")]
+mod profiler;
+mod suspension;
 mod vm;
 
 // flatten
 pub use chunk::Chunk;
+pub use debugger::Debugger;
+pub use disassembler::{Disassembler, Operands, Record};
 pub use error::{ExecutionError, ExecutionResult};
+pub use instruction::Instruction;
+pub use limits::Limits;
+pub use linkage::Linkage;
+#[cfg(feature = "profiling")]
+pub use profiler::{Profile, Statistics, TraceEntry};
+pub use suspension::{Execution, Suspended};
+pub(crate) use vm::STACK_SIZE;
 pub use vm::VM;
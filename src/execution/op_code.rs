@@ -2,7 +2,7 @@
 //! [`OpCode`] implementation for the bytecode [`Chunk`](crate::execution::Chunk).
 
 /// The available instructions for the virtual machine-
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(u8)]
 pub enum OpCode {
 	/// No operation
@@ -57,43 +57,98 @@ pub enum OpCode {
 	Negate,
 	/// Return value to caller
 	Return,
+	/// Concatenate the given number of values into a string
+	Concat,
+	/// Call a native function
+	Call,
+	/// Suspend the execution
+	Yield,
 	/// Print value to "stdout"
 	#[cfg(feature = "std")]
 	Print = 254,
 }
 
-impl From<u8> for OpCode {
-	fn from(value: u8) -> Self {
+impl OpCode {
+	/// The [`OpCode`] of a byte, `None` for an unknown value.
+	#[must_use]
+	pub const fn from_byte(value: u8) -> Option<Self> {
 		match value {
-			0 => Self::None,
-			1 => Self::Constant,
-			2 => Self::Nil,
-			3 => Self::True,
-			4 => Self::False,
-			5 => Self::Pop,
-			6 => Self::DefineExternal,
-			7 => Self::GetExternal,
-			8 => Self::SetExternal,
-			9 => Self::Equal,
-			10 => Self::Greater,
-			11 => Self::Less,
-			12 => Self::Jmp,
-			13 => Self::JmpIfTrue,
-			14 => Self::JmpIfFalse,
-			15 => Self::Add,
-			16 => Self::Subtract,
-			17 => Self::Multiply,
-			18 => Self::Divide,
-			19 => Self::BitwiseNot,
-			20 => Self::BitwiseAnd,
-			21 => Self::BitwiseOr,
-			22 => Self::BitwiseXor,
-			23 => Self::Not,
-			24 => Self::Negate,
-			25 => Self::Return,
+			0 => Some(Self::None),
+			1 => Some(Self::Constant),
+			2 => Some(Self::Nil),
+			3 => Some(Self::True),
+			4 => Some(Self::False),
+			5 => Some(Self::Pop),
+			6 => Some(Self::DefineExternal),
+			7 => Some(Self::GetExternal),
+			8 => Some(Self::SetExternal),
+			9 => Some(Self::Equal),
+			10 => Some(Self::Greater),
+			11 => Some(Self::Less),
+			12 => Some(Self::Jmp),
+			13 => Some(Self::JmpIfTrue),
+			14 => Some(Self::JmpIfFalse),
+			15 => Some(Self::Add),
+			16 => Some(Self::Subtract),
+			17 => Some(Self::Multiply),
+			18 => Some(Self::Divide),
+			19 => Some(Self::BitwiseNot),
+			20 => Some(Self::BitwiseAnd),
+			21 => Some(Self::BitwiseOr),
+			22 => Some(Self::BitwiseXor),
+			23 => Some(Self::Not),
+			24 => Some(Self::Negate),
+			25 => Some(Self::Return),
+			26 => Some(Self::Concat),
+			27 => Some(Self::Call),
+			28 => Some(Self::Yield),
+			#[cfg(feature = "std")]
+			254 => Some(Self::Print),
+			_ => None,
+		}
+	}
+
+	/// The name of the [`OpCode`] as used in disassembled code.
+	#[must_use]
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::None => "OP_NONE",
+			Self::Constant => "OP_CONSTANT",
+			Self::Nil => "OP_NIL",
+			Self::True => "OP_TRUE",
+			Self::False => "OP_FALSE",
+			Self::Pop => "OP_POP",
+			Self::DefineExternal => "OP_DEFINE_EXTERNAL",
+			Self::GetExternal => "OP_GET_EXTERNAL",
+			Self::SetExternal => "OP_SET_EXTERNAL",
+			Self::Equal => "OP_EQUAL",
+			Self::Greater => "OP_GREATER",
+			Self::Less => "OP_LESS",
+			Self::Jmp => "OP_JMP",
+			Self::JmpIfTrue => "OP_JMP_IF_TRUE",
+			Self::JmpIfFalse => "OP_JMP_IF_FALSE",
+			Self::Add => "OP_ADD",
+			Self::Subtract => "OP_SUBTRACT",
+			Self::Multiply => "OP_MULTIPLY",
+			Self::Divide => "OP_DIVIDE",
+			Self::BitwiseNot => "OP_BITWISE_NOT",
+			Self::BitwiseAnd => "OP_BITWISE_AND",
+			Self::BitwiseOr => "OP_BITWISE_OR",
+			Self::BitwiseXor => "OP_BITWISE_XOR",
+			Self::Not => "OP_NOT",
+			Self::Negate => "OP_NEGATE",
+			Self::Return => "OP_RETURN",
+			Self::Concat => "OP_CONCAT",
+			Self::Call => "OP_CALL",
+			Self::Yield => "OP_YIELD",
 			#[cfg(feature = "std")]
-			254 => Self::Print,
-			_ => todo!("unknown value for OpCode"),
+			Self::Print => "OP_PRINT",
 		}
 	}
 }
+
+impl From<u8> for OpCode {
+	fn from(value: u8) -> Self {
+		Self::from_byte(value).unwrap_or_else(|| todo!("unknown value for OpCode"))
+	}
+}
@@ -0,0 +1,183 @@
+// Copyright © 2026 Stephan Kunz
+//! Tracing of executed instructions and a [`Profile`] of the execution times,
+//! available with feature `profiling`.
+//!
+//! The trace records each executed [`Instruction`] together with the stack before its execution,
+//! the profile counts executions and time per [`OpCode`] and per source line of each script.
+//! Both are collected by the [`VM`](crate::execution::VM) only if enabled.
+
+#[doc(hidden)]
+extern crate std;
+
+// region:		--- modules
+use alloc::{collections::btree_map::BTreeMap, vec::Vec};
+use core::{
+	fmt::Display,
+	hash::{Hash, Hasher},
+	time::Duration,
+};
+use std::hash::DefaultHasher;
+
+use super::{Chunk, Instruction, op_code::OpCode};
+use crate::scripting_value::ScriptingValue;
+// endregion:	--- modules
+
+// region:		--- TraceEntry
+/// An executed instruction with the state before its execution.
+#[derive(Clone, Debug)]
+pub struct TraceEntry {
+	/// Index of the instruction.
+	pub ip: usize,
+	/// Source line of the instruction.
+	pub line: usize,
+	/// The executed instruction.
+	pub instruction: Instruction,
+	/// The values on the stack, bottom first.
+	pub stack: Vec<ScriptingValue>,
+}
+
+impl Display for TraceEntry {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{:04} {:4} {:?} [", self.ip, self.line, self.instruction)?;
+		for (i, value) in self.stack.iter().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "{value}")?;
+		}
+		write!(f, "]")
+	}
+}
+// endregion:	--- TraceEntry
+
+// region:		--- Statistics
+/// Number of executions and the time spent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Statistics {
+	/// Number of executions.
+	pub count: u64,
+	/// Total time of the executions.
+	pub time: Duration,
+}
+
+impl Statistics {
+	fn add(&mut self, time: Duration) {
+		self.count += 1;
+		self.time += time;
+	}
+}
+// endregion:	--- Statistics
+
+// region:		--- Profile
+/// Execution statistics per [`OpCode`] and per source line, accumulated over all profiled runs.
+///
+/// The lines are kept per script, which is identified by the [`Profile::chunk_id`] of its [`Chunk`],
+/// so the same line of different scripts is not mixed up.
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+	op_codes: BTreeMap<OpCode, Statistics>,
+	lines: BTreeMap<(u64, usize), Statistics>,
+	total: Statistics,
+}
+
+impl Profile {
+	/// The statistics per [`OpCode`].
+	pub fn op_codes(&self) -> impl Iterator<Item = (OpCode, &Statistics)> {
+		self.op_codes
+			.iter()
+			.map(|(op_code, statistics)| (*op_code, statistics))
+	}
+
+	/// The statistics per script and source line, the scripts are identified by their [`Profile::chunk_id`].
+	pub fn lines(&self) -> impl Iterator<Item = (u64, usize, &Statistics)> {
+		self.lines
+			.iter()
+			.map(|((chunk, line), statistics)| (*chunk, *line, statistics))
+	}
+
+	/// The statistics per source line of the script compiled into `chunk`.
+	pub fn chunk_lines(&self, chunk: &Chunk) -> impl Iterator<Item = (usize, &Statistics)> {
+		let id = Self::chunk_id(chunk);
+		self.lines
+			.range((id, 0)..=(id, usize::MAX))
+			.map(|((_, line), statistics)| (*line, statistics))
+	}
+
+	/// The id identifying the script of a [`Chunk`] in the profile,
+	/// which is computed from its content, so chunks of the same script get the same id.
+	#[must_use]
+	pub fn chunk_id(chunk: &Chunk) -> u64 {
+		let mut hasher = DefaultHasher::new();
+		chunk.code().hash(&mut hasher);
+		chunk.lines().hash(&mut hasher);
+		for constant in chunk.constants() {
+			constant.type_name().hash(&mut hasher);
+			std::format!("{constant}").hash(&mut hasher);
+		}
+		hasher.finish()
+	}
+
+	/// The statistics of all instructions.
+	#[must_use]
+	pub const fn total(&self) -> Statistics {
+		self.total
+	}
+
+	/// Remove all statistics.
+	pub fn clear(&mut self) {
+		self.op_codes.clear();
+		self.lines.clear();
+		self.total = Statistics::default();
+	}
+
+	/// Record an executed instruction of the chunk with the id `chunk`.
+	pub(super) fn record(&mut self, op_code: OpCode, chunk: u64, line: usize, time: Duration) {
+		self.op_codes
+			.entry(op_code)
+			.or_default()
+			.add(time);
+		self.lines
+			.entry((chunk, line))
+			.or_default()
+			.add(time);
+		self.total.add(time);
+	}
+}
+
+impl Display for Profile {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		writeln!(f, "{:16} {:>10} {:>12}", "op code", "count", "time")?;
+		for (op_code, statistics) in self.op_codes() {
+			writeln!(
+				f,
+				"{:16} {:>10} {:>12?}",
+				std::format!("{op_code:?}"),
+				statistics.count,
+				statistics.time
+			)?;
+		}
+		writeln!(f, "{:16} {:>5} {:>10} {:>12}", "script", "line", "count", "time")?;
+		for (chunk, line, statistics) in self.lines() {
+			writeln!(f, "{chunk:016x} {line:>5} {:>10} {:>12?}", statistics.count, statistics.time)?;
+		}
+		write!(f, "{:16} {:>10} {:>12?}", "total", self.total.count, self.total.time)
+	}
+}
+// endregion:	--- Profile
+
+// region:		--- Instrumentation
+/// The tracing and profiling state of a [`VM`](crate::execution::VM).
+#[derive(Debug, Default)]
+pub struct Instrumentation {
+	/// Whether the executed instructions are traced.
+	pub tracing: bool,
+	/// The trace of the current run.
+	pub trace: Vec<TraceEntry>,
+	/// Whether the execution is profiled.
+	pub profiling: bool,
+	/// The accumulated profile.
+	pub profile: Profile,
+	/// The [`Profile::chunk_id`] of the chunk of the current run, computed at its first profiled instruction.
+	pub chunk: Option<u64>,
+}
+// endregion:	--- Instrumentation
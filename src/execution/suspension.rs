@@ -0,0 +1,74 @@
+// Copyright © 2026 Stephan Kunz
+//! States of a resumable execution of a [`Chunk`](crate::execution::Chunk).
+//!
+//! A resumable execution started with [`VM::start`](crate::execution::VM::start) stops at each `yield`
+//! statement and returns [`Execution::Suspended`], which the host passes to
+//! [`VM::resume`](crate::execution::VM::resume) to continue, e.g. on the next tick of a cooperative scheduler.
+
+// region:		--- modules
+use alloc::vec::Vec;
+
+use crate::scripting_value::ScriptingValue;
+// endregion:	--- modules
+
+// region:		--- Execution
+/// The result of a resumable execution.
+#[derive(Clone, Debug)]
+pub enum Execution {
+	/// The script has finished with its result value.
+	Completed(ScriptingValue),
+	/// The script has reached a `yield` and can be resumed.
+	Suspended(Suspended),
+}
+
+impl Execution {
+	/// Returns `true` if the script has finished.
+	#[must_use]
+	pub const fn is_completed(&self) -> bool {
+		matches!(self, Self::Completed(_))
+	}
+
+	/// The suspended state, if the script has not finished.
+	#[must_use]
+	pub fn into_suspended(self) -> Option<Suspended> {
+		match self {
+			Self::Completed(_) => None,
+			Self::Suspended(suspended) => Some(suspended),
+		}
+	}
+}
+// endregion:	--- Execution
+
+// region:		--- Suspended
+/// The state of a suspended execution.
+/// It must be resumed with the same [`Chunk`](crate::execution::Chunk) it was suspended in.
+#[derive(Clone, Debug)]
+pub struct Suspended {
+	/// Index of the instruction to continue with.
+	pub(super) ip: usize,
+	/// Source line of the instruction to continue with.
+	pub(super) line: usize,
+	/// The values on the stack, bottom first.
+	pub(super) stack: Vec<ScriptingValue>,
+}
+
+impl Suspended {
+	/// Index of the instruction to continue with.
+	#[must_use]
+	pub const fn ip(&self) -> usize {
+		self.ip
+	}
+
+	/// Source line of the instruction to continue with.
+	#[must_use]
+	pub const fn line(&self) -> usize {
+		self.line
+	}
+
+	/// The values on the stack, bottom first.
+	#[must_use]
+	pub fn stack(&self) -> &[ScriptingValue] {
+		&self.stack
+	}
+}
+// endregion:	--- Suspended
@@ -6,18 +6,45 @@
 extern crate std;
 
 // region:		--- modules
-use super::{Chunk, op_code::OpCode};
+use super::{Chunk, Debugger, Execution, Instruction, Limits, Linkage, Suspended, limits::Usage, op_code::OpCode};
+#[cfg(feature = "profiling")]
+use super::{Profile, TraceEntry, profiler::Instrumentation};
 use crate::{
-	environment::Environment,
+	environment::{AsyncEnvironment, Environment},
 	execution::{ExecutionError, ExecutionResult},
+	library::NativeFunction,
 	scripting_value::ScriptingValue,
 };
-use alloc::{borrow::ToOwned, string::ToString};
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::fmt::Write;
 // endregion:	--- modules
 
 /// Stack size is fixed to avoid cache misses, which drastically reduce performance.
 /// For the intended purpose (short inline scripting) this size should be enough.
-const STACK_SIZE: usize = 8;
+pub const STACK_SIZE: usize = 8;
+
+// region:		--- NoEnvironment
+/// An [`Environment`] without any variable.
+/// Used by the asynchronous execution for the instructions not accessing variables.
+struct NoEnvironment;
+
+impl Environment for NoEnvironment {
+	fn define_env(&mut self, key: &str, _value: impl Into<ScriptingValue>) -> Result<(), crate::environment::Error> {
+		Err(crate::environment::Error::EnvVarNotDefined { name: key.into() })
+	}
+
+	fn get_env(&self, key: &str) -> Result<ScriptingValue, crate::environment::Error> {
+		Err(crate::environment::Error::EnvVarNotDefined { name: key.into() })
+	}
+
+	fn set_env(&mut self, key: &str, _value: impl Into<ScriptingValue>) -> Result<(), crate::environment::Error> {
+		Err(crate::environment::Error::EnvVarNotDefined { name: key.into() })
+	}
+}
+// endregion:	--- NoEnvironment
 
 // region:		--- VM
 /// A stack based Virtual Machine.
@@ -31,6 +58,13 @@ pub struct VM {
 	stack: [ScriptingValue; STACK_SIZE],
 	/// Pointer to the next free stack place
 	stack_top: usize,
+	/// Limits for each run
+	limits: Limits,
+	/// Resources used by the current run
+	usage: Usage,
+	/// Tracing and profiling
+	#[cfg(feature = "profiling")]
+	instrumentation: Instrumentation,
 }
 
 impl core::fmt::Debug for VM {
@@ -39,7 +73,9 @@ impl core::fmt::Debug for VM {
 			.field("ip", &self.ip)
 			.field("stack", &self.stack)
 			.field("stack_top", &self.stack_top)
-			.finish()
+			.field("limits", &self.limits)
+			.field("usage", &self.usage)
+			.finish_non_exhaustive()
 	}
 }
 
@@ -49,15 +85,201 @@ impl Default for VM {
 			ip: 0,
 			stack: [const { ScriptingValue::nil() }; STACK_SIZE],
 			stack_top: 0,
+			limits: Limits::unlimited(),
+			usage: Usage::default(),
+			#[cfg(feature = "profiling")]
+			instrumentation: Instrumentation::default(),
 		}
 	}
 }
 
 impl VM {
+	/// Evaluates an operator with constant operands, as used for constant folding
+	/// and by the [`TypeChecker`](crate::compilation::TypeChecker) with a sample value per type.
+	/// # Errors
+	/// - the errors the operator would cause at runtime
+	/// - if the operator can not be evaluated without a [`Chunk`] or an [`Environment`]
+	pub(crate) fn evaluate(operator: Instruction, operands: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+		let mut vm = Self::default();
+		for operand in operands {
+			vm.push(operand.clone())?;
+		}
+		match operator {
+			Instruction::Add | Instruction::Divide | Instruction::Multiply | Instruction::Subtract => {
+				vm.arithmetic_operator(operator.op_code())?;
+			}
+			Instruction::BitwiseAnd | Instruction::BitwiseOr | Instruction::BitwiseXor => {
+				vm.bitwise_operator(operator.op_code())?;
+			}
+			Instruction::BitwiseNot => vm.bitwise_not()?,
+			Instruction::Concat(count) => vm.concat(usize::from(count))?,
+			Instruction::Equal => vm.equal()?,
+			Instruction::Greater | Instruction::Less => vm.comparison_operator(operator.op_code())?,
+			Instruction::Negate => vm.negate()?,
+			Instruction::Not => vm.not()?,
+			_ => {
+				return Err(ExecutionError::Unreachable {
+					file: file!().into(),
+					line: line!(),
+				});
+			}
+		}
+		Ok(vm.pop())
+	}
+
+	/// Set the [`Limits`] for each run.
+	pub const fn set_limits(&mut self, limits: Limits) {
+		self.limits = limits;
+	}
+
+	/// The [`Limits`] for each run.
+	#[must_use]
+	pub const fn limits(&self) -> Limits {
+		self.limits
+	}
+
+	/// Index of the [`Instruction`] started last by the latest run, after an error the failing one.
+	#[must_use]
+	pub const fn last_instruction(&self) -> Option<usize> {
+		self.ip.checked_sub(1)
+	}
+
 	fn reset(&mut self) {
 		self.ip = 0;
 		self.stack = [const { ScriptingValue::nil() }; STACK_SIZE];
 		self.stack_top = 0;
+		self.usage = Usage::default();
+		#[cfg(feature = "profiling")]
+		{
+			self.instrumentation.trace.clear();
+			self.instrumentation.chunk = None;
+		}
+	}
+
+	/// Enable or disable the tracing of executed instructions, the trace is kept for the last run.
+	#[cfg(feature = "profiling")]
+	pub const fn set_tracing(&mut self, enabled: bool) {
+		self.instrumentation.tracing = enabled;
+	}
+
+	/// The executed instructions of the last run, if tracing is enabled.
+	#[cfg(feature = "profiling")]
+	#[must_use]
+	pub fn trace(&self) -> &[TraceEntry] {
+		&self.instrumentation.trace
+	}
+
+	/// Enable or disable the profiling of executed instructions, the profile is accumulated over all runs.
+	#[cfg(feature = "profiling")]
+	pub const fn set_profiling(&mut self, enabled: bool) {
+		self.instrumentation.profiling = enabled;
+	}
+
+	/// The accumulated [`Profile`].
+	#[cfg(feature = "profiling")]
+	#[must_use]
+	pub const fn profile(&self) -> &Profile {
+		&self.instrumentation.profile
+	}
+
+	/// Remove all statistics from the [`Profile`].
+	#[cfg(feature = "profiling")]
+	pub fn clear_profile(&mut self) {
+		self.instrumentation.profile.clear();
+	}
+
+	/// Traces the instruction at `ip` and starts measuring its execution time if enabled.
+	#[cfg(feature = "profiling")]
+	fn before_instruction(&mut self, chunk: &Chunk, instruction: Instruction) -> Option<(u64, usize, std::time::Instant)> {
+		let line = chunk
+			.instruction_lines()
+			.get(self.ip)
+			.copied()
+			.unwrap_or_default();
+		if self.instrumentation.tracing {
+			let stack = self.stack[..self.stack_top].to_vec();
+			self.instrumentation.trace.push(TraceEntry {
+				ip: self.ip,
+				line,
+				instruction,
+				stack,
+			});
+		}
+		if !self.instrumentation.profiling {
+			return None;
+		}
+		let id = *self
+			.instrumentation
+			.chunk
+			.get_or_insert_with(|| Profile::chunk_id(chunk));
+		Some((id, line, std::time::Instant::now()))
+	}
+
+	/// Records the execution time of an instruction started with [`VM::before_instruction`].
+	#[cfg(feature = "profiling")]
+	fn after_instruction(&mut self, instruction: Instruction, measurement: Option<(u64, usize, std::time::Instant)>) {
+		if let Some((chunk, line, started)) = measurement {
+			self.instrumentation
+				.profile
+				.record(instruction.op_code(), chunk, line, started.elapsed());
+		}
+	}
+
+	/// Counts an executed instruction against the [`Limits`].
+	const fn count_instruction(&mut self) -> ExecutionResult<()> {
+		self.usage.instructions += 1;
+		if let Some(limit) = self.limits.max_instructions
+			&& self.usage.instructions > limit
+		{
+			return Err(ExecutionError::InstructionLimit { limit });
+		}
+		Ok(())
+	}
+
+	/// Counts a write to the environment against the [`Limits`].
+	const fn count_env_write(&mut self) -> ExecutionResult<()> {
+		self.usage.env_writes += 1;
+		if let Some(limit) = self.limits.max_env_writes
+			&& self.usage.env_writes > limit
+		{
+			return Err(ExecutionError::EnvWriteLimit { limit });
+		}
+		Ok(())
+	}
+
+	/// Checks a string of `length` bytes against the [`Limits`] before it is created.
+	const fn check_string(&self, length: usize) -> ExecutionResult<()> {
+		if let Some(limit) = self.limits.max_string_length
+			&& length > limit
+		{
+			return Err(ExecutionError::StringLengthLimit { length, limit });
+		}
+		if let Some(limit) = self.limits.max_allocated_bytes
+			&& self.usage.allocated_bytes.saturating_add(length) > limit
+		{
+			return Err(ExecutionError::AllocationLimit { limit });
+		}
+		Ok(())
+	}
+
+	/// Checks the string a native function would create against the [`Limits`] before calling it,
+	/// as far as the function can tell its length in advance.
+	fn check_call(&self, function: &NativeFunction, args: &[ScriptingValue]) -> ExecutionResult<()> {
+		if self.limits.max_string_length.is_none() && self.limits.max_allocated_bytes.is_none() {
+			return Ok(());
+		}
+		function
+			.result_length(args)
+			.map_or(Ok(()), |length| self.check_string(length))
+	}
+
+	/// Counts a created string against the [`Limits`], other values are ignored.
+	fn count_string(&mut self, value: &ScriptingValue) -> ExecutionResult<()> {
+		if let ScriptingValue::String(s) = value {
+			self.check_string(s.len())?;
+			self.usage.allocated_bytes += s.len();
+		}
+		Ok(())
 	}
 
 	const fn peek(&self, distance: usize) -> &ScriptingValue {
@@ -65,7 +287,7 @@ impl VM {
 	}
 
 	fn push(&mut self, value: ScriptingValue) -> ExecutionResult<()> {
-		if self.stack_top == u8::MAX as usize {
+		if self.stack_top == STACK_SIZE {
 			return Err(ExecutionError::StackOverflow);
 		}
 		self.stack[self.stack_top] = value;
@@ -73,21 +295,14 @@ impl VM {
 		Ok(())
 	}
 
-	fn pop(&mut self) -> ScriptingValue {
+	/// Moves the topmost value out of the stack, leaving `nil` behind.
+	const fn pop(&mut self) -> ScriptingValue {
 		self.stack_top -= 1;
-		self.stack[self.stack_top].clone()
-	}
-
-	fn read_jmp_address(&mut self, chunk: &Chunk) -> usize {
-		let byte1 = chunk.code()[self.ip];
-		self.ip += 1;
-		let byte2 = chunk.code()[self.ip];
-		self.ip += 1;
-		((byte1 as usize) << 8) + byte2 as usize
+		core::mem::replace(&mut self.stack[self.stack_top], ScriptingValue::nil())
 	}
 
 	#[allow(clippy::cast_precision_loss)]
-	fn arithmetic_operator(&mut self, operator: &OpCode) -> ExecutionResult<()> {
+	fn arithmetic_operator(&mut self, operator: OpCode) -> ExecutionResult<()> {
 		let b_val = self.pop();
 		let a_val = self.pop();
 		match (&a_val, &b_val) {
@@ -138,10 +353,15 @@ impl VM {
 			}
 			(ScriptingValue::Int64(a), ScriptingValue::Int64(b)) => {
 				let res = match operator {
-					OpCode::Add => a + b,
-					OpCode::Subtract => a - b,
-					OpCode::Multiply => a * b,
-					OpCode::Divide => a / b,
+					OpCode::Add => a.checked_add(*b),
+					OpCode::Subtract => a.checked_sub(*b),
+					OpCode::Multiply => a.checked_mul(*b),
+					OpCode::Divide => {
+						if *b == 0 {
+							return Err(ExecutionError::DivisionByZero);
+						}
+						a.checked_div(*b)
+					}
 					_ => {
 						return Err(ExecutionError::Unreachable {
 							file: file!().into(),
@@ -149,28 +369,23 @@ impl VM {
 						});
 					}
 				};
+				let res = res.ok_or(ExecutionError::Overflow)?;
 				self.push(ScriptingValue::Int64(res))
 			}
-			(ScriptingValue::String(a), _) => {
-				let res = match operator {
-					OpCode::Add => a.to_owned() + &b_val.to_string(),
-					_ => return Err(ExecutionError::OnlyAdd),
-				};
-				self.push(ScriptingValue::String(res))
-			}
-			(_, ScriptingValue::String(b)) => {
-				let res = match operator {
-					OpCode::Add => a_val.to_string() + b,
-					_ => return Err(ExecutionError::OnlyAdd),
-				};
-				self.push(ScriptingValue::String(res))
-			}
+			(ScriptingValue::String(_), _) | (_, ScriptingValue::String(_)) => match operator {
+				OpCode::Add => {
+					self.push(a_val)?;
+					self.push(b_val)?;
+					self.concat(2)
+				}
+				_ => Err(ExecutionError::OnlyAdd),
+			},
 			(ScriptingValue::Nil(), _) | (_, ScriptingValue::Nil()) => Err(ExecutionError::NilValue),
 			(ScriptingValue::Boolean(_), _) | (_, ScriptingValue::Boolean(_)) => Err(ExecutionError::BoolNoArithmetic),
 		}
 	}
 
-	fn bitwise_operator(&mut self, operator: &OpCode) -> ExecutionResult<()> {
+	fn bitwise_operator(&mut self, operator: OpCode) -> ExecutionResult<()> {
 		let b_val = self.pop();
 		let mut a_val = self.pop();
 		match (a_val, b_val) {
@@ -196,7 +411,7 @@ impl VM {
 	}
 
 	#[allow(clippy::cast_precision_loss)]
-	fn comparison_operator(&mut self, operator: &OpCode) -> ExecutionResult<()> {
+	fn comparison_operator(&mut self, operator: OpCode) -> ExecutionResult<()> {
 		let b_val = self.pop();
 		let mut a_val = self.pop();
 		let res = match (a_val, b_val) {
@@ -246,13 +461,59 @@ impl VM {
 		self.push(a_val)
 	}
 
-	fn constant(&mut self, chunk: &Chunk) -> ExecutionResult<()> {
-		let pos = chunk.code()[self.ip];
-		let constant = chunk.read_constant(pos);
-		self.ip += 1;
+	fn constant(&mut self, chunk: &Chunk, pos: u8) -> ExecutionResult<()> {
+		let constant = chunk.read_constant(pos).clone();
 		self.push(constant)
 	}
 
+	/// Concatenates the topmost `count` values into a single string,
+	/// allocating the resulting string only once and only if it is within the [`Limits`].
+	fn concat(&mut self, count: usize) -> ExecutionResult<()> {
+		let Some(start) = self.stack_top.checked_sub(count) else {
+			return Err(ExecutionError::Unreachable {
+				file: file!().into(),
+				line: line!(),
+			});
+		};
+		let values = &self.stack[start..self.stack_top];
+		let length = values
+			.iter()
+			.map(ScriptingValue::display_len)
+			.sum();
+		self.check_string(length)?;
+		let mut res = String::with_capacity(length);
+		for value in values {
+			match value {
+				ScriptingValue::String(s) => res.push_str(s),
+				value => {
+					let _ = write!(res, "{value}");
+				}
+			}
+		}
+		self.stack_top = start;
+		let res = ScriptingValue::String(res.into());
+		self.count_string(&res)?;
+		self.push(res)
+	}
+
+	/// Calls a native function with the topmost values as arguments.
+	fn call(&mut self, chunk: &Chunk, pos: u8, count: u8) -> ExecutionResult<()> {
+		let count = usize::from(count);
+		let Some(start) = self.stack_top.checked_sub(count) else {
+			return Err(ExecutionError::Unreachable {
+				file: file!().into(),
+				line: line!(),
+			});
+		};
+		let function = chunk.read_function(pos);
+		let args = &self.stack[start..self.stack_top];
+		self.check_call(function, args)?;
+		let res = function.call(args)?;
+		self.stack_top = start;
+		self.count_string(&res)?;
+		self.push(res)
+	}
+
 	#[allow(clippy::cast_precision_loss)]
 	fn equal(&mut self) -> ExecutionResult<()> {
 		let b_val = self.pop();
@@ -283,7 +544,7 @@ impl VM {
 	fn negate(&mut self) -> ExecutionResult<()> {
 		let val = self.pop();
 		let res = match val {
-			ScriptingValue::Int64(v) => ScriptingValue::Int64(-v),
+			ScriptingValue::Int64(v) => ScriptingValue::Int64(v.checked_neg().ok_or(ExecutionError::Overflow)?),
 			ScriptingValue::Float64(v) => ScriptingValue::Float64(-v),
 			_ => {
 				return Err(ExecutionError::NoNumber {
@@ -327,113 +588,581 @@ impl VM {
 		}
 	}
 
-	fn define_global(&mut self, chunk: &Chunk, globals: &mut impl Environment) -> ExecutionResult<()> {
-		let pos = chunk.code()[self.ip];
-		let name_val = chunk.read_constant(pos);
-		self.ip += 1;
+	fn define_global(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		pos: u8,
+		globals: &mut impl Environment,
+	) -> ExecutionResult<()> {
+		self.count_env_write()?;
 		let value_val = self.pop();
-		//let name = chunk.get_string(name_val.as_string_pos()?);
-		globals.define_env(&name_val.to_string(), value_val)?;
+		match linkage.handle(pos) {
+			Some(handle) => globals.define_env_handle(handle, value_val)?,
+			None => globals.define_env(chunk.read_constant(pos).as_str()?, value_val)?,
+		}
 		Ok(())
 	}
 
-	fn get_global(&mut self, chunk: &Chunk, globals: &impl Environment) -> ExecutionResult<()> {
-		let pos = chunk.code()[self.ip];
-		let name_val = chunk.read_constant(pos);
-		self.ip += 1;
-		let val = globals.get_env(&name_val.to_string())?;
+	fn get_global(&mut self, chunk: &Chunk, linkage: &Linkage, pos: u8, globals: &impl Environment) -> ExecutionResult<()> {
+		let val = match linkage.handle(pos) {
+			Some(handle) => globals.get_env_handle(handle)?,
+			None => globals.get_env(chunk.read_constant(pos).as_str()?)?,
+		};
 		self.push(val)?;
 		Ok(())
 	}
 
-	fn set_global(&mut self, chunk: &Chunk, globals: &mut impl Environment) -> ExecutionResult<()> {
-		let pos = chunk.code()[self.ip];
-		let name_val = chunk.read_constant(pos);
-		self.ip += 1;
-		// let name = chunk.get_string(name_val.as_string_pos()?);
+	fn set_global(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		pos: u8,
+		globals: &mut impl Environment,
+	) -> ExecutionResult<()> {
+		self.count_env_write()?;
 		let value_val = self.pop();
-		globals.set_env(&name_val.to_string(), value_val)?;
+		match linkage.handle(pos) {
+			Some(handle) => globals.set_env_handle(handle, value_val)?,
+			None => globals.set_env(chunk.read_constant(pos).as_str()?, value_val)?,
+		}
 		Ok(())
 	}
 
-	/// Execute a [`Chunk`] with the virtual machine,
+	/// Execute a [`Chunk`] with the virtual machine using its lowered [`Instruction`]s,
 	/// Returns the topmost stack [`ScriptingValue`] if there is one, otherwise [`ScriptingValue::nil()`].
+	/// Variables are accessed by their name, `yield` statements are ignored.
 	/// # Errors
-	/// - unknown `OpCode`
+	/// - passes through the errors of the executed instructions
 	pub fn run(
 		&mut self,
 		chunk: &Chunk,
 		globals: &mut impl Environment,
 		#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+	) -> ExecutionResult<ScriptingValue> {
+		#[cfg(feature = "std")]
+		let res = self.run_linked(chunk, &Linkage::default(), globals, stdout);
+		#[cfg(not(feature = "std"))]
+		let res = self.run_linked(chunk, &Linkage::default(), globals);
+		res
+	}
+
+	/// Execute a [`Chunk`] like [`VM::run`], accessing variables by the handles of a [`Linkage`].
+	/// The linkage must have been created by [`Chunk::link`] with the same [`Environment`].
+	/// # Errors
+	/// - passes through the errors of the executed instructions
+	pub fn run_linked(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		globals: &mut impl Environment,
+		#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+	) -> ExecutionResult<ScriptingValue> {
+		self.reset();
+		// ignore empty chunks
+		if chunk.instructions().is_empty() {
+			return Ok(ScriptingValue::nil());
+		}
+
+		loop {
+			self.count_instruction()?;
+			let instruction = chunk.instructions()[self.ip];
+			#[cfg(feature = "profiling")]
+			let measurement = self.before_instruction(chunk, instruction);
+			self.ip += 1;
+			#[cfg(feature = "std")]
+			let res = self.execute(instruction, chunk, linkage, globals, stdout)?;
+			#[cfg(not(feature = "std"))]
+			let res = self.execute(instruction, chunk, linkage, globals)?;
+			#[cfg(feature = "profiling")]
+			self.after_instruction(instruction, measurement);
+			if let Some(value) = res {
+				return Ok(value);
+			}
+		}
+	}
+
+	/// Start a resumable execution of a [`Chunk`], accessing variables like [`VM::run_linked`].
+	/// The execution stops at each `yield` returning [`Execution::Suspended`],
+	/// which is continued with [`VM::resume`].
+	/// # Errors
+	/// - passes through the errors of the executed instructions
+	pub fn start(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		globals: &mut impl Environment,
+		#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+	) -> ExecutionResult<Execution> {
+		self.reset();
+		// ignore empty chunks
+		if chunk.instructions().is_empty() {
+			return Ok(Execution::Completed(ScriptingValue::nil()));
+		}
+		#[cfg(feature = "std")]
+		let res = self.run_resumable(chunk, linkage, globals, stdout);
+		#[cfg(not(feature = "std"))]
+		let res = self.run_resumable(chunk, linkage, globals);
+		res
+	}
+
+	/// Resume a [`Suspended`] execution of the same [`Chunk`] and [`Linkage`] it was started with.
+	/// # Errors
+	/// - [`ExecutionError::InvalidResume`] if the suspended state does not fit to the chunk
+	/// - passes through the errors of the executed instructions
+	pub fn resume(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		suspended: Suspended,
+		globals: &mut impl Environment,
+		#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+	) -> ExecutionResult<Execution> {
+		if suspended.ip == 0
+			|| chunk.instructions().get(suspended.ip - 1) != Some(&Instruction::Yield)
+			|| suspended.stack.len() > STACK_SIZE
+		{
+			return Err(ExecutionError::InvalidResume);
+		}
+		self.restore(suspended)?;
+		#[cfg(feature = "std")]
+		let res = self.run_resumable(chunk, linkage, globals, stdout);
+		#[cfg(not(feature = "std"))]
+		let res = self.run_resumable(chunk, linkage, globals);
+		res
+	}
+
+	/// Runs the lowered [`Instruction`]s from the current instruction pointer until the end or a `yield`.
+	fn run_resumable(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		globals: &mut impl Environment,
+		#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+	) -> ExecutionResult<Execution> {
+		loop {
+			self.count_instruction()?;
+			let instruction = chunk.instructions()[self.ip];
+			#[cfg(feature = "profiling")]
+			let measurement = self.before_instruction(chunk, instruction);
+			self.ip += 1;
+			if instruction == Instruction::Yield {
+				return Ok(Execution::Suspended(self.suspend(chunk)));
+			}
+			#[cfg(feature = "std")]
+			let res = self.execute(instruction, chunk, linkage, globals, stdout)?;
+			#[cfg(not(feature = "std"))]
+			let res = self.execute(instruction, chunk, linkage, globals)?;
+			#[cfg(feature = "profiling")]
+			self.after_instruction(instruction, measurement);
+			if let Some(value) = res {
+				return Ok(Execution::Completed(value));
+			}
+		}
+	}
+
+	/// Moves the state of the execution out of the virtual machine.
+	fn suspend(&mut self, chunk: &Chunk) -> Suspended {
+		let mut stack = Vec::with_capacity(self.stack_top);
+		for value in &mut self.stack[..self.stack_top] {
+			stack.push(core::mem::replace(value, ScriptingValue::nil()));
+		}
+		self.stack_top = 0;
+		let line = chunk
+			.instruction_lines()
+			.get(self.ip)
+			.copied()
+			.unwrap_or_default();
+		Suspended {
+			ip: self.ip,
+			line,
+			stack,
+		}
+	}
+
+	/// Moves a suspended state into the virtual machine, the counted usage starts again.
+	fn restore(&mut self, suspended: Suspended) -> ExecutionResult<()> {
+		self.reset();
+		self.ip = suspended.ip;
+		for value in suspended.stack {
+			self.push(value)?;
+		}
+		Ok(())
+	}
+
+	/// Execute a single [`Instruction`] of a [`Chunk`] under control of a [`Debugger`],
+	/// starting a new execution if the debugger has no paused one.
+	/// A new execution pauses before its first instruction.
+	/// Returns the result value when the execution has finished, otherwise the state of the paused
+	/// execution is available with [`Debugger::paused`].
+	/// # Errors
+	/// - [`ExecutionError::InvalidResume`] if the paused execution belongs to another chunk
+	/// - passes through the errors of the executed instructions
+	pub fn debug_step(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		debugger: &mut Debugger,
+		globals: &mut impl Environment,
+		#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+	) -> ExecutionResult<Option<ScriptingValue>> {
+		#[cfg(feature = "std")]
+		let res = self.run_debug(chunk, linkage, debugger, true, globals, stdout);
+		#[cfg(not(feature = "std"))]
+		let res = self.run_debug(chunk, linkage, debugger, true, globals);
+		res
+	}
+
+	/// Continue the execution of a [`Chunk`] under control of a [`Debugger`] until a breakpoint is reached,
+	/// starting a new execution if the debugger has no paused one.
+	/// The execution pauses before the first instruction of a source line with a breakpoint.
+	/// Returns the result value when the execution has finished, otherwise the state of the paused
+	/// execution is available with [`Debugger::paused`].
+	/// # Errors
+	/// - [`ExecutionError::InvalidResume`] if the paused execution belongs to another chunk
+	/// - passes through the errors of the executed instructions
+	pub fn debug_continue(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		debugger: &mut Debugger,
+		globals: &mut impl Environment,
+		#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+	) -> ExecutionResult<Option<ScriptingValue>> {
+		#[cfg(feature = "std")]
+		let res = self.run_debug(chunk, linkage, debugger, false, globals, stdout);
+		#[cfg(not(feature = "std"))]
+		let res = self.run_debug(chunk, linkage, debugger, false, globals);
+		res
+	}
+
+	/// Runs the lowered [`Instruction`]s until the next stop of the [`Debugger`] or the end.
+	fn run_debug(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		debugger: &mut Debugger,
+		single_step: bool,
+		globals: &mut impl Environment,
+		#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+	) -> ExecutionResult<Option<ScriptingValue>> {
+		// the line of the last executed instruction, a breakpoint stops only when entering a line
+		let mut last_line = None;
+		let resumed = if let Some(paused) = debugger.take_paused(chunk)? {
+			if paused.ip >= chunk.instructions().len() || paused.stack.len() > STACK_SIZE {
+				return Err(ExecutionError::InvalidResume);
+			}
+			last_line = Some(paused.line);
+			self.restore(paused)?;
+			true
+		} else {
+			self.reset();
+			// ignore empty chunks
+			if chunk.instructions().is_empty() {
+				return Ok(Some(ScriptingValue::nil()));
+			}
+			false
+		};
+
+		let mut executed = 0_usize;
+		loop {
+			let line = chunk.instruction_lines()[self.ip];
+			let stop = if single_step {
+				executed > 0 || !resumed
+			} else {
+				last_line != Some(line) && debugger.has_breakpoint(line)
+			};
+			if stop {
+				debugger.pause(chunk, self.suspend(chunk));
+				return Ok(None);
+			}
+			last_line = Some(line);
+
+			self.count_instruction()?;
+			let instruction = chunk.instructions()[self.ip];
+			self.ip += 1;
+			#[cfg(feature = "std")]
+			let res = self.execute(instruction, chunk, linkage, globals, stdout)?;
+			#[cfg(not(feature = "std"))]
+			let res = self.execute(instruction, chunk, linkage, globals)?;
+			if let Some(value) = res {
+				return Ok(Some(value));
+			}
+			executed += 1;
+		}
+	}
+
+	/// Execute a [`Chunk`] asynchronously, awaiting the [`AsyncEnvironment`] and asynchronous native functions.
+	/// The returned [`Future`](core::future::Future) does not depend on any specific executor.
+	/// Variables are accessed by their name, `yield` statements are ignored.
+	/// # Errors
+	/// - passes through the errors of the executed instructions
+	pub async fn run_async(
+		&mut self,
+		chunk: &Chunk,
+		globals: &mut impl AsyncEnvironment,
+		#[cfg(feature = "std")] stdout: &mut (impl std::io::Write + Send),
 	) -> ExecutionResult<ScriptingValue> {
 		self.reset();
 		// ignore empty chunks
-		if chunk.code().is_empty() {
+		if chunk.instructions().is_empty() {
 			return Ok(ScriptingValue::nil());
 		}
 
 		loop {
-			//std::dbg!(self.ip);
-			let instruction: OpCode = chunk.code()[self.ip].into();
+			self.count_instruction()?;
+			let instruction = chunk.instructions()[self.ip];
 			self.ip += 1;
 			match instruction {
-				OpCode::Add | OpCode::Divide | OpCode::Multiply | OpCode::Subtract => {
-					self.arithmetic_operator(&instruction)?;
+				Instruction::Call { function, count } if chunk.read_function(function).is_async() => {
+					self.call_async(chunk, function, count).await?;
 				}
-				OpCode::BitwiseAnd | OpCode::BitwiseOr | OpCode::BitwiseXor => {
-					self.bitwise_operator(&instruction)?;
+				Instruction::DefineExternal(pos) => {
+					self.count_env_write()?;
+					let value = self.pop();
+					globals
+						.define_env_async(chunk.read_constant(pos).as_str()?, value)
+						.await?;
 				}
-				OpCode::BitwiseNot => self.bitwise_not()?,
-				OpCode::Constant => self.constant(chunk)?,
-				OpCode::DefineExternal => self.define_global(chunk, globals)?,
-				OpCode::Equal => self.equal()?,
-				OpCode::False => self.push(ScriptingValue::Boolean(false))?,
-				OpCode::GetExternal => self.get_global(chunk, globals)?,
-				OpCode::Greater => self.comparison_operator(&instruction)?,
-				OpCode::Jmp => {
-					let target = self.read_jmp_address(chunk);
-					self.ip = target;
+				Instruction::GetExternal(pos) => {
+					let value = globals
+						.get_env_async(chunk.read_constant(pos).as_str()?)
+						.await?;
+					self.push(value)?;
 				}
-				OpCode::JmpIfFalse => {
-					let target = self.read_jmp_address(chunk);
-					if !self.peek(0).as_bool()? {
-						self.ip = target;
-					}
+				Instruction::SetExternal(pos) => {
+					self.count_env_write()?;
+					let value = self.pop();
+					globals
+						.set_env_async(chunk.read_constant(pos).as_str()?, value)
+						.await?;
 				}
-				OpCode::JmpIfTrue => {
-					let target = self.read_jmp_address(chunk);
-					if self.peek(0).as_bool()? {
-						self.ip = target;
+				_ => {
+					#[cfg(feature = "std")]
+					let res = self.execute(instruction, chunk, &Linkage::default(), &mut NoEnvironment, stdout)?;
+					#[cfg(not(feature = "std"))]
+					let res = self.execute(instruction, chunk, &Linkage::default(), &mut NoEnvironment)?;
+					if let Some(value) = res {
+						return Ok(value);
 					}
 				}
-				OpCode::Less => self.comparison_operator(&instruction)?,
-				OpCode::Negate => self.negate()?,
-				OpCode::Nil => self.push(ScriptingValue::nil())?,
-				OpCode::Not => self.not()?,
-				OpCode::Pop => {
-					self.pop();
-				}
-				#[cfg(feature = "std")]
-				OpCode::Print => self.print(stdout),
-				OpCode::Return => {
-					let val = if self.stack_top > 0 {
-						self.pop()
-					} else {
-						ScriptingValue::nil()
-					};
-					//chunk.restore_state();
-					return Ok(val);
+			}
+		}
+	}
+
+	/// Calls a native function moving the arguments out of the stack while it is awaited.
+	async fn call_async(&mut self, chunk: &Chunk, pos: u8, count: u8) -> ExecutionResult<()> {
+		let count = usize::from(count);
+		let Some(start) = self.stack_top.checked_sub(count) else {
+			return Err(ExecutionError::Unreachable {
+				file: file!().into(),
+				line: line!(),
+			});
+		};
+		let function = chunk.read_function(pos);
+		self.check_call(function, &self.stack[start..self.stack_top])?;
+		let mut args = Vec::with_capacity(count);
+		for value in &mut self.stack[start..self.stack_top] {
+			args.push(core::mem::replace(value, ScriptingValue::nil()));
+		}
+		self.stack_top = start;
+		let res = function.call_async(args).await?;
+		self.count_string(&res)?;
+		self.push(res)
+	}
+
+	/// Execute a single [`Instruction`], the instruction pointer must already point to the next one.
+	/// Jumps set the instruction pointer to the target, so the unit of the target must match the
+	/// unit of the instruction pointer.
+	/// Returns the result value on [`Instruction::Return`].
+	fn execute(
+		&mut self,
+		instruction: Instruction,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		globals: &mut impl Environment,
+		#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+	) -> ExecutionResult<Option<ScriptingValue>> {
+		match instruction {
+			Instruction::Add | Instruction::Divide | Instruction::Multiply | Instruction::Subtract => {
+				self.arithmetic_operator(instruction.op_code())?;
+			}
+			Instruction::BitwiseAnd | Instruction::BitwiseOr | Instruction::BitwiseXor => {
+				self.bitwise_operator(instruction.op_code())?;
+			}
+			Instruction::BitwiseNot => self.bitwise_not()?,
+			Instruction::Call { function, count } => self.call(chunk, function, count)?,
+			Instruction::Concat(count) => self.concat(usize::from(count))?,
+			Instruction::Constant(pos) => self.constant(chunk, pos)?,
+			Instruction::DefineExternal(pos) => self.define_global(chunk, linkage, pos, globals)?,
+			Instruction::Equal => self.equal()?,
+			Instruction::False => self.push(ScriptingValue::Boolean(false))?,
+			Instruction::GetExternal(pos) => self.get_global(chunk, linkage, pos, globals)?,
+			Instruction::Greater | Instruction::Less => self.comparison_operator(instruction.op_code())?,
+			Instruction::Jmp(target) => self.ip = usize::from(target),
+			Instruction::JmpIfFalse(target) => {
+				if !self.peek(0).as_bool()? {
+					self.ip = usize::from(target);
 				}
-				OpCode::SetExternal => self.set_global(chunk, globals)?,
-				OpCode::True => self.push(ScriptingValue::Boolean(true))?,
-				_ => {
-					return Err(ExecutionError::Unreachable {
-						file: file!().into(),
-						line: line!(),
-					});
+			}
+			Instruction::JmpIfTrue(target) => {
+				if self.peek(0).as_bool()? {
+					self.ip = usize::from(target);
 				}
 			}
+			Instruction::Negate => self.negate()?,
+			Instruction::Nil => self.push(ScriptingValue::nil())?,
+			Instruction::Not => self.not()?,
+			Instruction::Pop => {
+				self.pop();
+			}
+			#[cfg(feature = "std")]
+			Instruction::Print => self.print(stdout),
+			Instruction::Return => {
+				let val = if self.stack_top > 0 {
+					self.pop()
+				} else {
+					ScriptingValue::nil()
+				};
+				return Ok(Some(val));
+			}
+			Instruction::SetExternal(pos) => self.set_global(chunk, linkage, pos, globals)?,
+			Instruction::True => self.push(ScriptingValue::Boolean(true))?,
+			// only resumable executions are suspended
+			Instruction::Yield => {}
 		}
+		Ok(None)
 	}
 }
 // endregion:	--- VM
+
+// region:		--- bytecode dispatch
+/// The dispatch of the bytecode before the lowering into [`Instruction`]s,
+/// kept as baseline for the benchmark `dispatch`.
+#[cfg(feature = "bench")]
+mod bytecode {
+	#[cfg(feature = "std")]
+	use super::std;
+	use super::{Chunk, ExecutionError, ExecutionResult, Linkage, OpCode, ScriptingValue, VM};
+	use crate::environment::Environment;
+
+	impl VM {
+		/// Execute a [`Chunk`] with the virtual machine decoding the bytecode while running.
+		/// Returns the topmost stack [`ScriptingValue`] if there is one, otherwise [`ScriptingValue::nil()`].
+		/// # Errors
+		/// - unknown `OpCode`
+		/// - passes through the errors of the executed instructions
+		pub fn run_bytecode(
+			&mut self,
+			chunk: &Chunk,
+			globals: &mut impl Environment,
+			#[cfg(feature = "std")] stdout: &mut impl std::io::Write,
+		) -> ExecutionResult<ScriptingValue> {
+			self.reset();
+			// ignore empty chunks
+			if chunk.code().is_empty() {
+				return Ok(ScriptingValue::nil());
+			}
+
+			let linkage = Linkage::default();
+			loop {
+				self.count_instruction()?;
+				let instruction: OpCode = chunk.code()[self.ip].into();
+				self.ip += 1;
+				match instruction {
+					OpCode::Add | OpCode::Divide | OpCode::Multiply | OpCode::Subtract => {
+						self.arithmetic_operator(instruction)?;
+					}
+					OpCode::BitwiseAnd | OpCode::BitwiseOr | OpCode::BitwiseXor => {
+						self.bitwise_operator(instruction)?;
+					}
+					OpCode::BitwiseNot => self.bitwise_not()?,
+					OpCode::Call => {
+						let pos = self.read_byte(chunk);
+						let count = self.read_byte(chunk);
+						self.call(chunk, pos, count)?;
+					}
+					OpCode::Concat => {
+						let count = self.read_byte(chunk);
+						self.concat(usize::from(count))?;
+					}
+					OpCode::Constant => {
+						let pos = self.read_byte(chunk);
+						self.constant(chunk, pos)?;
+					}
+					OpCode::DefineExternal => {
+						let pos = self.read_byte(chunk);
+						self.define_global(chunk, &linkage, pos, globals)?;
+					}
+					OpCode::Equal => self.equal()?,
+					OpCode::False => self.push(ScriptingValue::Boolean(false))?,
+					OpCode::GetExternal => {
+						let pos = self.read_byte(chunk);
+						self.get_global(chunk, &linkage, pos, globals)?;
+					}
+					OpCode::Greater | OpCode::Less => self.comparison_operator(instruction)?,
+					OpCode::Jmp => {
+						let target = self.read_jmp_address(chunk);
+						self.ip = target;
+					}
+					OpCode::JmpIfFalse => {
+						let target = self.read_jmp_address(chunk);
+						if !self.peek(0).as_bool()? {
+							self.ip = target;
+						}
+					}
+					OpCode::JmpIfTrue => {
+						let target = self.read_jmp_address(chunk);
+						if self.peek(0).as_bool()? {
+							self.ip = target;
+						}
+					}
+					OpCode::Negate => self.negate()?,
+					OpCode::Nil => self.push(ScriptingValue::nil())?,
+					OpCode::Not => self.not()?,
+					OpCode::Pop => {
+						self.pop();
+					}
+					#[cfg(feature = "std")]
+					OpCode::Print => self.print(stdout),
+					OpCode::Return => {
+						let val = if self.stack_top > 0 {
+							self.pop()
+						} else {
+							ScriptingValue::nil()
+						};
+						return Ok(val);
+					}
+					OpCode::SetExternal => {
+						let pos = self.read_byte(chunk);
+						self.set_global(chunk, &linkage, pos, globals)?;
+					}
+					OpCode::True => self.push(ScriptingValue::Boolean(true))?,
+					// only resumable executions are suspended
+					OpCode::Yield => {}
+					OpCode::None => {
+						return Err(ExecutionError::Unreachable {
+							file: file!().into(),
+							line: line!(),
+						});
+					}
+				}
+			}
+		}
+
+		/// Reads the operand byte at the instruction pointer.
+		fn read_byte(&mut self, chunk: &Chunk) -> u8 {
+			let byte = chunk.code()[self.ip];
+			self.ip += 1;
+			byte
+		}
+
+		/// Reads the 2 byte jump address at the instruction pointer.
+		fn read_jmp_address(&mut self, chunk: &Chunk) -> usize {
+			let high = usize::from(self.read_byte(chunk));
+			let low = usize::from(self.read_byte(chunk));
+			(high << 8) + low
+		}
+	}
+}
+// endregion:	--- bytecode dispatch
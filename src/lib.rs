@@ -8,18 +8,23 @@ doc_comment::doctest!("../README.md");
 #[doc(hidden)]
 extern crate alloc;
 
+pub mod cache;
 pub mod compilation;
+pub mod engine;
 pub mod environment;
 pub mod error;
 pub mod execution;
+pub mod library;
 pub mod prelude;
 pub mod runtime;
 pub mod scripting_value;
 
 // flatten
+pub use engine::{Engine, SharedEngine};
 pub use environment::{DefaultEnvironment, Environment};
 pub use error::{Error, Result};
 pub use execution::Chunk;
+pub use library::Library;
 pub use runtime::{Runtime, SharedRuntime};
 pub use scripting_value::ScriptingValue;
 
@@ -0,0 +1,104 @@
+// Copyright © 2026 Stephan Kunz
+//! Type conversion and inspection functions of the standard library.
+//!
+//! Strings are parsed with the rules of [`ScriptingValue::from_str`] before being converted.
+//! These functions are always available, they are also used for the `as` operator.
+
+// region:		--- modules
+use alloc::string::ToString;
+use core::str::FromStr;
+
+use crate::{
+	execution::{ExecutionError, ExecutionResult},
+	scripting_value::ScriptingValue,
+};
+
+use super::Library;
+// endregion:	--- modules
+
+/// Registers the conversion functions.
+pub(super) fn register(library: &mut Library) {
+	library.insert("bool", 1..=1, to_bool);
+	library.insert("float", 1..=1, to_float);
+	library.insert("int", 1..=1, to_int);
+	library.insert("str", 1..=1, to_str);
+	library.insert("typeof", 1..=1, type_of);
+}
+
+/// Maps a type name used with the `as` operator to the name of its conversion function.
+pub fn conversion_function(type_name: &str) -> Option<&'static str> {
+	match type_name {
+		"bool" => Some("bool"),
+		"float" => Some("float"),
+		"int" => Some("int"),
+		"str" | "string" => Some("str"),
+		_ => None,
+	}
+}
+
+fn failed(value: &ScriptingValue, into: &str) -> ExecutionError {
+	ExecutionError::Conversion {
+		value: value.to_string().into(),
+		into: into.into(),
+	}
+}
+
+/// Parses a string, other values are returned unchanged.
+fn parsed(value: &ScriptingValue) -> ExecutionResult<ScriptingValue> {
+	match value {
+		ScriptingValue::String(s) => ScriptingValue::from_str(s.trim()),
+		value => Ok(value.clone()),
+	}
+}
+
+/// `bool(x)`, numbers are `true` if not zero, `nil` is `false`.
+fn to_bool(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	match parsed(&args[0])? {
+		ScriptingValue::Boolean(b) => Ok(ScriptingValue::Boolean(b)),
+		ScriptingValue::Int64(i) => Ok(ScriptingValue::Boolean(i != 0)),
+		ScriptingValue::Float64(f) => Ok(ScriptingValue::Boolean(f != 0.0)),
+		ScriptingValue::Nil() => Ok(ScriptingValue::Boolean(false)),
+		ScriptingValue::String(_) => Err(failed(&args[0], "bool")),
+	}
+}
+
+/// `float(x)`, booleans are converted to `1.0` or `0.0`.
+#[allow(clippy::cast_precision_loss)]
+fn to_float(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	match parsed(&args[0])? {
+		ScriptingValue::Boolean(b) => Ok(ScriptingValue::Float64(if b { 1.0 } else { 0.0 })),
+		ScriptingValue::Int64(i) => Ok(ScriptingValue::Float64(i as f64)),
+		ScriptingValue::Float64(f) => Ok(ScriptingValue::Float64(f)),
+		ScriptingValue::Nil() | ScriptingValue::String(_) => Err(failed(&args[0], "float")),
+	}
+}
+
+/// `int(x)`, floats are truncated towards zero, booleans are converted to `1` or `0`.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_precision_loss)]
+fn to_int(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	match parsed(&args[0])? {
+		ScriptingValue::Boolean(b) => Ok(ScriptingValue::Int64(i64::from(b))),
+		ScriptingValue::Int64(i) => Ok(ScriptingValue::Int64(i)),
+		// `i64::MAX as f64` is rounded up to 2^63, which is already out of range
+		ScriptingValue::Float64(f) if f.is_finite() && f >= i64::MIN as f64 && f < i64::MAX as f64 => {
+			Ok(ScriptingValue::Int64(f as i64))
+		}
+		_ => Err(failed(&args[0], "int")),
+	}
+}
+
+/// `str(x)`, the same representation as used by `print`.
+#[allow(clippy::unnecessary_wraps)]
+fn to_str(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	match &args[0] {
+		ScriptingValue::String(s) => Ok(ScriptingValue::String(s.clone())),
+		value => Ok(ScriptingValue::String(value.to_string().into())),
+	}
+}
+
+/// `typeof(x)` returns the name of the type.
+#[allow(clippy::unnecessary_wraps)]
+fn type_of(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::String(args[0].type_name().into()))
+}
@@ -0,0 +1,202 @@
+// Copyright © 2026 Stephan Kunz
+//! Math functions and constants of the standard library.
+//!
+//! Mixing integer and float arguments follows the rules of the arithmetic operators:
+//! if any argument is a float, the calculation is done with floats.
+//! Functions that are only defined on reals always return a float.
+
+// region:		--- modules
+use alloc::string::ToString;
+
+use crate::{
+	execution::{ExecutionError, ExecutionResult},
+	scripting_value::ScriptingValue,
+};
+
+use super::Library;
+// endregion:	--- modules
+
+/// Registers the math functions and constants.
+pub(super) fn register(library: &mut Library) {
+	library.insert_constant("E", ScriptingValue::Float64(core::f64::consts::E));
+	library.insert_constant("PI", ScriptingValue::Float64(core::f64::consts::PI));
+	library.insert_constant("TAU", ScriptingValue::Float64(core::f64::consts::TAU));
+
+	library.insert("abs", 1..=1, abs);
+	library.insert("atan2", 2..=2, atan2);
+	library.insert("ceil", 1..=1, ceil);
+	library.insert("clamp", 3..=3, clamp);
+	library.insert("cos", 1..=1, cos);
+	library.insert("floor", 1..=1, floor);
+	library.insert("max", 2..=u8::MAX, max);
+	library.insert("min", 2..=u8::MAX, min);
+	library.insert("pow", 2..=2, pow);
+	library.insert("round", 1..=1, round);
+	library.insert("sin", 1..=1, sin);
+	library.insert("sqrt", 1..=1, sqrt);
+	library.insert("tan", 1..=1, tan);
+}
+
+/// A numeric argument.
+#[derive(Clone, Copy)]
+enum Number {
+	Int(i64),
+	Float(f64),
+}
+
+impl Number {
+	/// Get the numeric value of an argument.
+	fn of(value: &ScriptingValue) -> ExecutionResult<Self> {
+		match value {
+			ScriptingValue::Int64(i) => Ok(Self::Int(*i)),
+			ScriptingValue::Float64(f) => Ok(Self::Float(*f)),
+			ScriptingValue::Nil() => Err(ExecutionError::NilValue),
+			ScriptingValue::Boolean(_) => Err(ExecutionError::BoolNoArithmetic),
+			ScriptingValue::String(_) => Err(ExecutionError::NoNumber {
+				value: value.to_string().into(),
+			}),
+		}
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	const fn as_f64(self) -> f64 {
+		match self {
+			Self::Int(i) => i as f64,
+			Self::Float(f) => f,
+		}
+	}
+}
+
+impl From<Number> for ScriptingValue {
+	fn from(value: Number) -> Self {
+		match value {
+			Number::Int(i) => Self::Int64(i),
+			Number::Float(f) => Self::Float64(f),
+		}
+	}
+}
+
+/// Get the float value of an argument, integers are promoted.
+fn float(value: &ScriptingValue) -> ExecutionResult<f64> {
+	Number::of(value).map(Number::as_f64)
+}
+
+fn invalid(function: &str, value: &ScriptingValue) -> ExecutionError {
+	ExecutionError::InvalidArgument {
+		function: function.into(),
+		value: value.to_string().into(),
+	}
+}
+
+/// Applies `int` if both numbers are integers, `float` otherwise.
+fn combine(a: Number, b: Number, int: impl Fn(i64, i64) -> i64, float: impl Fn(f64, f64) -> f64) -> Number {
+	match (a, b) {
+		(Number::Int(a), Number::Int(b)) => Number::Int(int(a, b)),
+		(a, b) => Number::Float(float(a.as_f64(), b.as_f64())),
+	}
+}
+
+/// `abs(x)`
+fn abs(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	match Number::of(&args[0])? {
+		Number::Int(i) => i
+			.checked_abs()
+			.map(ScriptingValue::Int64)
+			.ok_or_else(|| invalid("abs", &args[0])),
+		Number::Float(f) => Ok(ScriptingValue::Float64(libm::fabs(f))),
+	}
+}
+
+/// `atan2(y, x)`
+fn atan2(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Float64(libm::atan2(float(&args[0])?, float(&args[1])?)))
+}
+
+/// `ceil(x)`, integers are returned unchanged.
+fn ceil(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	match Number::of(&args[0])? {
+		Number::Int(i) => Ok(ScriptingValue::Int64(i)),
+		Number::Float(f) => Ok(ScriptingValue::Float64(libm::ceil(f))),
+	}
+}
+
+/// `clamp(x, min, max)`
+fn clamp(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let value = Number::of(&args[0])?;
+	let lower = Number::of(&args[1])?;
+	let upper = Number::of(&args[2])?;
+	if lower.as_f64() > upper.as_f64() {
+		return Err(invalid("clamp", &args[1]));
+	}
+	let res = combine(value, lower, i64::max, f64::max);
+	Ok(combine(res, upper, i64::min, f64::min).into())
+}
+
+/// `cos(x)`
+fn cos(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Float64(libm::cos(float(&args[0])?)))
+}
+
+/// `floor(x)`, integers are returned unchanged.
+fn floor(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	match Number::of(&args[0])? {
+		Number::Int(i) => Ok(ScriptingValue::Int64(i)),
+		Number::Float(f) => Ok(ScriptingValue::Float64(libm::floor(f))),
+	}
+}
+
+/// `max(a, b, ...)`
+fn max(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let mut res = Number::of(&args[0])?;
+	for arg in &args[1..] {
+		res = combine(res, Number::of(arg)?, i64::max, f64::max);
+	}
+	Ok(res.into())
+}
+
+/// `min(a, b, ...)`
+fn min(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let mut res = Number::of(&args[0])?;
+	for arg in &args[1..] {
+		res = combine(res, Number::of(arg)?, i64::min, f64::min);
+	}
+	Ok(res.into())
+}
+
+/// `pow(base, exponent)`, an integer base with a non negative integer exponent results in an integer.
+fn pow(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let base = Number::of(&args[0])?;
+	let exponent = Number::of(&args[1])?;
+	let res = match (base, exponent) {
+		(Number::Int(base), Number::Int(exponent)) if exponent >= 0 => u32::try_from(exponent)
+			.ok()
+			.and_then(|exponent| base.checked_pow(exponent))
+			.map(Number::Int)
+			.ok_or_else(|| invalid("pow", &args[1]))?,
+		(base, exponent) => Number::Float(libm::pow(base.as_f64(), exponent.as_f64())),
+	};
+	Ok(res.into())
+}
+
+/// `round(x)` rounds half away from zero, integers are returned unchanged.
+fn round(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	match Number::of(&args[0])? {
+		Number::Int(i) => Ok(ScriptingValue::Int64(i)),
+		Number::Float(f) => Ok(ScriptingValue::Float64(libm::round(f))),
+	}
+}
+
+/// `sin(x)`
+fn sin(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Float64(libm::sin(float(&args[0])?)))
+}
+
+/// `sqrt(x)`
+fn sqrt(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Float64(libm::sqrt(float(&args[0])?)))
+}
+
+/// `tan(x)`
+fn tan(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Float64(libm::tan(float(&args[0])?)))
+}
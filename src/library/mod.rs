@@ -0,0 +1,235 @@
+// Copyright © 2026 Stephan Kunz
+//! Native functions and constants callable from scripts and the standard library modules.
+//!
+//! A [`Library`] is consulted at compile time, the selected [`NativeFunction`]s and constants
+//! are stored within the [`Chunk`](crate::Chunk), so the virtual machine does not need any lookup.
+
+mod conversion;
+#[cfg(feature = "math")]
+mod math;
+#[cfg(feature = "string")]
+mod string;
+
+// region:		--- modules
+use alloc::{boxed::Box, collections::btree_map::BTreeMap, vec::Vec};
+use core::{future::Future, ops::RangeInclusive, pin::Pin};
+
+use crate::{
+	ConstString,
+	error::Error,
+	execution::{ExecutionError, ExecutionResult},
+	scripting_value::ScriptingValue,
+};
+
+pub(crate) use conversion::conversion_function;
+// endregion:	--- modules
+
+// region:		--- types
+/// Signature of a native function.
+/// The number of arguments is already checked against the registered arity when called.
+pub type NativeFn = fn(&[ScriptingValue]) -> ExecutionResult<ScriptingValue>;
+
+/// The [`Future`] returned by an asynchronous native function.
+pub type NativeFuture = Pin<Box<dyn Future<Output = ExecutionResult<ScriptingValue>> + Send>>;
+
+/// Signature of an asynchronous native function.
+/// It gets the arguments by value, as they are removed from the stack while the function is awaited.
+pub type AsyncNativeFn = fn(Vec<ScriptingValue>) -> NativeFuture;
+
+/// Computes the length in bytes of the string a native function would create, without creating it,
+/// so the [`Limits`](crate::execution::Limits) can be checked in advance.
+/// Returns `None` if the function does not create a string or fails anyway.
+pub(crate) type LengthFn = fn(&[ScriptingValue]) -> Option<usize>;
+// endregion:	--- types
+
+// region:		--- NativeFunction
+/// The callable of a [`NativeFunction`].
+#[derive(Clone, Copy, Debug)]
+enum Callable {
+	Sync(NativeFn),
+	Async(AsyncNativeFn),
+}
+
+/// A native function together with its name and its arity.
+#[derive(Clone, Debug)]
+pub struct NativeFunction {
+	name: ConstString,
+	min_args: u8,
+	max_args: u8,
+	function: Callable,
+	length: Option<LengthFn>,
+}
+
+impl NativeFunction {
+	/// Name of the function.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Check whether the function accepts `count` arguments.
+	#[must_use]
+	pub const fn accepts(&self, count: usize) -> bool {
+		count >= self.min_args as usize && count <= self.max_args as usize
+	}
+
+	/// The accepted number of arguments.
+	#[must_use]
+	pub const fn arity(&self) -> RangeInclusive<u8> {
+		self.min_args..=self.max_args
+	}
+
+	/// Check whether the function is asynchronous.
+	#[must_use]
+	pub const fn is_async(&self) -> bool {
+		matches!(self.function, Callable::Async(_))
+	}
+
+	/// The length of the string the function would create for the arguments, if it is known in advance.
+	pub(crate) fn result_length(&self, args: &[ScriptingValue]) -> Option<usize> {
+		self.length.and_then(|length| length(args))
+	}
+
+	/// Call the function.
+	/// # Errors
+	/// - [`ExecutionError::AsyncFunction`] if the function is asynchronous
+	/// - passes through the errors of the function
+	pub fn call(&self, args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+		match self.function {
+			Callable::Sync(function) => function(args),
+			Callable::Async(_) => Err(ExecutionError::AsyncFunction { name: self.name.clone() }),
+		}
+	}
+
+	/// Call the function and await its result, which works for synchronous functions too.
+	/// # Errors
+	/// - passes through the errors of the function
+	pub async fn call_async(&self, args: Vec<ScriptingValue>) -> ExecutionResult<ScriptingValue> {
+		match self.function {
+			Callable::Sync(function) => function(&args),
+			Callable::Async(function) => function(args).await,
+		}
+	}
+}
+// endregion:	--- NativeFunction
+
+// region:		--- Library
+/// A registry of [`NativeFunction`]s and named constants.
+///
+/// The default library contains all modules enabled by cargo features.
+#[derive(Clone, Debug)]
+pub struct Library {
+	functions: BTreeMap<ConstString, NativeFunction>,
+	constants: BTreeMap<ConstString, ScriptingValue>,
+}
+
+impl Default for Library {
+	fn default() -> Self {
+		let mut library = Self::empty();
+		conversion::register(&mut library);
+		#[cfg(feature = "math")]
+		math::register(&mut library);
+		#[cfg(feature = "string")]
+		string::register(&mut library);
+		library
+	}
+}
+
+impl Library {
+	/// Create a [`Library`] without any function.
+	#[must_use]
+	pub const fn empty() -> Self {
+		Self {
+			functions: BTreeMap::new(),
+			constants: BTreeMap::new(),
+		}
+	}
+
+	/// Register a native function.
+	/// # Errors
+	/// - [`Error::DuplicateFunction`] if a function with that name already exists.
+	pub fn register_function(&mut self, name: &str, arity: RangeInclusive<u8>, function: NativeFn) -> Result<(), Error> {
+		if self.functions.contains_key(name) {
+			return Err(Error::DuplicateFunction { name: name.into() });
+		}
+		self.insert(name, arity, function);
+		Ok(())
+	}
+
+	/// Register an asynchronous native function, which can only be called by an asynchronous execution.
+	/// # Errors
+	/// - [`Error::DuplicateFunction`] if a function with that name already exists.
+	pub fn register_async_function(
+		&mut self,
+		name: &str,
+		arity: RangeInclusive<u8>,
+		function: AsyncNativeFn,
+	) -> Result<(), Error> {
+		if self.functions.contains_key(name) {
+			return Err(Error::DuplicateFunction { name: name.into() });
+		}
+		self.insert_callable(name, arity, Callable::Async(function), None);
+		Ok(())
+	}
+
+	/// Insert a function, replacing an already existing one.
+	fn insert(&mut self, name: &str, arity: RangeInclusive<u8>, function: NativeFn) {
+		self.insert_callable(name, arity, Callable::Sync(function), None);
+	}
+
+	/// Insert a function creating strings, whose length is computed in advance by `length`.
+	#[cfg_attr(not(feature = "string"), allow(dead_code))]
+	fn insert_with_length(&mut self, name: &str, arity: RangeInclusive<u8>, function: NativeFn, length: LengthFn) {
+		self.insert_callable(name, arity, Callable::Sync(function), Some(length));
+	}
+
+	fn insert_callable(&mut self, name: &str, arity: RangeInclusive<u8>, function: Callable, length: Option<LengthFn>) {
+		let native = NativeFunction {
+			name: name.into(),
+			min_args: *arity.start(),
+			max_args: *arity.end(),
+			function,
+			length,
+		};
+		self.functions.insert(name.into(), native);
+	}
+
+	/// Register a named constant.
+	/// # Errors
+	/// - [`Error::DuplicateConstant`] if a constant with that name already exists.
+	pub fn register_constant(&mut self, name: &str, value: impl Into<ScriptingValue>) -> Result<(), Error> {
+		if self.constants.contains_key(name) {
+			return Err(Error::DuplicateConstant { name: name.into() });
+		}
+		self.insert_constant(name, value.into());
+		Ok(())
+	}
+
+	/// Insert a constant, replacing an already existing one.
+	fn insert_constant(&mut self, name: &str, value: ScriptingValue) {
+		self.constants.insert(name.into(), value);
+	}
+
+	/// Get the value of a registered constant.
+	#[must_use]
+	pub fn constant(&self, name: &str) -> Option<&ScriptingValue> {
+		self.constants.get(name)
+	}
+
+	/// Iterate over the names of all registered constants.
+	pub fn constant_names(&self) -> impl Iterator<Item = &str> {
+		self.constants.keys().map(AsRef::as_ref)
+	}
+
+	/// Get a registered function.
+	#[must_use]
+	pub fn function(&self, name: &str) -> Option<&NativeFunction> {
+		self.functions.get(name)
+	}
+
+	/// Iterate over the names of all registered functions.
+	pub fn function_names(&self) -> impl Iterator<Item = &str> {
+		self.functions.keys().map(AsRef::as_ref)
+	}
+}
+// endregion:	--- Library
@@ -0,0 +1,192 @@
+// Copyright © 2026 Stephan Kunz
+//! String functions of the standard library.
+//!
+//! Positions and lengths are counted in characters, not in bytes.
+//! As there is no list type, `split` returns only the n-th part of the split string.
+
+// region:		--- modules
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+use crate::{
+	execution::{ExecutionError, ExecutionResult},
+	scripting_value::{LengthCounter, ScriptingValue},
+};
+
+use super::Library;
+// endregion:	--- modules
+
+/// Registers the string functions.
+pub(super) fn register(library: &mut Library) {
+	library.insert("contains", 2..=2, contains);
+	library.insert("ends_with", 2..=2, ends_with);
+	library.insert_with_length("format", 1..=u8::MAX, format, format_length);
+	library.insert("len", 1..=1, len);
+	library.insert_with_length("replace", 3..=3, replace, replace_length);
+	library.insert("split", 3..=3, split);
+	library.insert("starts_with", 2..=2, starts_with);
+	library.insert("substr", 2..=3, substr);
+	library.insert_with_length("to_lower", 1..=1, to_lower, to_lower_length);
+	library.insert_with_length("to_upper", 1..=1, to_upper, to_upper_length);
+	library.insert("trim", 1..=1, trim);
+}
+
+/// Converts an integer argument into a position or length.
+fn index(function: &str, value: &ScriptingValue) -> ExecutionResult<usize> {
+	usize::try_from(value.as_int()?).map_err(|_| ExecutionError::InvalidArgument {
+		function: function.into(),
+		value: value.to_string().into(),
+	})
+}
+
+/// `contains(string, pattern)`
+fn contains(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Boolean(args[0].as_str()?.contains(args[1].as_str()?)))
+}
+
+/// `ends_with(string, suffix)`
+fn ends_with(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Boolean(args[0].as_str()?.ends_with(args[1].as_str()?)))
+}
+
+/// `format(template, values...)` replaces each `{}` in the template with the next value.
+/// Literal braces are written as `{{` and `}}`.
+fn format(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let mut res = String::with_capacity(args[0].as_str()?.len());
+	format_into(args, &mut res)?;
+	Ok(ScriptingValue::String(res.into()))
+}
+
+/// Length of the result of `format`.
+fn format_length(args: &[ScriptingValue]) -> Option<usize> {
+	let mut counter = LengthCounter(0);
+	format_into(args, &mut counter).ok()?;
+	Some(counter.0)
+}
+
+/// Writes the formatted template, so that its length can be computed without creating it.
+fn format_into(args: &[ScriptingValue], res: &mut impl Write) -> ExecutionResult<()> {
+	let template = args[0].as_str()?;
+	let invalid = || ExecutionError::InvalidArgument {
+		function: "format".into(),
+		value: template.into(),
+	};
+	let mut values = args[1..].iter();
+	let mut chars = template.chars().peekable();
+	while let Some(c) = chars.next() {
+		let _ = match c {
+			'{' if chars.next_if_eq(&'{').is_some() => res.write_char('{'),
+			'}' if chars.next_if_eq(&'}').is_some() => res.write_char('}'),
+			'{' if chars.next_if_eq(&'}').is_some() => {
+				let value = values.next().ok_or_else(invalid)?;
+				write!(res, "{value}")
+			}
+			'{' | '}' => return Err(invalid()),
+			c => res.write_char(c),
+		};
+	}
+	if values.next().is_some() {
+		return Err(invalid());
+	}
+	Ok(())
+}
+
+/// `len(string)`
+fn len(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let count = args[0].as_str()?.chars().count();
+	i64::try_from(count)
+		.map(ScriptingValue::Int64)
+		.map_err(|_| ExecutionError::InvalidArgument {
+			function: "len".into(),
+			value: args[0].to_string().into(),
+		})
+}
+
+/// `replace(string, from, to)` replaces all occurences of `from`.
+fn replace(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let res = args[0]
+		.as_str()?
+		.replace(args[1].as_str()?, args[2].as_str()?);
+	Ok(ScriptingValue::String(res.into()))
+}
+
+/// Length of the result of `replace`.
+fn replace_length(args: &[ScriptingValue]) -> Option<usize> {
+	let string = args[0].as_str().ok()?;
+	let from = args[1].as_str().ok()?;
+	let to = args[2].as_str().ok()?;
+	let count = string.matches(from).count();
+	// an overflow can never fit into the limits
+	Some((string.len() - count * from.len()).saturating_add(count.saturating_mul(to.len())))
+}
+
+/// `split(string, separator, n)` returns the n-th part or `nil` if there are less parts.
+fn split(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let n = index("split", &args[2])?;
+	let res = args[0]
+		.as_str()?
+		.split(args[1].as_str()?)
+		.nth(n)
+		.map_or_else(ScriptingValue::nil, |part| ScriptingValue::String(part.into()));
+	Ok(res)
+}
+
+/// `starts_with(string, prefix)`
+fn starts_with(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Boolean(args[0].as_str()?.starts_with(args[1].as_str()?)))
+}
+
+/// `substr(string, start [, length])`, the length defaults to the rest of the string.
+fn substr(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let start = index("substr", &args[1])?;
+	let length = match args.get(2) {
+		Some(value) => index("substr", value)?,
+		None => usize::MAX,
+	};
+	let res = args[0]
+		.as_str()?
+		.chars()
+		.skip(start)
+		.take(length)
+		.collect::<String>();
+	Ok(ScriptingValue::String(res.into()))
+}
+
+/// `to_lower(string)`
+fn to_lower(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::String(args[0].as_str()?.to_lowercase().into()))
+}
+
+/// Length of the result of `to_lower`, which may differ from the original length for some characters.
+fn to_lower_length(args: &[ScriptingValue]) -> Option<usize> {
+	let length = args[0]
+		.as_str()
+		.ok()?
+		.chars()
+		.flat_map(char::to_lowercase)
+		.map(char::len_utf8)
+		.sum();
+	Some(length)
+}
+
+/// `to_upper(string)`
+fn to_upper(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::String(args[0].as_str()?.to_uppercase().into()))
+}
+
+/// Length of the result of `to_upper`, which may differ from the original length for some characters.
+fn to_upper_length(args: &[ScriptingValue]) -> Option<usize> {
+	let length = args[0]
+		.as_str()
+		.ok()?
+		.chars()
+		.flat_map(char::to_uppercase)
+		.map(char::len_utf8)
+		.sum();
+	Some(length)
+}
+
+/// `trim(string)` removes leading and trailing whitespace.
+fn trim(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::String(args[0].as_str()?.trim().into()))
+}
@@ -5,4 +5,4 @@
 //!
 //! ```use tinyscript::prelude::*;```
 
-pub use crate::{ScriptEnum, SharedRuntime};
+pub use crate::{ScriptEnum, SharedEngine, SharedRuntime};
@@ -6,79 +6,211 @@
 extern crate std;
 
 // region:      --- modules
-use alloc::{collections::btree_map::BTreeMap, string::String, sync::Arc};
+use alloc::{sync::Arc, vec::Vec};
 use spin::Mutex;
 
 use crate::{
-	compilation::Parser,
-	environment::Environment,
+	compilation::{Schema, Stmt, TypeError, Warning},
+	engine::Engine,
+	environment::{AsyncEnvironment, Environment},
 	error::Error,
-	execution::{Chunk, VM},
+	execution::{Chunk, Debugger, Execution, Limits, Linkage, Suspended, VM},
+	library::{AsyncNativeFn, NativeFn},
 	scripting_value::ScriptingValue,
 };
+use core::ops::RangeInclusive;
 
-#[cfg(feature = "std")]
-use std::vec::Vec;
+#[cfg(feature = "profiling")]
+use crate::execution::{Profile, TraceEntry};
 // endregion:   --- modules
 
 // region:      --- types
 /// Defines a shared [`Runtime`].
+/// To run scripts concurrently use a [`SharedEngine`](crate::engine::SharedEngine)
+/// with a [`Runtime`] per thread instead.
 pub type SharedRuntime = Arc<Mutex<Runtime>>;
 // endregion:   --- types
 
 // region:      --- Runtime
 /// Runtime to execute tinyscript.
+///
+/// The compiled state is kept in an [`Engine`], which may be shared by several runtimes,
+/// while the [`VM`] and stdout belong to the runtime.
 #[derive(Debug, Default)]
 pub struct Runtime {
-	parser: Parser,
+	engine: Arc<Engine>,
 	vm: VM,
-	enums: BTreeMap<String, i8>,
 	#[cfg(feature = "std")]
 	stdout: Vec<u8>,
 }
 
-/// Cloning a Runtime is cloning the environment.
-/// Parser, VM and stdout are created new.
+/// Cloning a Runtime is cloning its [`Engine`], so the clone does not share the compiled state,
+/// and its [`Limits`]. VM and stdout are created new.
 impl Clone for Runtime {
 	fn clone(&self) -> Self {
+		let mut runtime = Self::with_engine(Arc::new(self.engine.as_ref().clone()));
+		runtime.set_limits(self.limits());
+		runtime
+	}
+}
+
+impl Runtime {
+	/// Create a runtime using a shared [`Engine`].
+	#[must_use]
+	pub fn with_engine(engine: Arc<Engine>) -> Self {
 		Self {
-			parser: Parser::default(),
+			engine,
 			vm: VM::default(),
-			enums: self.enums.clone(),
 			#[cfg(feature = "std")]
 			stdout: Vec::new(),
 		}
 	}
-}
 
-impl Runtime {
+	/// Access the [`Engine`] of the runtime.
+	#[must_use]
+	pub const fn engine(&self) -> &Arc<Engine> {
+		&self.engine
+	}
+
+	/// Set the [`Limits`] enforced for each execution, by default there are no limits.
+	pub const fn set_limits(&mut self, limits: Limits) {
+		self.vm.set_limits(limits);
+	}
+
+	/// The [`Limits`] enforced for each execution.
+	#[must_use]
+	pub const fn limits(&self) -> Limits {
+		self.vm.limits()
+	}
+
+	/// Source line of the instruction of `chunk` started last by the latest execution,
+	/// after an execution error the line of the failing instruction, see [`VM::last_instruction`].
+	#[must_use]
+	pub fn last_line(&self, chunk: &Chunk) -> Option<usize> {
+		self.vm
+			.last_instruction()
+			.and_then(|index| chunk.instruction_lines().get(index).copied())
+	}
+
+	/// Enable or disable the tracing of executed instructions, see [`VM::set_tracing`].
+	#[cfg(feature = "profiling")]
+	pub const fn set_tracing(&mut self, enabled: bool) {
+		self.vm.set_tracing(enabled);
+	}
+
+	/// The executed instructions of the last execution, if tracing is enabled.
+	#[cfg(feature = "profiling")]
+	#[must_use]
+	pub fn trace(&self) -> &[TraceEntry] {
+		self.vm.trace()
+	}
+
+	/// Enable or disable the profiling of executed instructions, see [`VM::set_profiling`].
+	#[cfg(feature = "profiling")]
+	pub const fn set_profiling(&mut self, enabled: bool) {
+		self.vm.set_profiling(enabled);
+	}
+
+	/// The [`Profile`] accumulated over all profiled executions.
+	#[cfg(feature = "profiling")]
+	#[must_use]
+	pub const fn profile(&self) -> &Profile {
+		self.vm.profile()
+	}
+
+	/// Remove all statistics from the [`Profile`].
+	#[cfg(feature = "profiling")]
+	pub fn clear_profile(&mut self) {
+		self.vm.clear_profile();
+	}
+
 	/// Inserts an enum value.
 	/// # Errors
 	/// - [`Error::DuplicateEnumVariant`] if en enum definition (key) already exists.
 	pub fn register_enum_tuple(&mut self, key: &str, value: i8) -> Result<(), Error> {
-		if let Some(old_value) = self.enums.get(key) {
-			return Err(Error::DuplicateVariant {
-				name: key.into(),
-				old: *old_value,
-				new: value,
-			});
-		}
-		self.enums.insert(key.into(), value);
-		Ok(())
+		self.engine.register_enum_tuple(key, value)
+	}
+
+	/// Registers a native function callable from scripts.
+	/// # Errors
+	/// - [`Error::DuplicateFunction`] if a function with that name already exists.
+	pub fn register_function(&mut self, name: &str, arity: RangeInclusive<u8>, function: NativeFn) -> Result<(), Error> {
+		self.engine
+			.register_function(name, arity, function)
+	}
+
+	/// Registers an asynchronous native function callable from scripts executed asynchronously.
+	/// # Errors
+	/// - [`Error::DuplicateFunction`] if a function with that name already exists.
+	pub fn register_async_function(
+		&mut self,
+		name: &str,
+		arity: RangeInclusive<u8>,
+		function: AsyncNativeFn,
+	) -> Result<(), Error> {
+		self.engine
+			.register_async_function(name, arity, function)
+	}
+
+	/// Registers a named constant usable in scripts.
+	/// # Errors
+	/// - [`Error::DuplicateConstant`] if a constant with that name already exists.
+	pub fn register_constant(&mut self, name: &str, value: impl Into<ScriptingValue>) -> Result<(), Error> {
+		self.engine.register_constant(name, value)
+	}
+
+	/// Enable or disable the optimization of compiled scripts, it is enabled by default.
+	pub fn set_optimization(&mut self, enabled: bool) {
+		self.engine.set_optimization(enabled);
+	}
+
+	/// Set the [`Schema`] to check the types of compiled scripts against,
+	/// see [`TypeChecker`](crate::compilation::TypeChecker).
+	/// Without a schema, which is the default, the types are not checked.
+	pub fn set_schema(&mut self, schema: Option<Schema>) {
+		self.engine.set_schema(schema);
 	}
 
 	/// Get the discriminant of an enum value if it exists
 	#[must_use]
 	pub fn enum_discriminant(&self, name: &str) -> Option<i8> {
-		self.enums.get(name).copied()
+		self.engine.enum_discriminant(name)
 	}
 
 	/// Parse a scripting source.
 	/// # Errors
 	/// - [`Error::Compilation`] if script is invalid
 	pub fn parse(&mut self, script: &str) -> Result<Chunk, Error> {
-		let chunk = self.parser.parse(&self.enums, script)?;
-		Ok(chunk)
+		self.engine.parse(script)
+	}
+
+	/// Parse a scripting source into its abstract syntax tree.
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid
+	pub fn parse_ast(&self, script: &str) -> Result<Vec<Stmt>, Error> {
+		self.engine.parse_ast(script)
+	}
+
+	/// Assemble bytecode in the textual format of the [`Disassembler`](crate::execution::Disassembler).
+	/// # Errors
+	/// - [`Error::Compilation`] if the text is invalid
+	pub fn assemble(&self, text: &str) -> Result<Chunk, Error> {
+		self.engine.assemble(text)
+	}
+
+	/// Lint a script with the registered enum values, see [`Linter`](crate::compilation::Linter).
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid
+	pub fn lint(&self, script: &str) -> Result<Vec<Warning>, Error> {
+		self.engine.lint(script)
+	}
+
+	/// Check the types of a script against the set [`Schema`] or an empty one,
+	/// see [`TypeChecker`](crate::compilation::TypeChecker).
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid
+	pub fn check(&self, script: &str) -> Result<Vec<TypeError>, Error> {
+		self.engine.check(script)
 	}
 
 	/// Execute a bytecode chunk.
@@ -92,19 +224,148 @@ impl Runtime {
 		Ok(res)
 	}
 
-	/// Compiles and runs the new script without clearing stdout.
+	/// Execute a bytecode chunk linked to the environment with [`Chunk::link`].
+	/// # Errors
+	/// - [`Error::Execution`] if script cannot be executed.
+	pub fn execute_linked(
+		&mut self,
+		chunk: &Chunk,
+		linkage: &Linkage,
+		globals: &mut impl Environment,
+	) -> Result<ScriptingValue, Error> {
+		#[cfg(not(feature = "std"))]
+		let res = self.vm.run_linked(chunk, linkage, globals)?;
+		#[cfg(feature = "std")]
+		let res = self
+			.vm
+			.run_linked(chunk, linkage, globals, &mut self.stdout)?;
+		Ok(res)
+	}
+
+	/// Execute a bytecode chunk asynchronously.
+	/// # Errors
+	/// - [`Error::Execution`] if script cannot be executed.
+	pub async fn execute_async(
+		&mut self,
+		chunk: &Chunk,
+		globals: &mut impl AsyncEnvironment,
+	) -> Result<ScriptingValue, Error> {
+		#[cfg(not(feature = "std"))]
+		let res = self.vm.run_async(chunk, globals).await?;
+		#[cfg(feature = "std")]
+		let res = self
+			.vm
+			.run_async(chunk, globals, &mut self.stdout)
+			.await?;
+		Ok(res)
+	}
+
+	/// Run a script asynchronously, awaiting the environment and asynchronous native functions.
+	/// Clears stdout before execution, the compiled script is cached.
 	/// # Errors
 	/// - [`Error::Compilation`] if script is invalid.
 	/// - [`Error::Execution`] if script cannot be executed.
-	pub fn continue_run(&mut self, script: &str, globals: &mut impl Environment) -> Result<ScriptingValue, Error> {
-		let chunk = self.parser.parse(&self.enums, script)?;
+	pub async fn run_async(&mut self, script: &str, globals: &mut impl AsyncEnvironment) -> Result<ScriptingValue, Error> {
+		#[cfg(feature = "std")]
+		self.stdout.clear();
+		let chunk = self.compile(script)?;
+		self.execute_async(&chunk, globals).await
+	}
+
+	/// Start a resumable execution of a bytecode chunk, which is suspended at each `yield`.
+	/// # Errors
+	/// - [`Error::Execution`] if script cannot be executed.
+	pub fn start(&mut self, chunk: &Chunk, globals: &mut impl Environment) -> Result<Execution, Error> {
+		#[cfg(not(feature = "std"))]
+		let res = self
+			.vm
+			.start(chunk, &Linkage::default(), globals)?;
+		#[cfg(feature = "std")]
+		let res = self
+			.vm
+			.start(chunk, &Linkage::default(), globals, &mut self.stdout)?;
+		Ok(res)
+	}
+
+	/// Resume a suspended execution of the bytecode chunk it was started with.
+	/// # Errors
+	/// - [`Error::Execution`] if script cannot be executed or the chunk does not fit.
+	pub fn resume(
+		&mut self,
+		chunk: &Chunk,
+		suspended: Suspended,
+		globals: &mut impl Environment,
+	) -> Result<Execution, Error> {
+		#[cfg(not(feature = "std"))]
+		let res = self
+			.vm
+			.resume(chunk, &Linkage::default(), suspended, globals)?;
+		#[cfg(feature = "std")]
+		let res = self
+			.vm
+			.resume(chunk, &Linkage::default(), suspended, globals, &mut self.stdout)?;
+		Ok(res)
+	}
+
+	/// Execute a single instruction of a bytecode chunk under control of a [`Debugger`].
+	/// Returns the result value when the script has finished, `None` while it is paused.
+	/// # Errors
+	/// - [`Error::Execution`] if script cannot be executed.
+	pub fn debug_step(
+		&mut self,
+		chunk: &Chunk,
+		debugger: &mut Debugger,
+		globals: &mut impl Environment,
+	) -> Result<Option<ScriptingValue>, Error> {
+		#[cfg(not(feature = "std"))]
+		let res = self
+			.vm
+			.debug_step(chunk, &Linkage::default(), debugger, globals)?;
+		#[cfg(feature = "std")]
+		let res = self
+			.vm
+			.debug_step(chunk, &Linkage::default(), debugger, globals, &mut self.stdout)?;
+		Ok(res)
+	}
+
+	/// Continue the execution of a bytecode chunk under control of a [`Debugger`] until the next breakpoint.
+	/// Returns the result value when the script has finished, `None` while it is paused.
+	/// # Errors
+	/// - [`Error::Execution`] if script cannot be executed.
+	pub fn debug_continue(
+		&mut self,
+		chunk: &Chunk,
+		debugger: &mut Debugger,
+		globals: &mut impl Environment,
+	) -> Result<Option<ScriptingValue>, Error> {
 		#[cfg(not(feature = "std"))]
-		let res = self.vm.run(&chunk, globals)?;
+		let res = self
+			.vm
+			.debug_continue(chunk, &Linkage::default(), debugger, globals)?;
 		#[cfg(feature = "std")]
-		let res = self.vm.run(&chunk, globals, &mut self.stdout)?;
+		let res = self
+			.vm
+			.debug_continue(chunk, &Linkage::default(), debugger, globals, &mut self.stdout)?;
 		Ok(res)
 	}
 
+	/// Compile a script, reusing the cached [`Chunk`] if the same source has been compiled before.
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid
+	pub fn compile(&mut self, script: &str) -> Result<Arc<Chunk>, Error> {
+		self.engine.compile(script)
+	}
+
+	/// Compiles and runs the new script without clearing stdout.
+	/// The compiled script is cached.
+	/// # Errors
+	/// - [`Error::Compilation`] if script is invalid.
+	/// - [`Error::Execution`] if script cannot be executed.
+	pub fn continue_run(&mut self, script: &str, globals: &mut impl Environment) -> Result<ScriptingValue, Error> {
+		let chunk = self.compile(script)?;
+		self.execute(&chunk, globals)
+	}
+
 	/// Run a script.
 	/// Clears stdout before execution.
 	/// # Errors
@@ -115,6 +376,58 @@ impl Runtime {
 		self.continue_run(script, globals)
 	}
 
+	/// Precompile a script and keep it under `name` until it is removed.
+	/// Precompiled scripts are not affected by the cache
+	/// and keep the enums and functions known at the time of compilation.
+	/// # Errors
+	/// - [`Error::DuplicateScript`] if a script with that name already exists.
+	/// - [`Error::Compilation`] if script is invalid.
+	pub fn precompile(&mut self, name: &str, script: &str) -> Result<Arc<Chunk>, Error> {
+		self.engine.precompile(name, script)
+	}
+
+	/// Get a precompiled script.
+	#[must_use]
+	pub fn script(&self, name: &str) -> Option<Arc<Chunk>> {
+		self.engine.script(name)
+	}
+
+	/// Remove a precompiled script, returning it if it existed.
+	pub fn remove_script(&mut self, name: &str) -> Option<Arc<Chunk>> {
+		self.engine.remove_script(name)
+	}
+
+	/// Run a precompiled script.
+	/// Clears stdout before execution.
+	/// # Errors
+	/// - [`Error::ScriptNotFound`] if there is no script with that name.
+	/// - [`Error::Execution`] if script cannot be executed.
+	pub fn run_script(&mut self, name: &str, globals: &mut impl Environment) -> Result<ScriptingValue, Error> {
+		let chunk = self
+			.engine
+			.script(name)
+			.ok_or_else(|| Error::ScriptNotFound { name: name.into() })?;
+		#[cfg(feature = "std")]
+		self.stdout.clear();
+		self.execute(&chunk, globals)
+	}
+
+	/// Number of compiled scripts in the cache.
+	#[must_use]
+	pub fn cache_len(&self) -> usize {
+		self.engine.cache_len()
+	}
+
+	/// Change the number of cached compiled scripts, `0` disables the cache.
+	pub fn set_cache_capacity(&mut self, capacity: usize) {
+		self.engine.set_cache_capacity(capacity);
+	}
+
+	/// Remove all compiled scripts from the cache.
+	pub fn clear_cache(&mut self) {
+		self.engine.clear_cache();
+	}
+
 	/// Returns the stdout.
 	#[cfg(feature = "std")]
 	#[must_use]
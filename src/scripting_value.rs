@@ -6,11 +6,24 @@ extern crate alloc;
 
 use alloc::string::{String, ToString};
 use core::{
-	fmt::{Debug, Display, Formatter},
+	fmt::{Debug, Display, Formatter, Write},
 	str::FromStr,
 };
 
-use crate::execution::{ExecutionError, ExecutionResult};
+use crate::{
+	ConstString,
+	execution::{ExecutionError, ExecutionResult},
+};
+
+/// A [`Write`] target counting the bytes written instead of storing them.
+pub(crate) struct LengthCounter(pub usize);
+
+impl Write for LengthCounter {
+	fn write_str(&mut self, s: &str) -> core::fmt::Result {
+		self.0 += s.len();
+		Ok(())
+	}
+}
 
 /// Value type that allows storing different kinds of values.
 #[derive(Clone, Debug)]
@@ -23,8 +36,8 @@ pub enum ScriptingValue {
 	Float64(f64),
 	/// Integer 64bit
 	Int64(i64),
-	/// String type
-	String(String),
+	/// String type, reference counted so that copies do not allocate
+	String(ConstString),
 }
 
 impl Display for ScriptingValue {
@@ -303,7 +316,7 @@ impl TryFrom<ScriptingValue> for String {
 
 	fn try_from(value: ScriptingValue) -> Result<Self, Self::Error> {
 		if let ScriptingValue::String(s) = value {
-			Ok(s)
+			Ok(s.to_string())
 		} else {
 			Err(crate::Error::TryConversion {
 				value: value.to_string().into(),
@@ -313,6 +326,18 @@ impl TryFrom<ScriptingValue> for String {
 	}
 }
 
+impl From<String> for ScriptingValue {
+	fn from(value: String) -> Self {
+		Self::String(value.into())
+	}
+}
+
+impl From<&str> for ScriptingValue {
+	fn from(value: &str) -> Self {
+		Self::String(value.into())
+	}
+}
+
 impl<'a> TryFrom<&'a ScriptingValue> for &'a str {
 	type Error = crate::Error;
 
@@ -348,6 +373,58 @@ impl ScriptingValue {
 		}
 	}
 
+	/// Return the integer value.
+	/// Internal use only.
+	/// # Errors
+	/// - if it is not an integer type
+	#[cfg_attr(not(feature = "string"), allow(dead_code))]
+	pub(crate) fn as_int(&self) -> ExecutionResult<i64> {
+		match self {
+			Self::Int64(i) => Ok(*i),
+			_ => Err(ExecutionError::NoInteger {
+				value: self.to_string().into(),
+			}),
+		}
+	}
+
+	/// Return the string value.
+	/// Internal use only.
+	/// # Errors
+	/// - if it is not a string type
+	pub(crate) fn as_str(&self) -> ExecutionResult<&str> {
+		match self {
+			Self::String(s) => Ok(s),
+			_ => Err(ExecutionError::NoString {
+				value: self.to_string().into(),
+			}),
+		}
+	}
+
+	/// Length in bytes of the values display form, computed without creating it.
+	/// Internal use only.
+	pub(crate) fn display_len(&self) -> usize {
+		match self {
+			Self::String(s) => s.len(),
+			value => {
+				let mut counter = LengthCounter(0);
+				let _ = write!(counter, "{value}");
+				counter.0
+			}
+		}
+	}
+
+	/// Name of the values type, as returned by the script function `typeof`.
+	#[must_use]
+	pub const fn type_name(&self) -> &'static str {
+		match self {
+			Self::Nil() => "nil",
+			Self::Boolean(_) => "bool",
+			Self::Float64(_) => "float",
+			Self::Int64(_) => "int",
+			Self::String(_) => "string",
+		}
+	}
+
 	/// Check if it is a boolean value.
 	#[must_use]
 	pub const fn is_bool(&self) -> bool {
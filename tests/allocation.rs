@@ -0,0 +1,65 @@
+//! Tests of allocations while running scripts
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+// counting allocations requires a global allocator
+#![allow(unsafe_code)]
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+use tinyscript::{Runtime, environment::DefaultEnvironment, execution::VM};
+
+/// Allocator counting the number of allocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		unsafe { System.alloc(layout) }
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		unsafe { System.dealloc(ptr, layout) };
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// A single test, as parallel tests would disturb the counting.
+#[test]
+fn allocations_per_run() {
+	let scripts = [
+		("'this is a ' == 'test string';", 0),
+		("'a' == 'b' ? 'yes' : 'no';", 0),
+		("!(5 - 4 > 3 * 2 == !nil);", 0),
+		// the resulting string and its conversion into a reference counted string
+		("'this is a ' + 'test string';", 2),
+		("`{'this is a '}{'test string'}`;", 2),
+		// a template fitting on the stack is joined at once
+		("`a{1}b{2.5}c{true}`;", 2),
+		// a longer one in two batches
+		("`{1}{2}{3}{4}{5}{6}{7}{8}{9}`;", 4),
+	];
+
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.set_optimization(false);
+	let mut vm = VM::default();
+	let mut stdout = std::io::sink();
+
+	for (script, expected) in scripts {
+		let chunk = runtime.parse(script).unwrap();
+		let before = ALLOCATIONS.load(Ordering::Relaxed);
+		let res = vm.run(&chunk, &mut env, &mut stdout);
+		let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+		res.unwrap();
+		assert_eq!(allocations, expected, "{script}");
+	}
+}
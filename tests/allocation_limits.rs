@@ -0,0 +1,83 @@
+//! Tests of the memory allocated by scripts exceeding the limits
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+// measuring allocations requires a global allocator
+#![allow(unsafe_code)]
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+use tinyscript::{
+	Environment, Error, Runtime,
+	environment::DefaultEnvironment,
+	execution::{ExecutionError, Limits},
+};
+
+/// Allocator keeping track of the peak of allocated bytes.
+struct PeakAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+		PEAK.fetch_max(allocated, Ordering::Relaxed);
+		unsafe { System.alloc(layout) }
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+		unsafe { System.dealloc(ptr, layout) };
+	}
+}
+
+#[global_allocator]
+static GLOBAL: PeakAllocator = PeakAllocator;
+
+// A single test, as parallel tests would disturb the measurement.
+#[test]
+fn peak_allocation_within_limits() {
+	let scripts = [
+		("replace(x, 'a', x);", true),
+		("format('{}{}{}', x, x, x);", true),
+		("x + x;", true),
+		("`{x}{x}`;", true),
+		("a := x + 'b'; b := a + 'c'; c := b + 'd';", false),
+	];
+
+	let mut runtime = Runtime::default();
+	runtime.set_limits(
+		Limits::default()
+			.with_max_string_length(4096)
+			.with_max_allocated_bytes(8192),
+	);
+	for (script, length_limit) in scripts {
+		let mut env = DefaultEnvironment::default();
+		env.define_env("x", "a".repeat(4000)).unwrap();
+		let chunk = runtime.parse(script).unwrap();
+
+		let before = ALLOCATED.load(Ordering::Relaxed);
+		PEAK.store(before, Ordering::Relaxed);
+		let res = runtime.execute(&chunk, &mut env);
+		let peak = PEAK.load(Ordering::Relaxed) - before;
+
+		let Err(Error::Execution { source }) = res else {
+			unreachable!()
+		};
+		if length_limit {
+			assert!(
+				matches!(source, ExecutionError::StringLengthLimit { limit: 4096, .. }),
+				"{script}"
+			);
+		} else {
+			assert!(matches!(source, ExecutionError::AllocationLimit { limit: 8192 }), "{script}");
+		}
+		// the strings exceeding the limits are never created
+		assert!(peak < 8192, "{script}: {peak} bytes");
+	}
+}
@@ -0,0 +1,142 @@
+//! Tests of the assembler
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use rstest::rstest;
+use tinyscript::{
+	Error, Runtime,
+	compilation::{Assembler, CompilationError},
+	environment::DefaultEnvironment,
+};
+
+#[rstest]
+#[case("a := 1; b := a + 2.5; print b * 10;", true)]
+#[case("a := 1; b := a + 2.5; print b * 10;", false)]
+#[case("s := 'say \"hi\"\n\\'; print len(s) > 3 ? `{s}!` : substr(s, 1);", true)]
+#[case("s := 'say \"hi\"\n\\'; print len(s) > 3 ? `{s}!` : substr(s, 1);", false)]
+#[case("a := true; b := a && !false || nil == nil; c := 0x0f & ~3 | 1 ^ 2; -c;", false)]
+#[case("x := 1; yield; x += 1; x;", true)]
+fn round_trip(#[case] script: &str, #[case] optimize: bool) {
+	let mut runtime = Runtime::default();
+	runtime.set_optimization(optimize);
+	let chunk = runtime.parse(script).unwrap();
+	let text = chunk.disassembler().to_string();
+
+	let assembled = runtime.assemble(&text).unwrap();
+	assert_eq!(assembled.code(), chunk.code());
+	assert_eq!(assembled.lines(), chunk.lines());
+	assert_eq!(assembled.instructions(), chunk.instructions());
+	assert_eq!(assembled.disassembler().to_string(), text);
+
+	let mut env = DefaultEnvironment::default();
+	let expected = runtime.execute(&chunk, &mut env).unwrap();
+	let stdout = runtime.stdout().clone();
+	runtime.clear();
+	let mut env = DefaultEnvironment::default();
+	let result = runtime.execute(&assembled, &mut env).unwrap();
+	assert_eq!(result.to_string(), expected.to_string());
+	assert_eq!(*runtime.stdout(), stdout);
+}
+
+const SUM: &str = "
+	// sum of 1..=n
+		OP_CONSTANT 0
+		OP_DEFINE_EXTERNAL \"sum\"
+		OP_CONSTANT 4
+		OP_DEFINE_EXTERNAL \"n\"
+loop:	OP_GET_EXTERNAL \"n\"
+		OP_CONSTANT 0
+		OP_GREATER
+		OP_JMP_IF_FALSE done
+		OP_POP
+		OP_GET_EXTERNAL \"sum\"
+		OP_GET_EXTERNAL \"n\"
+		OP_ADD
+		OP_SET_EXTERNAL \"sum\"
+		OP_GET_EXTERNAL \"n\"
+		OP_CONSTANT 1
+		OP_SUBTRACT
+		OP_SET_EXTERNAL \"n\"
+		OP_JMP loop
+done:
+		OP_POP
+		OP_GET_EXTERNAL \"sum\"
+		OP_RETURN
+";
+
+#[test]
+fn hand_written() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	let chunk = runtime.assemble(SUM).unwrap();
+	assert_eq!(chunk.lines()[0], 3);
+	let result = runtime.execute(&chunk, &mut env).unwrap();
+	assert_eq!(result.to_string(), "10");
+
+	// offsets of an edited disassembly are jump targets, without source line the line of the text is used
+	let chunk = Assembler::new()
+		.assemble(
+			"0000    7 OP_TRUE
+			 0001    | OP_JMP_IF_TRUE     0005
+			          OP_CONSTANT        \"skipped\"
+			 0005    8 OP_RETURN",
+		)
+		.unwrap();
+	assert_eq!(chunk.lines(), &[7, 7, 7, 7, 3, 3, 8]);
+	let result = runtime.execute(&chunk, &mut env).unwrap();
+	assert_eq!(result.to_string(), "true");
+}
+
+#[rstest]
+#[case("OP_UNKNOWN\nOP_RETURN", "unknown instruction OP_UNKNOWN at line 1")]
+#[case("OP_NONE\nOP_RETURN", "unknown instruction OP_NONE at line 1")]
+#[case("OP_JMP end\nOP_RETURN", "the label end at line 1 is not defined")]
+#[case("a: OP_NIL\na: OP_RETURN", "the label a at line 2 is already defined")]
+#[case("OP_JMP 0003\n0000 1 OP_RETURN", "the jump target 3 at line 1 is not an instruction")]
+#[case("OP_JMP end\nOP_RETURN\nend:", "the jump target end at line 1 is not an instruction")]
+#[case("OP_NIL", "expected OP_RETURN as last instruction at line 1")]
+#[case("OP_CONSTANT\nOP_RETURN", "the instruction OP_CONSTANT at line 1 expects operands")]
+#[case("OP_CONSTANT 'a'\nOP_RETURN", "invalid operand 'a' at line 1")]
+#[case("OP_CONSTANT \"a\\q\"\nOP_RETURN", "invalid operand \"a\\q\" at line 1")]
+#[case("OP_CONSTANT 0 1\nOP_CONSTANT 0 2\nOP_RETURN", "invalid operand 0 at line 2")]
+#[case("OP_NIL 1\nOP_RETURN", "invalid operand 1 at line 1")]
+#[case("1 2 3 OP_RETURN", "invalid operand 3 at line 1")]
+#[case("OP_CALL unknown (0)\nOP_RETURN", "the function unknown at line 1 is not defined")]
+#[case("OP_CALL len (2)\nOP_RETURN", "the function len at line 1 does not take 2 arguments")]
+#[case("OP_POP\nOP_RETURN", "the instruction OP_POP at line 1 takes values from an empty stack")]
+#[case("OP_ADD\nOP_RETURN", "the instruction OP_ADD at line 1 takes values from an empty stack")]
+#[case(
+	"OP_NIL\nOP_ADD\nOP_RETURN",
+	"the instruction OP_ADD at line 2 takes values from an empty stack"
+)]
+#[case(
+	"OP_TRUE\nOP_JMP_IF_TRUE end\nOP_POP\nend: OP_POP\nOP_POP\nOP_RETURN",
+	"the instruction OP_POP at line 5 takes values from an empty stack"
+)]
+#[case(
+	"loop: OP_NIL\nOP_JMP loop\nOP_RETURN",
+	"the instruction OP_NIL at line 1 exceeds the stack size"
+)]
+#[case(
+	"OP_NIL\nOP_NIL\nOP_NIL\nOP_NIL\nOP_NIL\nOP_NIL\nOP_NIL\nOP_NIL\nOP_NIL\nOP_RETURN",
+	"the instruction OP_NIL at line 9 exceeds the stack size"
+)]
+fn errors(#[case] text: &str, #[case] message: &str) {
+	let error = Assembler::new().assemble(text).unwrap_err();
+	assert_eq!(error.to_string(), message);
+	assert!(matches!(Runtime::default().assemble(text), Err(Error::Compilation { .. })));
+}
+
+#[test]
+fn empty() {
+	let chunk = Assembler::default()
+		.assemble("// nothing\n\n")
+		.unwrap();
+	assert!(chunk.code().is_empty());
+	assert!(matches!(
+		Assembler::default().assemble("OP_POP"),
+		Err(CompilationError::ReturnExpected { pos: 1 })
+	));
+}
@@ -0,0 +1,201 @@
+//! Tests of the abstract syntax tree
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use std::collections::BTreeMap;
+
+use rstest::rstest;
+use tinyscript::{
+	Runtime,
+	compilation::{
+		CompilationError, Compiler, Expr, Parser, Stmt,
+		ast::{AssignOp, BinaryOp, ExprKind, Literal, LogicalOp, StmtKind, TemplatePart},
+	},
+};
+
+fn parse_ast(script: &str) -> Vec<Stmt> {
+	Parser::new()
+		.parse_ast(&BTreeMap::new(), script)
+		.unwrap()
+}
+
+fn expression(statement: &Stmt) -> &Expr {
+	match &statement.kind {
+		StmtKind::Expression(expr) | StmtKind::Print(expr) => expr,
+		StmtKind::Yield => unreachable!(),
+	}
+}
+
+#[test]
+fn structure() {
+	let statements = parse_ast("a := 1 + 2 * 3;\nprint a > 2 && !false;\nyield;");
+	assert_eq!(statements.len(), 3);
+
+	let ExprKind::Assign { op, name, value } = &expression(&statements[0]).kind else {
+		unreachable!()
+	};
+	assert_eq!(*op, AssignOp::Define);
+	assert_eq!(name.as_ref(), "a");
+	let ExprKind::Binary { op, left, right, .. } = &value.kind else {
+		unreachable!()
+	};
+	assert_eq!(*op, BinaryOp::Add);
+	assert_eq!(left.kind, ExprKind::Literal(Literal::Int(1)));
+	assert!(matches!(
+		right.kind,
+		ExprKind::Binary {
+			op: BinaryOp::Multiply,
+			..
+		}
+	));
+
+	assert!(matches!(statements[1].kind, StmtKind::Print(_)));
+	assert!(matches!(
+		expression(&statements[1]).kind,
+		ExprKind::Logical { op: LogicalOp::And, .. }
+	));
+	assert_eq!(statements[2].kind, StmtKind::Yield);
+	assert_eq!(statements[2].span.line, 3);
+}
+
+#[test]
+fn spans() {
+	let script = "x := 1;\nprint  max(x,\n 2) ;\ns := `x = {x + 1}!` as str";
+	let statements = parse_ast(script);
+	let text = |expr: &Expr| &script[expr.span.start..expr.span.end];
+
+	let print = &statements[1];
+	assert_eq!(&script[print.span.start..print.span.end], "print  max(x,\n 2) ;");
+	assert_eq!((print.span.line, print.span.end_line), (2, 3));
+	let call = expression(print);
+	assert_eq!(text(call), "max(x,\n 2)");
+	let ExprKind::Call { args, commas, .. } = &call.kind else {
+		unreachable!()
+	};
+	assert_eq!(text(&args[1]), "2");
+	assert_eq!(args[1].span.line, 3);
+	assert_eq!(commas.len(), 1);
+	assert_eq!(&script[commas[0].start..commas[0].end], ",");
+
+	let ExprKind::Assign { value, .. } = &expression(&statements[2]).kind else {
+		unreachable!()
+	};
+	assert_eq!(text(value), "`x = {x + 1}!` as str");
+	let ExprKind::Cast {
+		value,
+		operator,
+		type_name,
+	} = &value.kind
+	else {
+		unreachable!()
+	};
+	assert_eq!(type_name.as_ref(), "str");
+	assert_eq!(&script[operator.start..operator.end], "as");
+	let ExprKind::Template(parts) = &value.kind else {
+		unreachable!()
+	};
+	assert_eq!(parts.len(), 3);
+	assert_eq!(parts[0], TemplatePart::Text("x = ".into()));
+	let TemplatePart::Expr(embedded) = &parts[1] else {
+		unreachable!()
+	};
+	assert_eq!(text(embedded), "x + 1");
+	let ExprKind::Binary { operator, .. } = &embedded.kind else {
+		unreachable!()
+	};
+	assert_eq!(&script[operator.start..operator.end], "+");
+}
+
+#[rstest]
+#[case("a := 1; b := a + 2.5; print b * 10;")]
+#[case("s := 'abc'; print len(s) > 3 ? `{s}!` : substr(s, 1) as int;")]
+#[case("a := true; b := a && !false || nil == nil; c := 0x0f & ~3 | 1 ^ 2; -(c);")]
+#[case("x := 1;\nyield;\nx += 2;\nx -= 1; x *= 2; x /= 3; x = x >= 1 != x <= 2;")]
+#[case("")]
+fn compiled_like_parsed(#[case] script: &str) {
+	let mut parser = Parser::new();
+	parser.set_optimization(false);
+	let chunk = parser.parse(&BTreeMap::new(), script).unwrap();
+
+	let mut compiler = Compiler::new();
+	compiler.set_optimization(false);
+	let compiled = compiler.compile(&parse_ast(script)).unwrap();
+	assert_eq!(compiled.code(), chunk.code());
+	assert_eq!(compiled.lines(), chunk.lines());
+	assert_eq!(compiled.disassembler().to_string(), chunk.disassembler().to_string());
+}
+
+#[test]
+fn resolved_by_compiler() {
+	// the syntax tree does not know the library
+	let statements = parse_ast("unknown(1); LIMIT := 3; 1 as complex;");
+	assert_eq!(statements.len(), 3);
+
+	let mut compiler = Compiler::new();
+	compiler
+		.library_mut()
+		.register_constant("LIMIT", 10)
+		.unwrap();
+	assert!(matches!(
+		compiler.compile(&statements[..1]),
+		Err(CompilationError::FunctionNotFound { pos: 1, .. })
+	));
+	assert!(matches!(
+		compiler.compile(&statements[1..2]),
+		Err(CompilationError::ConstantAssignment { .. })
+	));
+	assert!(matches!(
+		compiler.compile(&statements[2..]),
+		Err(CompilationError::UnknownType { .. })
+	));
+}
+
+#[test]
+fn errors_and_runtime() {
+	let runtime = Runtime::default();
+	assert!(runtime.parse_ast("a := ;").is_err());
+	assert!(runtime.parse_ast("a := (1;").is_err());
+	let statements = runtime.parse_ast("a := 1;").unwrap();
+	assert_eq!(statements[0].span.end, 7);
+}
+
+#[rstest]
+#[case("a := 1 +", 1)]
+#[case("a := 1;\nb := -", 2)]
+#[case("a := (1 +\n2\n", 3)]
+fn errors_at_the_end(#[case] script: &str, #[case] line: usize) {
+	let Err(error) = Parser::new().parse_ast(&BTreeMap::new(), script) else {
+		unreachable!()
+	};
+	assert_eq!(error.line(), Some(line));
+}
+
+#[test]
+fn lexer_error_while_recovering() {
+	let Err(error) = Parser::new().parse_ast(&BTreeMap::new(), "a := ;\nb := 'unterminated") else {
+		unreachable!()
+	};
+	assert!(matches!(error, CompilationError::ExpressionExpected { pos: 1, .. }));
+}
+
+#[test]
+fn expression_expected_at_the_end() {
+	let Err(error) = Parser::new().parse_ast(&BTreeMap::new(), "a := -") else {
+		unreachable!()
+	};
+	assert_eq!(error.to_string(), "expression expected at line 1, got NONE");
+}
+
+#[rstest]
+#[case("a := 1 +", "expression expected at line 1, got NONE")]
+#[case("a := 1;\nb := -", "expression expected at line 2, got NONE")]
+#[case("a := (1 +\n2", "expected token ), found Token NONE at line 2")]
+#[case("a := `pos {\nb", "unterminated string `pos {\nb at line 1")]
+fn unfinished(#[case] script: &str, #[case] message: &str) {
+	let Err(error) = Parser::new().parse_ast(&BTreeMap::new(), script) else {
+		unreachable!()
+	};
+	assert_eq!(error.to_string(), message);
+}
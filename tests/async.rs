@@ -0,0 +1,154 @@
+//! Tests of asynchronous execution
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use std::{
+	pin::pin,
+	task::{Context, Poll, Waker},
+};
+
+use tinyscript::{
+	Error, Runtime, ScriptingValue,
+	environment::{self, AsyncEnvironment, DefaultEnvironment, Environment},
+	execution::{ExecutionError, ExecutionResult},
+	library::NativeFuture,
+};
+
+use rstest::rstest;
+
+/// A minimal executor polling the future until it is ready, returning the number of polls.
+fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+	let mut future = pin!(future);
+	let mut context = Context::from_waker(Waker::noop());
+	let mut polls = 1;
+	loop {
+		if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+			return (output, polls);
+		}
+		polls += 1;
+	}
+}
+
+/// A future that is pending once before it is ready.
+async fn pending_once() {
+	let mut pending = true;
+	std::future::poll_fn(|context| {
+		if pending {
+			pending = false;
+			context.waker().wake_by_ref();
+			Poll::Pending
+		} else {
+			Poll::Ready(())
+		}
+	})
+	.await;
+}
+
+fn integer(value: &ScriptingValue) -> ExecutionResult<i64> {
+	if let ScriptingValue::Int64(i) = value {
+		Ok(*i)
+	} else {
+		Err(ExecutionError::NoInteger {
+			value: value.to_string().into(),
+		})
+	}
+}
+
+fn delayed_double(args: Vec<ScriptingValue>) -> NativeFuture {
+	Box::pin(async move {
+		pending_once().await;
+		Ok(ScriptingValue::Int64(integer(&args[0])? * 2))
+	})
+}
+
+fn double(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Int64(integer(&args[0])? * 2))
+}
+
+/// An environment with a backend that needs a poll for each access.
+#[derive(Default)]
+struct RemoteEnvironment {
+	inner: DefaultEnvironment,
+}
+
+impl AsyncEnvironment for RemoteEnvironment {
+	async fn define_env_async(&mut self, key: &str, value: ScriptingValue) -> Result<(), environment::Error> {
+		pending_once().await;
+		self.inner.define_env(key, value)
+	}
+
+	async fn get_env_async(&self, key: &str) -> Result<ScriptingValue, environment::Error> {
+		pending_once().await;
+		self.inner.get_env(key)
+	}
+
+	async fn set_env_async(&mut self, key: &str, value: ScriptingValue) -> Result<(), environment::Error> {
+		pending_once().await;
+		self.inner.set_env(key, value)
+	}
+}
+
+fn runtime() -> Runtime {
+	let mut runtime = Runtime::default();
+	runtime
+		.register_async_function("delayed_double", 1..=1, delayed_double)
+		.unwrap();
+	runtime
+		.register_function("double", 1..=1, double)
+		.unwrap();
+	runtime
+}
+
+#[rstest]
+#[case("print delayed_double(21);", b"42\n", 2)]
+#[case("print double(delayed_double(2)) + delayed_double(1);", b"10\n", 3)]
+#[case("print double(3);", b"6\n", 1)]
+#[case("a := delayed_double(2); a += 1; print a;", b"5\n", 2)]
+fn async_functions(#[case] input: &str, #[case] expected: &[u8], #[case] expected_polls: usize) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = runtime();
+
+	let (result, polls) = block_on(runtime.run_async(input, &mut env));
+	result.unwrap();
+	assert_eq!(runtime.stdout(), expected);
+	assert_eq!(polls, expected_polls);
+}
+
+#[test]
+fn async_environment() {
+	let mut env = RemoteEnvironment::default();
+	let mut runtime = runtime();
+
+	// define, get, set and get again each need a poll
+	let (result, polls) = block_on(runtime.run_async("a := 1; a = a + 1; print a;", &mut env));
+	result.unwrap();
+	assert_eq!(runtime.stdout(), b"2\n");
+	assert_eq!(polls, 5);
+	assert_eq!(env.inner.get_env("a").unwrap().to_string(), "2");
+}
+
+#[test]
+fn async_function_needs_async_execution() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = runtime();
+
+	assert!(matches!(
+		runtime.run("print delayed_double(1);", &mut env),
+		Err(Error::Execution {
+			source: ExecutionError::AsyncFunction { .. }
+		})
+	));
+}
+
+#[test]
+fn futures_are_send() {
+	fn assert_send<T: Send>(_: &T) {}
+
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = runtime();
+	let future = runtime.run_async("print 1;", &mut env);
+	assert_send(&future);
+	block_on(future).0.unwrap();
+}
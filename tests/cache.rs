@@ -0,0 +1,110 @@
+//! Tests of the compile cache and precompiled scripts
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use std::sync::Arc;
+
+use tinyscript::{Error, Runtime, cache::ChunkCache, environment::DefaultEnvironment};
+
+#[test]
+fn runs_reuse_compiled_scripts() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	runtime.run("a := 1;", &mut env).unwrap();
+	let first = runtime.compile("a := 1;").unwrap();
+	let second = runtime.compile("a := 1;").unwrap();
+	assert!(Arc::ptr_eq(&first, &second));
+	assert_eq!(runtime.cache_len(), 1);
+
+	runtime.run("print a;", &mut env).unwrap();
+	runtime.run("print a;", &mut env).unwrap();
+	assert_eq!(runtime.stdout(), b"1\n");
+	assert_eq!(runtime.cache_len(), 2);
+}
+
+#[test]
+fn invalid_scripts_are_not_cached() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(runtime.run("1 +;", &mut env).is_err());
+	assert_eq!(runtime.cache_len(), 0);
+}
+
+#[test]
+fn least_recently_used_is_evicted() {
+	let mut cache = ChunkCache::new(2);
+	let mut runtime = Runtime::default();
+
+	cache.insert("1;", runtime.compile("1;").unwrap());
+	cache.insert("2;", runtime.compile("2;").unwrap());
+	assert!(cache.get("1;").is_some());
+	cache.insert("3;", runtime.compile("3;").unwrap());
+	assert_eq!(cache.len(), 2);
+	assert!(cache.contains("1;"));
+	assert!(!cache.contains("2;"));
+	assert!(cache.contains("3;"));
+
+	cache.set_capacity(1);
+	assert!(!cache.contains("1;"));
+	assert!(cache.contains("3;"));
+
+	cache.set_capacity(0);
+	cache.insert("1;", runtime.compile("1;").unwrap());
+	assert!(cache.is_empty());
+}
+
+#[test]
+fn registrations_invalidate_the_cache() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	// without the enum `Color` is an undefined variable
+	assert!(runtime.run("print Color;", &mut env).is_err());
+	runtime.run("print 1;", &mut env).unwrap();
+	assert_eq!(runtime.cache_len(), 2);
+
+	runtime.register_enum_tuple("Color", 2).unwrap();
+	assert_eq!(runtime.cache_len(), 0);
+	runtime.run("print Color;", &mut env).unwrap();
+	assert_eq!(runtime.stdout(), b"2\n");
+
+	runtime.set_cache_capacity(0);
+	runtime.run("print 1;", &mut env).unwrap();
+	assert_eq!(runtime.cache_len(), 0);
+}
+
+#[test]
+fn precompiled_scripts() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	runtime
+		.precompile("init", "counter := 0;")
+		.unwrap();
+	runtime
+		.precompile("tick", "counter += 1; print counter;")
+		.unwrap();
+	assert!(matches!(runtime.precompile("tick", "1;"), Err(Error::DuplicateScript { .. })));
+	assert!(matches!(
+		runtime.precompile("invalid", "1 +;"),
+		Err(Error::Compilation { .. })
+	));
+
+	runtime.run_script("init", &mut env).unwrap();
+	runtime.run_script("tick", &mut env).unwrap();
+	runtime.run_script("tick", &mut env).unwrap();
+	assert_eq!(runtime.stdout(), b"2\n");
+
+	// precompiled scripts survive clearing the cache
+	runtime.clear_cache();
+	assert!(runtime.script("tick").is_some());
+	assert!(runtime.remove_script("tick").is_some());
+	assert!(matches!(
+		runtime.run_script("tick", &mut env),
+		Err(Error::ScriptNotFound { .. })
+	));
+}
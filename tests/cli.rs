@@ -0,0 +1,164 @@
+//! Tests of the command line runner binary
+// Copyright © 2026 Stephan Kunz
+
+#![cfg(feature = "cli")]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use std::{
+	fs,
+	io::Write,
+	path::PathBuf,
+	process::{Command, Stdio},
+};
+
+use rstest::rstest;
+
+/// Write a file into the temporary directory of the tests.
+fn file(name: &str, content: &str) -> String {
+	let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+	fs::write(&path, content).unwrap();
+	path.to_str().unwrap().to_owned()
+}
+
+/// Run the binary with a script on stdin, returns the exit code, stdout and stderr.
+fn run(args: &[&str], input: &str) -> (i32, String, String) {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_tinyscript"))
+		.args(args)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.unwrap();
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(input.as_bytes())
+		.unwrap();
+	let output = child.wait_with_output().unwrap();
+	(
+		output.status.code().unwrap(),
+		String::from_utf8(output.stdout).unwrap(),
+		String::from_utf8(output.stderr).unwrap(),
+	)
+}
+
+#[rstest]
+#[case(&[], "print 'hello'; 1 + 2", "hello\nresult: 3\n")]
+#[case(&["-"], "x := 'a' + 'b';", "result: nil\nx = 'ab'\n")]
+#[case(&["--set", "a=41", "--set", "b=1.5"], "a = a + 1; b * 2", "result: 3\na = 42\nb = 1.5\n")]
+#[case(&["--set", "name=robot", "--set", "on = true"], "name", "result: 'robot'\nname = 'robot'\non = true\n")]
+#[case(&["--set", "a=1", "--set", "a='one'"], "a", "result: 'one'\na = 'one'\n")]
+#[case(&["--set", "x=print 5", "--set", "y=PI", "--set", "z=1 + 2"], "x", "result: 'print 5'\nx = 'print 5'\ny = 'PI'\nz = '1 + 2'\n")]
+#[case(&["--set", "a=-0x0f", "--set", "b=-1.5", "--set", "c=-true", "--set", "d=nil"], "a", "result: -15\na = -15\nb = -1.5\nc = '-true'\nd = nil\n")]
+fn stdin(#[case] args: &[&str], #[case] input: &str, #[case] expected: &str) {
+	let (code, stdout, stderr) = run(args, input);
+	assert_eq!(stderr, "");
+	assert_eq!(code, 0);
+	assert_eq!(stdout, expected);
+}
+
+#[test]
+fn script_file() {
+	let script = file("cli_script.ts", "x := 2;\nprint x;\nx * x");
+	let (code, stdout, _) = run(&[&script], "");
+	assert_eq!(code, 0);
+	assert_eq!(stdout, "2\nresult: 4\nx = 2\n");
+}
+
+#[rstest]
+#[case(
+	"cli_vars.json",
+	r#"{ "speed": 2.5, "name": "bot", "on": false, "none": null, "count": 3 }"#
+)]
+#[case("cli_vars.toml", "speed = 2.5\nname = 'bot'\non = false\ncount = 3\n")]
+fn variables(#[case] name: &str, #[case] content: &str) {
+	let vars = file(name, content);
+	let (code, stdout, _) = run(&["--vars", &vars, "--set", "count=4"], "speed = speed * 2; name");
+	assert_eq!(code, 0);
+	assert!(stdout.starts_with("result: 'bot'\n"));
+	for expected in [
+		"count = 4\n",
+		"name = 'bot'\n",
+		"on = false\n",
+		"speed = 5\n",
+	] {
+		assert!(stdout.contains(expected), "{expected:?} missing in {stdout:?}");
+	}
+}
+
+#[rstest]
+#[case("cli_enums.json", r#"{ "Red": 1, "Green": 2 }"#)]
+#[case("cli_enums.toml", "Red = 1\nGreen = 2\n")]
+fn enums(#[case] name: &str, #[case] content: &str) {
+	let enums = file(name, content);
+	let (code, stdout, _) = run(&["--enums", &enums], "color := Green; color == 2");
+	assert_eq!(code, 0);
+	assert_eq!(stdout, "result: true\ncolor = 2\n");
+}
+
+#[rstest]
+#[case(
+	"x := 1;\ny := x / ;",
+	"stdin:2: compilation error: expression expected at line 2, got ;\n   2 | y := x / ;\n"
+)]
+#[case(
+	"print 'start'; a - 1",
+	"stdin:1: execution error: to Strings you can only 'ADD' something\n   1 | print 'start'; a - 1\n"
+)]
+#[case(
+	"x := 1;\nprint x;\ny := a\n - x;",
+	"stdin:4: execution error: to Strings you can only 'ADD' something\n   4 |  - x;\n"
+)]
+#[case(
+	"x := 1;\nx = undefined;",
+	"stdin:2: execution error: environment error: the environment variable undefined has not been defined\n   2 | x = undefined;\n"
+)]
+fn failures(#[case] input: &str, #[case] expected: &str) {
+	let (code, _, stderr) = run(&["--set", "a='x'"], input);
+	assert_eq!(code, 1);
+	assert_eq!(stderr, expected);
+}
+
+#[test]
+fn output_before_failure() {
+	let (code, stdout, _) = run(&[], "print 'start'; 'a' - x");
+	assert_eq!(code, 1);
+	assert_eq!(stdout, "start\n");
+}
+
+#[rstest]
+#[case(&["--bogus"], 2, "unknown option --bogus")]
+#[case(&["--set"], 2, "--set expects a value")]
+#[case(&["--set", "value"], 2, "--set expects <key=value>, got value")]
+#[case(&["--vars", "vars.yaml"], 2, "vars.yaml: only .json and .toml files are supported")]
+#[case(&["a.ts", "b.ts"], 2, "only one script can be executed, got b.ts")]
+#[case(&["missing.ts"], 1, "missing.ts: ")]
+fn invalid_arguments(#[case] args: &[&str], #[case] expected_code: i32, #[case] expected: &str) {
+	let (code, _, stderr) = run(args, "");
+	assert_eq!(code, expected_code);
+	assert!(stderr.starts_with(expected), "{stderr:?}");
+}
+
+#[rstest]
+#[case(
+	"cli_array.json",
+	r#"{ "a": [1] }"#,
+	"the value of a is not nil, a boolean, a number or a string"
+)]
+#[case("cli_enum_range.toml", "Red = 1000\n", "the enum value Red is not an integer in -128..=127")]
+fn invalid_files(#[case] name: &str, #[case] content: &str, #[case] expected: &str) {
+	let path = file(name, content);
+	let option = if name.contains("enum") { "--enums" } else { "--vars" };
+	let (code, _, stderr) = run(&[option, &path], "");
+	assert_eq!(code, 1);
+	assert_eq!(stderr, format!("{path}: {expected}\n"));
+}
+
+#[test]
+fn help() {
+	let (code, stdout, _) = run(&["--help"], "");
+	assert_eq!(code, 0);
+	assert!(stdout.starts_with("usage: tinyscript [options] [file]"));
+}
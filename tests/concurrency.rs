@@ -0,0 +1,66 @@
+//! Tests of concurrent execution with a shared engine
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use std::{sync::Arc, thread};
+
+use tinyscript::{Engine, Runtime, environment::DefaultEnvironment};
+
+const THREADS: usize = 8;
+
+#[test]
+fn runtimes_share_the_engine() {
+	let engine = Arc::new(Engine::default());
+	engine.register_enum_tuple("Start", 10).unwrap();
+	engine
+		.precompile("count", "print limit > Start ? limit : Start;")
+		.unwrap();
+
+	let handles: Vec<_> = (0..THREADS)
+		.map(|i| {
+			let engine = engine.clone();
+			thread::spawn(move || {
+				let mut env = DefaultEnvironment::default();
+				let mut runtime = engine.executor();
+				runtime
+					.run(&format!("limit := {};", 20 + i), &mut env)
+					.unwrap();
+				runtime.run_script("count", &mut env).unwrap();
+				String::from_utf8(runtime.stdout().clone()).unwrap()
+			})
+		})
+		.collect();
+
+	for (i, handle) in (0..THREADS).zip(handles) {
+		assert_eq!(handle.join().unwrap(), format!("{}\n", 20 + i));
+	}
+	assert_eq!(engine.cache_len(), 1 + THREADS);
+}
+
+#[test]
+fn chunks_are_executed_on_other_threads() {
+	let mut runtime = Runtime::default();
+	let chunk = runtime.compile("print 'a' + 1;").unwrap();
+
+	let handle = thread::spawn(move || {
+		let mut env = DefaultEnvironment::default();
+		let mut runtime = Runtime::default();
+		runtime.execute(&chunk, &mut env).unwrap();
+		runtime.stdout().clone()
+	});
+	assert_eq!(handle.join().unwrap(), b"a1\n");
+}
+
+#[test]
+fn cloned_runtimes_do_not_share_the_engine() {
+	let mut runtime = Runtime::default();
+	let shared = runtime.engine().executor();
+	let clone = runtime.clone();
+
+	runtime.register_enum_tuple("Color", 1).unwrap();
+	assert_eq!(shared.enum_discriminant("Color"), Some(1));
+	assert_eq!(clone.enum_discriminant("Color"), None);
+	assert!(Arc::ptr_eq(runtime.engine(), shared.engine()));
+}
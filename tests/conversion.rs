@@ -0,0 +1,78 @@
+//! Tests of type conversion and inspection
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{Error, Runtime, environment::DefaultEnvironment, execution::ExecutionError};
+
+use rstest::rstest;
+
+#[rstest]
+#[case("print int(3.9);", b"3\n")]
+#[case("print int(-3.9);", b"-3\n")]
+#[case("print int('42');", b"42\n")]
+#[case("print int(' 4.5 ');", b"4\n")]
+#[case("print int(true);", b"1\n")]
+#[case("print float(3);", b"3\n")]
+#[case("print float('2.5') * 2;", b"5\n")]
+#[case("print float(false);", b"0\n")]
+#[case("print str(42) + 1;", b"421\n")]
+#[case("print str(nil);", b"nil\n")]
+#[case("print bool(0);", b"false\n")]
+#[case("print bool(0.5);", b"true\n")]
+#[case("print bool('true');", b"true\n")]
+#[case("print bool('0');", b"false\n")]
+#[case("print bool(nil);", b"false\n")]
+#[case("print typeof(nil);", b"nil\n")]
+#[case("print typeof(true);", b"bool\n")]
+#[case("print typeof(1);", b"int\n")]
+#[case("print typeof(1.5);", b"float\n")]
+#[case("print typeof('a');", b"string\n")]
+#[case("print typeof(1 + 1.0) == 'float';", b"true\n")]
+#[case("print 3.9 as int;", b"3\n")]
+#[case("print '42' as int + 1;", b"43\n")]
+#[case("print 2 * '21' as int;", b"42\n")]
+#[case("print -3.9 as int;", b"-3\n")]
+#[case("print 1 as float as string + '!';", b"1!\n")]
+#[case("print 1 as str + 1;", b"11\n")]
+#[case("print 1 as bool;", b"true\n")]
+#[case("print typeof(1 as float);", b"float\n")]
+fn conversions(#[case] input: &str, #[case] expected: &[u8]) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	runtime.run(input, &mut env).unwrap();
+	assert_eq!(runtime.stdout(), expected);
+}
+
+#[rstest]
+#[case("int('abc');")]
+#[case("int(nil);")]
+#[case("int(1 / 0.0);")]
+#[case("float('abc');")]
+#[case("float(nil);")]
+#[case("bool('abc');")]
+#[case("'abc' as int;")]
+fn failed_conversions(#[case] input: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(matches!(
+		runtime.run(input, &mut env),
+		Err(Error::Execution {
+			source: ExecutionError::Conversion { .. }
+		})
+	));
+}
+
+#[rstest]
+#[case("1 as number;")]
+#[case("1 as;")]
+#[case("1 as 2;")]
+fn invalid_casts(#[case] input: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(matches!(runtime.run(input, &mut env), Err(Error::Compilation { .. })));
+}
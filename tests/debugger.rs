@@ -0,0 +1,149 @@
+//! Tests of the step debugger
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{
+	Environment, Error, Runtime,
+	environment::DefaultEnvironment,
+	execution::{Debugger, ExecutionError},
+};
+
+const SCRIPT: &str = "a := 1;\nb := a + 1;\nprint b;\nb * 10;";
+
+#[test]
+fn step_through_all_instructions() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.set_optimization(false);
+	let chunk = runtime.parse(SCRIPT).unwrap();
+	let mut debugger = Debugger::default();
+
+	// a new execution pauses before the first instruction
+	assert!(
+		runtime
+			.debug_step(&chunk, &mut debugger, &mut env)
+			.unwrap()
+			.is_none()
+	);
+	assert_eq!(debugger.paused().unwrap().ip(), 0);
+	assert_eq!(debugger.paused().unwrap().line(), 1);
+
+	let mut steps = 0;
+	let result = loop {
+		if let Some(value) = runtime
+			.debug_step(&chunk, &mut debugger, &mut env)
+			.unwrap()
+		{
+			break value;
+		}
+		steps += 1;
+		assert_eq!(debugger.paused().unwrap().ip(), steps);
+	};
+	assert_eq!(steps + 1, chunk.instructions().len());
+	assert_eq!(result.to_string(), "20");
+	assert!(!debugger.is_paused());
+	assert_eq!(runtime.stdout(), b"2\n");
+}
+
+#[test]
+fn continue_to_breakpoints() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	let chunk = runtime.parse(SCRIPT).unwrap();
+	let mut debugger = Debugger::default();
+	assert!(debugger.add_breakpoint(2));
+	assert!(debugger.add_breakpoint(4));
+	assert!(!debugger.add_breakpoint(4));
+
+	assert!(
+		runtime
+			.debug_continue(&chunk, &mut debugger, &mut env)
+			.unwrap()
+			.is_none()
+	);
+	assert_eq!(debugger.paused().unwrap().line(), 2);
+	// variables can be inspected while paused
+	assert_eq!(env.get_env("a").unwrap().to_string(), "1");
+	assert!(env.get_env("b").is_err());
+
+	assert!(
+		runtime
+			.debug_continue(&chunk, &mut debugger, &mut env)
+			.unwrap()
+			.is_none()
+	);
+	let paused = debugger.paused().unwrap();
+	assert_eq!(paused.line(), 4);
+	assert!(paused.stack().is_empty());
+	assert_eq!(runtime.stdout(), b"2\n");
+
+	// stepping within a line shows the stack
+	runtime
+		.debug_step(&chunk, &mut debugger, &mut env)
+		.unwrap();
+	assert_eq!(debugger.paused().unwrap().stack()[0].to_string(), "2");
+
+	assert!(debugger.remove_breakpoint(4));
+	let result = runtime
+		.debug_continue(&chunk, &mut debugger, &mut env)
+		.unwrap();
+	assert_eq!(result.unwrap().to_string(), "20");
+}
+
+#[test]
+fn continue_without_breakpoints_runs_to_the_end() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	let chunk = runtime.parse(SCRIPT).unwrap();
+	let mut debugger = Debugger::default();
+
+	let result = runtime
+		.debug_continue(&chunk, &mut debugger, &mut env)
+		.unwrap();
+	assert_eq!(result.unwrap().to_string(), "20");
+
+	// an aborted execution starts again
+	debugger.add_breakpoint(3);
+	runtime
+		.debug_continue(&chunk, &mut debugger, &mut env)
+		.unwrap();
+	assert_eq!(debugger.paused().unwrap().line(), 3);
+	debugger.abort();
+	runtime
+		.debug_continue(&chunk, &mut debugger, &mut env)
+		.unwrap();
+	assert_eq!(debugger.paused().unwrap().line(), 3);
+}
+
+#[test]
+fn continue_with_another_chunk_fails() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.set_optimization(false);
+	let chunk = runtime.parse("print -1 + 2;").unwrap();
+	let other = runtime.parse("print 1 + 2;").unwrap();
+	let mut debugger = Debugger::default();
+
+	for _ in 0..3 {
+		runtime
+			.debug_step(&chunk, &mut debugger, &mut env)
+			.unwrap();
+	}
+	assert_eq!(debugger.paused().unwrap().ip(), 2);
+	assert_eq!(debugger.paused().unwrap().stack()[0].to_string(), "-1");
+
+	assert!(matches!(
+		runtime.debug_continue(&other, &mut debugger, &mut env),
+		Err(Error::Execution {
+			source: ExecutionError::InvalidResume
+		})
+	));
+	// the paused execution is kept for its own chunk
+	let result = runtime
+		.debug_continue(&chunk, &mut debugger, &mut env)
+		.unwrap();
+	assert!(result.is_some());
+	assert_eq!(runtime.stdout(), b"1\n");
+}
@@ -0,0 +1,95 @@
+//! Tests of the disassembler
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use core::fmt::Write;
+
+use tinyscript::{
+	Runtime, ScriptingValue,
+	execution::{ExecutionResult, Operands, op_code::OpCode},
+};
+
+#[allow(clippy::unnecessary_wraps)]
+fn count(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	Ok(ScriptingValue::Int64(i64::try_from(args.len()).unwrap_or_default()))
+}
+
+#[test]
+fn records() {
+	let mut runtime = Runtime::default();
+	runtime.set_optimization(false);
+	runtime
+		.register_function("count", 0..=1, count)
+		.unwrap();
+	let chunk = runtime
+		.parse("a := 'hello';\na == 'x' ? count(a) : 2.0;")
+		.unwrap();
+
+	let records: Vec<_> = chunk.disassembler().collect();
+	let size: usize = records
+		.windows(2)
+		.map(|pair| pair[1].offset - pair[0].offset)
+		.sum();
+	assert_eq!(size + 1, chunk.code().len());
+	assert_eq!(records.len(), chunk.instructions().len());
+	for (record, instruction) in records.iter().zip(chunk.instructions()) {
+		assert_eq!(record.op_code, instruction.op_code());
+	}
+
+	assert_eq!(records[0].line, 1);
+	assert!(matches!(
+		records[0].operands,
+		Operands::Constant { pos: 0, value: ScriptingValue::String(s) } if s.as_ref() == "hello"
+	));
+	assert_eq!(records[1].op_code, OpCode::DefineExternal);
+	let call = records
+		.iter()
+		.find(|record| record.op_code == OpCode::Call)
+		.unwrap();
+	assert_eq!(call.line, 2);
+	assert!(matches!(
+		call.operands,
+		Operands::Call {
+			pos: 0,
+			name: "count",
+			count: 1
+		}
+	));
+	let jump = records
+		.iter()
+		.find(|record| record.op_code == OpCode::JmpIfFalse)
+		.unwrap();
+	let Operands::Jump(target) = jump.operands else {
+		unreachable!()
+	};
+	assert!(
+		records
+			.iter()
+			.any(|record| record.offset == usize::from(target))
+	);
+}
+
+#[test]
+fn display() {
+	let mut runtime = Runtime::default();
+	runtime.set_optimization(false);
+	let chunk = runtime
+		.parse("a := 'say \"hi\"';\nb := 1.0;\nc := true;")
+		.unwrap();
+
+	let mut text = String::new();
+	write!(text, "{}", chunk.disassembler()).unwrap();
+	let lines: Vec<&str> = text.lines().collect();
+	assert_eq!(lines.len(), chunk.instructions().len());
+	assert_eq!(lines[0], "0000    1 OP_CONSTANT          0 \"say \\\"hi\\\"\"");
+	assert_eq!(lines[1], "0002    | OP_DEFINE_EXTERNAL   1 \"a\"");
+	assert_eq!(lines[2], "0004    2 OP_CONSTANT          2 1.0");
+	assert_eq!(lines[4], "0008    3 OP_TRUE");
+	assert!(lines.last().unwrap().ends_with("OP_RETURN"));
+
+	// a single record always shows its line
+	let record = chunk.disassembler().nth(1).unwrap();
+	assert_eq!(record.to_string(), "0002    1 OP_DEFINE_EXTERNAL   1 \"a\"");
+}
@@ -0,0 +1,39 @@
+//! Tests of the baseline bytecode dispatch
+// Copyright © 2026 Stephan Kunz
+
+#![cfg(feature = "bench")]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{Runtime, environment::DefaultEnvironment, execution::VM};
+
+use rstest::rstest;
+
+#[rstest]
+#[case("print 3 * 4 - 2;", b"10\n")]
+#[case("a := 2; print a > 1 ? a * 3 : 0;", b"6\n")]
+#[case("a := false; print a || 2 > 1;", b"true\n")]
+#[case("a := true; b := a && false ? 'x' : `y{a}`; print b;", b"ytrue\n")]
+#[case("a := '3'; print str(int(a) * 2) + a;", b"63\n")]
+fn bytecode_and_instructions_agree(#[case] input: &str, #[case] expected: &[u8]) {
+	for optimize in [true, false] {
+		let mut runtime = Runtime::default();
+		runtime.set_optimization(optimize);
+		let chunk = runtime.parse(input).unwrap();
+
+		let mut env = DefaultEnvironment::default();
+		let mut stdout = Vec::new();
+		let res = VM::default()
+			.run(&chunk, &mut env, &mut stdout)
+			.unwrap();
+		assert_eq!(stdout, expected);
+
+		let mut env = DefaultEnvironment::default();
+		let mut stdout = Vec::new();
+		let res_bytecode = VM::default()
+			.run_bytecode(&chunk, &mut env, &mut stdout)
+			.unwrap();
+		assert_eq!(stdout, expected);
+		assert_eq!(res.to_string(), res_bytecode.to_string());
+	}
+}
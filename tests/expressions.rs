@@ -4,7 +4,7 @@
 #![allow(missing_docs)]
 #![allow(clippy::unwrap_used)]
 
-use tinyscript::{Runtime, environment::DefaultEnvironment};
+use tinyscript::{Environment, Runtime, environment::DefaultEnvironment};
 
 use rstest::rstest;
 
@@ -12,6 +12,9 @@ use rstest::rstest;
 #[case("print (5 - (3 - 1)) + -1;", b"2\n")]
 #[case("print (5 - (3 - 1)) + +1;", b"4\n")]
 #[case("print !(5 - 4 > 3 * 2 == !nil);", b"true\n")]
+#[case("print 6 / 3; // halve it", b"2\n")]
+#[case("print 6 /* halve it */ / 3;", b"2\n")]
+#[case("// first\nprint 6 /\n/* second\n*/\n3;", b"2\n")]
 fn expressions(#[case] input: &str, #[case] expected: &[u8]) {
 	let mut env = DefaultEnvironment::default();
 	let mut runtime = Runtime::default();
@@ -19,3 +22,39 @@ fn expressions(#[case] input: &str, #[case] expected: &[u8]) {
 	runtime.run(input, &mut env).unwrap();
 	assert_eq!(runtime.stdout(), expected);
 }
+
+#[rstest]
+#[case("print ;")]
+#[case("x := ; print 1;")]
+#[case("print 1; print (1 + 2;")]
+fn invalid_expressions(#[case] input: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(runtime.run(input, &mut env).is_err());
+	assert_eq!(runtime.stdout(), b"");
+}
+
+#[test]
+fn stack_overflow() {
+	let mut env = DefaultEnvironment::default();
+	env.define_env("x", 1).unwrap();
+	let mut runtime = Runtime::default();
+
+	let nested = "x + (x + (x + (x + (x + (x + (x + (x + (x + x))))))))";
+	let Err(error) = runtime.run(nested, &mut env) else {
+		unreachable!()
+	};
+	assert!(error.to_string().contains("stack overflow"));
+}
+
+#[test]
+fn execution_error_message() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	let Err(error) = runtime.run("x := 0; print 1 / x;", &mut env) else {
+		unreachable!()
+	};
+	assert_eq!(error.to_string(), "execution error: integer division by zero");
+}
@@ -0,0 +1,82 @@
+//! Tests of the formatter
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use rstest::rstest;
+use tinyscript::{
+	Runtime,
+	compilation::{CompilationError, Formatter},
+};
+
+#[rstest]
+#[case("", "")]
+#[case("a:=1", "a := 1;\n")]
+#[case("a:=1;b   :=a+2.5 ; print b*10;", "a := 1;\nb := a + 2.5;\nprint b * 10;\n")]
+#[case("x = ( 1+2 )*-3", "x = (1 + 2) * -3;\n")]
+#[case("x+=1;x-=1;x*=2;x/=2;", "x += 1;\nx -= 1;\nx *= 2;\nx /= 2;\n")]
+#[case("x : = ! true&&false||nil==nil", "x := !true && false || nil == nil;\n")]
+#[case("c:=0x0f&~3|1^2;c!=1;c<=2;c>=3", "c := 0x0f & ~3 | 1 ^ 2;\nc != 1;\nc <= 2;\nc >= 3;\n")]
+#[case("a - -1; + +a", "a - -1;\n++a;\n")]
+#[case("print max( 1,2 ) as float", "print max(1, 2) as float;\n")]
+#[case("a>1?'yes':`{ a }{{}}`", "a > 1 ? 'yes' : `{ a }{{}}`;\n")]
+#[case("yield ;yield", "yield;\nyield;\n")]
+#[case("a;\n\n\n\nb;\nc;", "a;\n\nb;\nc;\n")]
+fn canonical(#[case] script: &str, #[case] expected: &str) {
+	let formatted = Formatter::new().format(script).unwrap();
+	assert_eq!(formatted, expected);
+	assert_eq!(Formatter::new().format(&formatted).unwrap(), formatted);
+}
+
+#[rstest]
+#[case("// only a comment", "// only a comment\n")]
+#[case(
+	"// header\n\na:=1; // trailing\n/* before */ b:=2;",
+	"// header\n\na := 1; // trailing\n/* before */\nb := 2;\n"
+)]
+#[case("a:=1 /* inside */ + 2;", "a := 1 /* inside */ + 2;\n")]
+#[case("a := 1 + /* inside */ 2;", "a := 1 + /* inside */ 2;\n")]
+#[case("a := 1 // c\n+ 2;", "a := 1 // c\n\t+ 2;\n")]
+#[case("a := b /* b */ && c;", "a := b /* b */ && c;\n")]
+#[case("a := 1 /* v */ as int;", "a := 1 /* v */ as int;\n")]
+#[case("a := 1 as /* t */ int;", "a := 1 as /* t */ int;\n")]
+#[case("max(1 /* a */, 2)", "max(1 /* a */, 2);\n")]
+#[case("max(1, /* b */ 2)", "max(1, /* b */ 2);\n")]
+#[case("f(1, // first\n2)", "f(1, // first\n\t2);\n")]
+#[case("f(1 // first\n, 2)", "f(1 // first\n\t, 2);\n")]
+#[case("a; // a\nb; /* b */ // b\n// end\n", "a; // a\nb; /* b */ // b\n// end\n")]
+#[case("'// no comment'; `/* none */`", "'// no comment';\n`/* none */`;\n")]
+fn comments(#[case] script: &str, #[case] expected: &str) {
+	let formatted = Formatter::new().format(script).unwrap();
+	assert_eq!(formatted, expected);
+	assert_eq!(Formatter::new().format(&formatted).unwrap(), formatted);
+}
+
+#[rstest]
+#[case("a:=1;b:=a+2.5;print b*10;")]
+#[case("s:='abc';print len(s)>3?`{s}!`:substr(s,1);")]
+#[case("a:=true;b:=a&&!false||nil==nil;c:=0x0f&~3|1^2;-(c);")]
+#[case("x := 1;\nyield;\nx += 2 /* two */;\nx = x >= 1\n != x <= 2;")]
+#[case("a := 1 > 2\n ? 3 // three\n : 4;\nb := a == 3\n && true\n || false;\nprint `multi\nline {a}`;")]
+fn same_bytecode(#[case] script: &str) {
+	let mut runtime = Runtime::default();
+	let formatted = Formatter::new().format(script).unwrap();
+	let original = runtime.parse(script).unwrap();
+	let chunk = runtime.parse(&formatted).unwrap();
+	assert_eq!(chunk.code(), original.code());
+	assert_eq!(format!("{:?}", chunk.constants()), format!("{:?}", original.constants()));
+}
+
+#[test]
+fn errors() {
+	let formatter = Formatter::new();
+	assert!(matches!(
+		formatter.format("a := ;"),
+		Err(CompilationError::ExpressionExpected { .. })
+	));
+	assert!(matches!(
+		formatter.format("a := 1 /* open"),
+		Err(CompilationError::UnterminatedComment { .. })
+	));
+}
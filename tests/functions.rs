@@ -0,0 +1,97 @@
+//! Tests of native functions
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{
+	Error, Runtime, ScriptingValue,
+	compilation::CompilationError,
+	environment::DefaultEnvironment,
+	execution::{ExecutionError, ExecutionResult},
+};
+
+#[allow(clippy::unnecessary_wraps)]
+fn sum(args: &[ScriptingValue]) -> ExecutionResult<ScriptingValue> {
+	let mut res = 0;
+	for arg in args {
+		if let ScriptingValue::Int64(i) = arg {
+			res += i;
+		} else {
+			return Err(ExecutionError::NoInteger {
+				value: arg.to_string().into(),
+			});
+		}
+	}
+	Ok(ScriptingValue::Int64(res))
+}
+
+#[test]
+fn native_function() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime
+		.register_function("sum", 0..=4, sum)
+		.unwrap();
+
+	runtime
+		.run("print sum(); print sum(1); print sum(1, 2, 3 * 4, sum(1, 1));", &mut env)
+		.unwrap();
+	assert_eq!(runtime.stdout(), b"0\n1\n17\n");
+	assert!(matches!(
+		runtime.run("sum(1, 'a')", &mut env),
+		Err(Error::Execution {
+			source: ExecutionError::NoInteger { .. }
+		})
+	));
+}
+
+#[test]
+fn native_function_errors() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime
+		.register_function("sum", 0..=2, sum)
+		.unwrap();
+
+	assert!(matches!(
+		runtime.register_function("sum", 0..=2, sum),
+		Err(Error::DuplicateFunction { .. })
+	));
+	assert!(matches!(
+		runtime.run("unknown(1)", &mut env),
+		Err(Error::Compilation {
+			source: CompilationError::FunctionNotFound { .. }
+		})
+	));
+	assert!(matches!(
+		runtime.run("sum(1, 2, 3)", &mut env),
+		Err(Error::Compilation {
+			source: CompilationError::WrongArgumentCount { found: 3, .. }
+		})
+	));
+	assert!(runtime.run("sum(1, 2", &mut env).is_err());
+	assert!(runtime.run("sum(1 2)", &mut env).is_err());
+}
+
+#[test]
+fn constants() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.register_constant("ANSWER", 42).unwrap();
+
+	runtime
+		.run("print ANSWER; print ANSWER * 2;", &mut env)
+		.unwrap();
+	assert_eq!(runtime.stdout(), b"42\n84\n");
+	assert!(matches!(
+		runtime.register_constant("ANSWER", 43),
+		Err(Error::DuplicateConstant { .. })
+	));
+	assert!(matches!(
+		runtime.run("ANSWER = 43", &mut env),
+		Err(Error::Compilation {
+			source: CompilationError::ConstantAssignment { .. }
+		})
+	));
+}
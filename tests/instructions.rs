@@ -0,0 +1,39 @@
+//! Tests of the lowered instructions
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{Runtime, execution::Instruction};
+
+use rstest::rstest;
+
+#[rstest]
+#[case("print 1;", &[Instruction::Constant(0), Instruction::Print, Instruction::Return])]
+#[case("a := 1;", &[Instruction::Constant(0), Instruction::DefineExternal(1), Instruction::Return])]
+#[case("print a ? 1 : 2;", &[
+	Instruction::GetExternal(0),
+	Instruction::JmpIfFalse(5),
+	Instruction::Pop,
+	Instruction::Constant(1),
+	Instruction::Jmp(7),
+	Instruction::Pop,
+	Instruction::Constant(2),
+	Instruction::Print,
+	Instruction::Return,
+])]
+#[case("print int('3');", &[
+	Instruction::Constant(0),
+	Instruction::Call { function: 0, count: 1 },
+	Instruction::Print,
+	Instruction::Return,
+])]
+fn lowered(#[case] input: &str, #[case] expected: &[Instruction]) {
+	let mut runtime = Runtime::default();
+	let chunk = runtime.parse(input).unwrap();
+
+	assert_eq!(chunk.instructions().as_slice(), expected);
+	assert_eq!(chunk.instruction_lines().len(), expected.len());
+	let size: usize = expected.iter().map(|i| i.size()).sum();
+	assert_eq!(chunk.code().len(), size);
+}
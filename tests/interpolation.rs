@@ -0,0 +1,74 @@
+//! Tests of string interpolation
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{Environment, Runtime, environment::DefaultEnvironment};
+
+use rstest::rstest;
+
+#[rstest]
+#[case("print ``;", b"\n")]
+#[case("print `plain`;", b"plain\n")]
+#[case("print `{1}`;", b"1\n")]
+#[case("print `sum: {1 + 2}`;", b"sum: 3\n")]
+#[case("print `{1} and {2.5} and {true} and {nil}`;", b"1 and 2.5 and true and nil\n")]
+#[case("print `{'a' + 'b'}c`;", b"abc\n")]
+#[case("print `{{escaped}}`;", b"{escaped}\n")]
+#[case("print `{1 < 2 ? 'yes' : 'no'}!`;", b"yes!\n")]
+#[case("print `a` + `b`;", b"ab\n")]
+#[case("print `{1}{2}{3}{4}{5}{6}{7}{8}{9}`;", b"123456789\n")]
+#[case("print `{'}'}`;", b"}\n")]
+#[case("print `({'{' + '}'})`;", b"({})\n")]
+fn interpolation(#[case] input: &str, #[case] expected: &[u8]) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	runtime.run(input, &mut env).unwrap();
+	assert_eq!(runtime.stdout(), expected);
+}
+
+#[test]
+fn interpolation_with_variables() {
+	let mut env = DefaultEnvironment::default();
+	env.define_env("x", 3).unwrap();
+	env.define_env("y", -4.5).unwrap();
+	let mut runtime = Runtime::default();
+
+	runtime
+		.run("msg := `pos: {x}, {y}`; print msg", &mut env)
+		.unwrap();
+	assert_eq!(runtime.stdout(), b"pos: 3, -4.5\n");
+	assert_eq!(env.get_env("msg").unwrap().to_string(), "pos: 3, -4.5");
+}
+
+#[rstest]
+#[case("`{`")]
+#[case("`}`")]
+#[case("`{1 +}`")]
+#[case("`{1 2}`")]
+#[case("`{}`")]
+#[case("`{'}`")]
+#[case("`unterminated")]
+fn invalid_interpolation(#[case] input: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(runtime.run(input, &mut env).is_err());
+}
+
+#[test]
+fn interpolation_with_many_pieces() {
+	let mut env = DefaultEnvironment::default();
+	env.define_env("a", 1).unwrap();
+	let mut runtime = Runtime::default();
+
+	runtime
+		.run(
+			"print `{a}-{a}-{a}-{a}-{a}-{a}`; print 1 + len(`{a}-{a}-{a}-{a}-{a}`);",
+			&mut env,
+		)
+		.unwrap();
+	assert_eq!(runtime.stdout(), b"1-1-1-1-1-1\n10\n");
+}
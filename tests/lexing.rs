@@ -7,7 +7,7 @@
 use std::collections::BTreeMap;
 
 use rstest::rstest;
-use tinyscript::compilation::{Lexer, TokenKind};
+use tinyscript::compilation::{CompilationError, Lexer, TokenKind};
 
 #[rstest]
 #[case("=", TokenKind::Equal)]
@@ -22,6 +22,7 @@ use tinyscript::compilation::{Lexer, TokenKind};
 #[case("/", TokenKind::Slash)]
 #[case("/=", TokenKind::SlashEqual)]
 #[case(";", TokenKind::Semicolon)]
+#[case(",", TokenKind::Comma)]
 #[case("&", TokenKind::Ampersand)]
 #[case("|", TokenKind::Pipe)]
 #[case("^", TokenKind::Caret)]
@@ -38,6 +39,7 @@ use tinyscript::compilation::{Lexer, TokenKind};
 #[case("?", TokenKind::QMark)]
 #[case("(", TokenKind::LeftParen)]
 #[case(")", TokenKind::RightParen)]
+#[case("as", TokenKind::As)]
 #[case("nil", TokenKind::Nil)]
 #[case("true", TokenKind::True)]
 #[case("false", TokenKind::False)]
@@ -47,6 +49,7 @@ use tinyscript::compilation::{Lexer, TokenKind};
 #[case("0xff", TokenKind::HexNumber)]
 #[case("3", TokenKind::IntNumber)]
 #[case("'test'", TokenKind::String)]
+#[case("`test {a}`", TokenKind::Template)]
 #[case("RED", TokenKind::Enum)]
 fn lexing_token(#[case] input: &str, #[case] expected: TokenKind) {
 	let mut enums: BTreeMap<String, i8> = BTreeMap::default();
@@ -179,3 +182,49 @@ fn lexing_enums() {
 	assert!(lexer.next().is_none());
 	assert!(lexer.next().is_none());
 }
+
+#[test]
+fn lexing_comments() {
+	let tokens =
+		"// a line comment\ntrue /* a block comment */ false // another line comment\n/* a\nmulti line\ncomment */print";
+	let enums: BTreeMap<String, i8> = BTreeMap::default();
+	let mut lexer = Lexer::new(&enums, tokens);
+	assert_eq!(lexer.next().unwrap().unwrap().kind(), TokenKind::True);
+	assert_eq!(lexer.next().unwrap().unwrap().kind(), TokenKind::False);
+	assert_eq!(lexer.next().unwrap().unwrap().kind(), TokenKind::Print);
+	assert!(lexer.next().is_none());
+	assert!(lexer.next().is_none());
+}
+
+#[test]
+fn lexing_comments_line_count() {
+	let tokens = "/* a\nmulti line\ncomment */ // line comment\n #";
+	let enums: BTreeMap<String, i8> = BTreeMap::default();
+	let mut lexer = Lexer::new(&enums, tokens);
+	assert!(matches!(
+		lexer.next().unwrap(),
+		Err(CompilationError::UnexpectedChar { c: '#', pos: 4 })
+	));
+}
+
+#[test]
+fn lexing_operators_line_count() {
+	let tokens = "a +\n\nb &\nc : \n= d";
+	let enums: BTreeMap<String, i8> = BTreeMap::default();
+	let lines: Vec<_> = Lexer::new(&enums, tokens)
+		.map(|token| token.unwrap().line())
+		.collect();
+	assert_eq!(lines, vec![1, 1, 3, 3, 4, 4, 5]);
+}
+
+#[test]
+fn lexing_unterminated_comment() {
+	let tokens = "true\n/* a block comment */ /* unterminated\ncomment *";
+	let enums: BTreeMap<String, i8> = BTreeMap::default();
+	let mut lexer = Lexer::new(&enums, tokens);
+	assert_eq!(lexer.next().unwrap().unwrap().kind(), TokenKind::True);
+	assert!(matches!(
+		lexer.next().unwrap(),
+		Err(CompilationError::UnterminatedComment { pos: 2 })
+	));
+}
@@ -0,0 +1,92 @@
+//! Tests of the execution limits
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{
+	Error, Runtime,
+	environment::DefaultEnvironment,
+	execution::{ExecutionError, Limits},
+};
+
+use rstest::rstest;
+
+fn run(limits: Limits, script: &str) -> Result<(), Error> {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.set_limits(limits);
+	runtime.run(script, &mut env).map(|_| ())
+}
+
+#[rstest]
+#[case(Limits::default(), "a := 'abc' + 'def'; a = a + a; a = a + a;")]
+#[case(Limits::default().with_max_instructions(5), "a := 1; a = 2;")]
+#[case(Limits::default().with_max_string_length(6), "a := 'abc'; b := a + 'def';")]
+#[case(Limits::default().with_max_allocated_bytes(12), "a := 'abc'; b := a + 'def'; c := a + a;")]
+#[case(Limits::default().with_max_env_writes(2), "a := 1; a += 1; print a;")]
+fn within_limits(#[case] limits: Limits, #[case] script: &str) {
+	run(limits, script).unwrap();
+}
+
+#[test]
+fn instruction_limit() {
+	assert!(matches!(
+		run(Limits::default().with_max_instructions(5), "a := 1; a = 2; a = 3;"),
+		Err(Error::Execution {
+			source: ExecutionError::InstructionLimit { limit: 5 }
+		})
+	));
+}
+
+#[rstest]
+#[case("a := 'abc'; b := a + 'defg';")]
+#[case("a := 'abc'; b := `{a}{a}{a}`;")]
+#[case("a := 1234; b := str(a) + 'abc';")]
+#[case("a := replace('abc', 'b', 'bbbbb');")]
+#[case("a := format('{} {}', 'abc', 'def');")]
+#[case("a := to_upper('abcdefg');")]
+fn string_length_limit(#[case] script: &str) {
+	assert!(matches!(
+		run(Limits::default().with_max_string_length(6), script),
+		Err(Error::Execution {
+			source: ExecutionError::StringLengthLimit { limit: 6, .. }
+		})
+	));
+}
+
+#[test]
+fn allocation_limit() {
+	assert!(matches!(
+		run(
+			Limits::default().with_max_allocated_bytes(12),
+			"a := 'abc'; b := a + 'def'; c := b + a + 'x';"
+		),
+		Err(Error::Execution {
+			source: ExecutionError::AllocationLimit { limit: 12 }
+		})
+	));
+}
+
+#[test]
+fn env_write_limit() {
+	assert!(matches!(
+		run(Limits::default().with_max_env_writes(2), "a := 1; a += 1; a -= 1;"),
+		Err(Error::Execution {
+			source: ExecutionError::EnvWriteLimit { limit: 2 }
+		})
+	));
+}
+
+#[test]
+fn limits_apply_per_run() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.set_limits(Limits::default().with_max_env_writes(1));
+
+	runtime.run("a := 1;", &mut env).unwrap();
+	runtime.run("a = 2;", &mut env).unwrap();
+	runtime.run("a = 3;", &mut env).unwrap();
+	assert!(runtime.run("a = 4; a = 5;", &mut env).is_err());
+	assert_eq!(runtime.clone().limits(), runtime.limits());
+}
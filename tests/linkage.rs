@@ -0,0 +1,136 @@
+//! Tests of linking chunks to environments
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use std::collections::BTreeMap;
+
+use tinyscript::{
+	Error, Runtime, ScriptingValue,
+	environment::{self, DefaultEnvironment, EnvHandle, Environment},
+	execution::ExecutionError,
+};
+
+use rstest::rstest;
+
+/// Environment without support for handles, counting the accesses by name.
+#[derive(Default)]
+struct NamedEnvironment {
+	storage: BTreeMap<String, ScriptingValue>,
+	accesses: usize,
+}
+
+impl Environment for NamedEnvironment {
+	fn define_env(&mut self, key: &str, value: impl Into<ScriptingValue>) -> Result<(), environment::Error> {
+		self.accesses += 1;
+		self.storage.insert(key.into(), value.into());
+		Ok(())
+	}
+
+	fn get_env(&self, key: &str) -> Result<ScriptingValue, environment::Error> {
+		self.storage
+			.get(key)
+			.cloned()
+			.ok_or_else(|| environment::Error::EnvVarNotDefined { name: key.into() })
+	}
+
+	fn set_env(&mut self, key: &str, value: impl Into<ScriptingValue>) -> Result<(), environment::Error> {
+		self.accesses += 1;
+		self.storage.insert(key.into(), value.into());
+		Ok(())
+	}
+}
+
+#[rstest]
+#[case("a := 1; b := a + 2; print a + b;", b"4\n")]
+#[case("a := 'x'; a = a + a; print a;", b"xx\n")]
+#[case("a := 2; a *= 3; a -= 1; print a;", b"5\n")]
+#[case("a := true; print a ? `{a}!` : 'no';", b"true!\n")]
+fn linked_runs(#[case] input: &str, #[case] expected: &[u8]) {
+	let mut runtime = Runtime::default();
+	let mut env = DefaultEnvironment::default();
+	let chunk = runtime.parse(input).unwrap();
+	let linkage = chunk.link(&mut env);
+
+	for _ in 0..3 {
+		runtime.clear();
+		runtime
+			.execute_linked(&chunk, &linkage, &mut env)
+			.unwrap();
+		assert_eq!(runtime.stdout(), expected);
+	}
+}
+
+#[test]
+fn linked_and_named_access_agree() {
+	let mut runtime = Runtime::default();
+	let mut env = DefaultEnvironment::default();
+	env.define_env("external", 40).unwrap();
+	let chunk = runtime
+		.parse("result := external + 1; external = 0;")
+		.unwrap();
+	let linkage = chunk.link(&mut env);
+
+	runtime
+		.execute_linked(&chunk, &linkage, &mut env)
+		.unwrap();
+	assert_eq!(env.get_env("result").unwrap().to_string(), "41");
+	assert_eq!(env.get_env("external").unwrap().to_string(), "0");
+
+	env.set_env("external", 1).unwrap();
+	runtime.execute(&chunk, &mut env).unwrap();
+	assert_eq!(env.get_env("result").unwrap().to_string(), "2");
+}
+
+#[test]
+fn handles_are_stable() {
+	let mut env = DefaultEnvironment::default();
+	let a = env.resolve_env("a").unwrap();
+	let b = env.resolve_env("b").unwrap();
+	assert_ne!(a, b);
+	env.define_env("c", 3).unwrap();
+	env.define_env("a", 1).unwrap();
+	assert_eq!(env.resolve_env("a"), Some(a));
+	assert_eq!(env.get_env_handle(a).unwrap().to_string(), "1");
+	assert!(matches!(
+		env.get_env_handle(b),
+		Err(environment::Error::EnvVarNotDefined { .. })
+	));
+	assert!(matches!(
+		env.get_env_handle(EnvHandle::new(42)),
+		Err(environment::Error::EnvHandleInvalid { handle: 42 })
+	));
+}
+
+#[test]
+fn undefined_variables() {
+	let mut runtime = Runtime::default();
+	let mut env = DefaultEnvironment::default();
+	let chunk = runtime.parse("a = 1;").unwrap();
+	let linkage = chunk.link(&mut env);
+
+	assert!(matches!(
+		runtime.execute_linked(&chunk, &linkage, &mut env),
+		Err(Error::Execution {
+			source: ExecutionError::Environment { .. }
+		})
+	));
+	// resolving a name does not define the variable
+	assert!(env.get_env("a").is_err());
+	assert_eq!(env.iter().count(), 0);
+}
+
+#[test]
+fn environment_without_handles() {
+	let mut runtime = Runtime::default();
+	let mut env = NamedEnvironment::default();
+	let chunk = runtime.parse("a := 1; a = a + 1;").unwrap();
+	let linkage = chunk.link(&mut env);
+
+	runtime
+		.execute_linked(&chunk, &linkage, &mut env)
+		.unwrap();
+	assert_eq!(env.get_env("a").unwrap().to_string(), "2");
+	assert_eq!(env.accesses, 2);
+}
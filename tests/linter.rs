@@ -0,0 +1,118 @@
+//! Tests of the linter
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use rstest::rstest;
+use tinyscript::{
+	Runtime,
+	compilation::{CompilationError, Linter, WarningKind, ast::BinaryOp},
+};
+
+fn kinds(script: &str) -> Vec<WarningKind> {
+	let mut linter = Linter::new();
+	linter.register_enum("Red");
+	linter.register_variable("host");
+	linter
+		.lint(script)
+		.unwrap()
+		.into_iter()
+		.map(|warning| warning.kind)
+		.collect()
+}
+
+#[rstest]
+#[case("a := 1; print a; host = a; host += 1;")]
+#[case("a := 1.5; print a > 1.0 ? `{a}` : 'small';")]
+#[case("a := 1; b := a == 1; c := 'x' != 'y'; print b && c;")]
+#[case("a := 1.5; a = 2; print a == 2;")]
+#[case("c := true; print c ? 1 : 2; print Red;")]
+fn clean(#[case] script: &str) {
+	assert_eq!(kinds(script), vec![]);
+}
+
+#[rstest]
+#[case("x = 1; x := 2; print x;", "x")]
+#[case("x += 1;", "x")]
+#[case("print 1; y := 2; y = 3; z /= y;", "z")]
+fn undefined_assignment(#[case] script: &str, #[case] name: &str) {
+	assert_eq!(kinds(script)[0], WarningKind::UndefinedAssignment { name: name.into() });
+}
+
+#[rstest]
+#[case("a := 1; print a == 1.5;", BinaryOp::Equal)]
+#[case("a := 1.5; print a != 2;", BinaryOp::NotEqual)]
+#[case("a := 1; a *= 0.5; print a == 1;", BinaryOp::Equal)]
+#[case("a := 1; print (a as float) == 1;", BinaryOp::Equal)]
+#[case("a := 1; print -float(a) + 1 == a;", BinaryOp::Equal)]
+fn float_equality(#[case] script: &str, #[case] op: BinaryOp) {
+	assert_eq!(kinds(script), vec![WarningKind::FloatEquality { op }]);
+}
+
+#[rstest]
+#[case("true ? 1 : 2;", true)]
+#[case("!(false || false) ? 1 : 2;", true)]
+#[case("false && 1 > 2 ? 1 : 2;", false)]
+#[case("1 >= 2 ? 1 : 2;", false)]
+#[case("'a' == 'a' ? 1 : 2;", true)]
+fn constant_condition(#[case] script: &str, #[case] value: bool) {
+	assert_eq!(kinds(script), vec![WarningKind::ConstantCondition { value }]);
+}
+
+#[test]
+fn unused_definition() {
+	assert_eq!(
+		kinds("a := 1; b := 2; b := 3; print b; c := `{b}`;"),
+		vec![
+			WarningKind::UnusedDefinition { name: "a".into() },
+			WarningKind::UnusedDefinition { name: "b".into() },
+			WarningKind::UnusedDefinition { name: "c".into() },
+		]
+	);
+}
+
+#[test]
+fn shadowed_enum() {
+	assert_eq!(
+		kinds("Red := 1; print Red; Red = 2;"),
+		vec![
+			WarningKind::ShadowedEnum { name: "Red".into() },
+			WarningKind::ShadowedEnum { name: "Red".into() },
+		]
+	);
+}
+
+#[test]
+fn spans_and_messages() {
+	let warnings = Linter::new()
+		.lint("a := 1;\nprint a == 0.1 ? 1 : 2;\nb = a;")
+		.unwrap();
+	assert_eq!(warnings.len(), 2);
+	assert_eq!((warnings[0].span.start, warnings[0].span.end), (14, 22));
+	assert_eq!(warnings[0].span.line, 2);
+	assert_eq!(
+		warnings[0].to_string(),
+		"the floats compared with == at line 2 are equal within a difference of 2e-15"
+	);
+	assert_eq!(warnings[1].span.line, 3);
+	assert_eq!(
+		warnings[1].to_string(),
+		"the variable b at line 3 is assigned before it is defined"
+	);
+}
+
+#[test]
+fn runtime() {
+	let mut runtime = Runtime::default();
+	runtime.register_enum_tuple("Green", 1).unwrap();
+	let warnings = runtime.lint("Green := 2; print Green;").unwrap();
+	assert_eq!(warnings[0].kind, WarningKind::ShadowedEnum { name: "Green".into() });
+	// the script itself does not compile
+	assert!(runtime.parse("Green := 2; print Green;").is_err());
+
+	assert!(matches!(
+		Linter::new().lint("a := ;"),
+		Err(CompilationError::ExpressionExpected { .. })
+	));
+}
@@ -0,0 +1,253 @@
+//! Tests of the language server binary
+// Copyright © 2026 Stephan Kunz
+
+#![cfg(feature = "lsp")]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use std::{
+	io::{BufRead, BufReader, Read, Write},
+	process::{Child, ChildStdout, Command, Stdio},
+};
+
+use rstest::rstest;
+use serde_json::{Value, json};
+
+const URI: &str = "file:///script.ts";
+
+/// A running language server.
+struct Server {
+	child: Child,
+	output: BufReader<ChildStdout>,
+	id: u64,
+}
+
+impl Server {
+	fn start() -> Self {
+		let mut child = Command::new(env!("CARGO_BIN_EXE_tinyscript-lsp"))
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.spawn()
+			.unwrap();
+		let output = BufReader::new(child.stdout.take().unwrap());
+		let mut server = Self { child, output, id: 0 };
+		let options = json!({
+			"enums": { "Red": 1, "Green": 2 },
+			"variables": { "speed": "float", "name": "string" },
+			"functions": { "sensor": "float" }
+		});
+		let result = server.request("initialize", &json!({ "capabilities": {}, "initializationOptions": options }));
+		assert!(result["capabilities"]["semanticTokensProvider"].is_object());
+		server.notify("initialized", &json!({}));
+		server
+	}
+
+	fn send(&mut self, message: &Value) {
+		let content = message.to_string();
+		let input = self.child.stdin.as_mut().unwrap();
+		write!(input, "Content-Length: {}\r\n\r\n{content}", content.len()).unwrap();
+		input.flush().unwrap();
+	}
+
+	fn receive(&mut self) -> Value {
+		let mut length = 0;
+		loop {
+			let mut header = String::new();
+			self.output.read_line(&mut header).unwrap();
+			let header = header.trim_end();
+			if header.is_empty() {
+				break;
+			}
+			if let Some(value) = header.strip_prefix("Content-Length: ") {
+				length = value.parse().unwrap();
+			}
+		}
+		let mut content = vec![0; length];
+		self.output.read_exact(&mut content).unwrap();
+		serde_json::from_slice(&content).unwrap()
+	}
+
+	fn notify(&mut self, method: &str, params: &Value) {
+		self.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+	}
+
+	fn request(&mut self, method: &str, params: &Value) -> Value {
+		self.id += 1;
+		let id = self.id;
+		self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+		let response = self.receive();
+		assert_eq!(response["id"], id);
+		response["result"].clone()
+	}
+
+	/// Open the document and get its diagnostics.
+	fn open(&mut self, text: &str) -> Vec<Value> {
+		self.notify(
+			"textDocument/didOpen",
+			&json!({ "textDocument": { "uri": URI, "languageId": "tinyscript", "version": 1, "text": text } }),
+		);
+		self.diagnostics()
+	}
+
+	fn diagnostics(&mut self) -> Vec<Value> {
+		let notification = self.receive();
+		assert_eq!(notification["method"], "textDocument/publishDiagnostics");
+		notification["params"]["diagnostics"]
+			.as_array()
+			.unwrap()
+			.clone()
+	}
+
+	fn position(line: u32, character: u32) -> Value {
+		json!({ "textDocument": { "uri": URI }, "position": { "line": line, "character": character } })
+	}
+
+	fn stop(mut self) {
+		assert_eq!(self.request("shutdown", &Value::Null), Value::Null);
+		self.notify("exit", &Value::Null);
+		assert!(self.child.wait().unwrap().success());
+	}
+}
+
+#[rstest]
+#[case("speed = speed * 2;\nname = `{speed} km/h`;", vec![])]
+#[case("x := ;", vec![(0, 1, "expression expected at line 1, got ;")])]
+#[case(
+	"speed = -name;\nprint unknown(Red);",
+	vec![
+		(1, 1, "the function unknown at line 2 is not defined"),
+		(0, 1, "invalid operation -string at line 1: expected numerical value, got s"),
+	]
+)]
+#[case("print Blue;", vec![(0, 1, "the variable Blue at line 1 is not defined")])]
+#[case(
+	"name = name - 'a';",
+	vec![(0, 1, "invalid operation string - string at line 1: to Strings you can only 'ADD' something")]
+)]
+#[case("x := 1.5;\nprint x == 1.5;", vec![(1, 2, "the floats compared with == at line 2 are equal within a difference of 2e-15")])]
+fn diagnostics(#[case] text: &str, #[case] expected: Vec<(u64, u64, &str)>) {
+	let mut server = Server::start();
+	let found: Vec<_> = server
+		.open(text)
+		.iter()
+		.map(|diagnostic| {
+			(
+				diagnostic["range"]["start"]["line"]
+					.as_u64()
+					.unwrap(),
+				diagnostic["severity"].as_u64().unwrap(),
+				diagnostic["message"].as_str().unwrap().to_owned(),
+			)
+		})
+		.collect();
+	let expected: Vec<_> = expected
+		.into_iter()
+		.map(|(line, severity, message)| (line, severity, message.to_owned()))
+		.collect();
+	assert_eq!(found, expected);
+	server.stop();
+}
+
+#[test]
+fn changes() {
+	let mut server = Server::start();
+	assert_eq!(server.open("speed = 1.0;").len(), 0);
+	server.notify(
+		"textDocument/didChange",
+		&json!({ "textDocument": { "uri": URI, "version": 2 }, "contentChanges": [{ "text": "speed = ;" }] }),
+	);
+	assert_eq!(server.diagnostics().len(), 1);
+	server.notify("textDocument/didClose", &json!({ "textDocument": { "uri": URI } }));
+	assert_eq!(server.diagnostics().len(), 0);
+	server.stop();
+}
+
+#[test]
+fn malformed_notifications() {
+	let mut server = Server::start();
+	server.notify("textDocument/didOpen", &json!({ "textDocument": { "uri": URI } }));
+	server.notify("textDocument/didChange", &json!({ "contentChanges": 1 }));
+	assert_eq!(server.open("speed = ;").len(), 1);
+	server.stop();
+}
+
+#[rstest]
+#[case(0, 1, "`speed`: float environment variable")]
+#[case(0, 7, "`x`: int script variable")]
+#[case(0, 14, "`Green`: enum value 2")]
+#[case(1, 8, "`sensor()`: function returning float")]
+#[case(1, 18, "`int()`: function")]
+fn hover(#[case] line: u32, #[case] character: u32, #[case] expected: &str) {
+	let mut server = Server::start();
+	server.open("speed; x := Green;\nprint sensor() + int(x);");
+	let result = server.request("textDocument/hover", &Server::position(line, character));
+	assert_eq!(result["contents"]["value"], expected);
+	server.stop();
+}
+
+#[test]
+fn no_hover() {
+	let mut server = Server::start();
+	server.open("print 'speed';");
+	let result = server.request("textDocument/hover", &Server::position(0, 8));
+	assert_eq!(result, Value::Null);
+	server.stop();
+}
+
+#[test]
+fn completion() {
+	let mut server = Server::start();
+	server.open("count := 1;\n");
+	let result = server.request("textDocument/completion", &Server::position(1, 0));
+	let items: Vec<_> = result
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|item| (item["label"].as_str().unwrap(), item["detail"].as_str().unwrap()))
+		.collect();
+	for expected in [
+		("Green", "enum value 2"),
+		("Red", "enum value 1"),
+		("count", "int script variable"),
+		("name", "string environment variable"),
+		("speed", "float environment variable"),
+		("sensor", "function returning float"),
+		("int", "function"),
+	] {
+		assert!(items.contains(&expected), "{expected:?} missing");
+	}
+	server.stop();
+}
+
+#[test]
+fn semantic_tokens() {
+	let mut server = Server::start();
+	server.open("x := Red;\nprint `a\nb` + len('c');");
+	let result = server.request("textDocument/semanticTokens/full", &json!({ "textDocument": { "uri": URI } }));
+	let data: Vec<u64> = result["data"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|value| value.as_u64().unwrap())
+		.collect();
+	// delta line, delta start, length, type
+	let tokens: Vec<_> = data
+		.chunks(5)
+		.map(|token| (token[0], token[1], token[2], token[3]))
+		.collect();
+	assert_eq!(
+		tokens,
+		vec![
+			(0, 0, 1, 1), // x
+			(0, 2, 2, 6), // :=
+			(0, 3, 3, 3), // Red
+			(1, 0, 5, 0), // print
+			(0, 6, 2, 5), // `a
+			(1, 0, 2, 5), // b`
+			(0, 3, 1, 6), // +
+			(0, 2, 3, 2), // len
+			(0, 4, 3, 5), // 'c'
+		]
+	);
+	server.stop();
+}
@@ -0,0 +1,60 @@
+//! Tests of the math library
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+#![cfg(feature = "math")]
+
+use tinyscript::{Runtime, environment::DefaultEnvironment};
+
+use rstest::rstest;
+
+#[rstest]
+#[case("print abs(-3);", b"3\n")]
+#[case("print abs(-3.5);", b"3.5\n")]
+#[case("print min(3, 2);", b"2\n")]
+#[case("print min(3, 2.5, 4);", b"2.5\n")]
+#[case("print max(3, -2);", b"3\n")]
+#[case("print max(3, 4.5);", b"4.5\n")]
+#[case("print clamp(5, 0, 3);", b"3\n")]
+#[case("print clamp(-5, 0, 3);", b"0\n")]
+#[case("print clamp(1.5, 0, 3);", b"1.5\n")]
+#[case("print floor(2.7);", b"2\n")]
+#[case("print floor(-2.2);", b"-3\n")]
+#[case("print floor(2);", b"2\n")]
+#[case("print ceil(2.2);", b"3\n")]
+#[case("print round(2.5);", b"3\n")]
+#[case("print round(-2.5);", b"-3\n")]
+#[case("print sqrt(16);", b"4\n")]
+#[case("print sin(0);", b"0\n")]
+#[case("print cos(0.0);", b"1\n")]
+#[case("print atan2(1, 1) * 4 == PI;", b"true\n")]
+#[case("print pow(2, 10);", b"1024\n")]
+#[case("print pow(2, -1);", b"0.5\n")]
+#[case("print pow(4, 0.5);", b"2\n")]
+#[case("print PI;", b"3.141592653589793\n")]
+#[case("print TAU / PI;", b"2\n")]
+fn math_functions(#[case] input: &str, #[case] expected: &[u8]) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	runtime.run(input, &mut env).unwrap();
+	assert_eq!(runtime.stdout(), expected);
+}
+
+#[rstest]
+#[case("abs('a');")]
+#[case("abs(true);")]
+#[case("abs(nil);")]
+#[case("abs(-9223372036854775807 - 1);")]
+#[case("clamp(1, 3, 0);")]
+#[case("pow(10, 100);")]
+#[case("PI := 3;")]
+#[case("PI = 3;")]
+#[case("PI += 3;")]
+fn math_function_errors(#[case] input: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(runtime.run(input, &mut env).is_err());
+}
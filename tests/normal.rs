@@ -4,7 +4,7 @@
 
 use tinyscript::{
 	Runtime,
-	compilation::{Lexer, Parser},
+	compilation::{Compiler, Expr, Lexer, Parser, Stmt},
 	execution::VM,
 };
 
@@ -13,8 +13,11 @@ const fn is_normal<T: Sized + Send + Sync>() {}
 
 #[test]
 const fn normal_types() {
+	is_normal::<Compiler>();
+	is_normal::<Expr>();
 	is_normal::<Lexer>();
 	is_normal::<Parser>();
 	is_normal::<Runtime>();
+	is_normal::<Stmt>();
 	is_normal::<VM>();
 }
@@ -0,0 +1,109 @@
+//! Tests of the bytecode optimizer
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{Error, Runtime, environment::DefaultEnvironment, execution::ExecutionError, execution::op_code::OpCode};
+
+use rstest::rstest;
+
+#[rstest]
+#[case("2 * 2 == 4;", &[OpCode::True as u8, OpCode::Return as u8])]
+#[case("1 + 2;", &[OpCode::Constant as u8, 2, OpCode::Return as u8])]
+#[case("!true;", &[OpCode::False as u8, OpCode::Return as u8])]
+#[case("true ? 1 : 2;", &[OpCode::Constant as u8, 0, OpCode::Return as u8])]
+#[case("print 2 * 2 == 4;", &[OpCode::True as u8, OpCode::Print as u8, OpCode::Return as u8])]
+#[case("print !(1 < 2);", &[OpCode::False as u8, OpCode::Print as u8, OpCode::Return as u8])]
+#[case("print -1 == 1 - 2;", &[OpCode::True as u8, OpCode::Print as u8, OpCode::Return as u8])]
+#[case("print 'a' + 1 == 'a1';", &[OpCode::True as u8, OpCode::Print as u8, OpCode::Return as u8])]
+#[case("print `{1 + 1} {true}` == '2 true';", &[OpCode::True as u8, OpCode::Print as u8, OpCode::Return as u8])]
+#[case("print false && true;", &[OpCode::False as u8, OpCode::Print as u8, OpCode::Return as u8])]
+#[case("print true || false;", &[OpCode::True as u8, OpCode::Print as u8, OpCode::Return as u8])]
+#[case("print false ? 1 : nil;", &[OpCode::Nil as u8, OpCode::Print as u8, OpCode::Return as u8])]
+fn folded(#[case] input: &str, #[case] expected: &[u8]) {
+	let mut runtime = Runtime::default();
+
+	let chunk = runtime.parse(input).unwrap();
+	assert_eq!(chunk.code().as_slice(), expected);
+}
+
+#[rstest]
+#[case("print 3 * 4 - 2;", b"10\n")]
+#[case("print 7 / 2 + 0.5;", b"3.5\n")]
+#[case("print 0x0F & 0x3C | 0x40;", b"76\n")]
+#[case("print ~0 ^ 1;", b"-2\n")]
+#[case("print 1 + 2 * 3 > 6 && 2 < 3;", b"true\n")]
+#[case("print true ? 'yes' : 'no';", b"yes\n")]
+#[case("print false ? 'yes' : 'no';", b"no\n")]
+#[case("print true ? (false ? 1 : 2) : 3;", b"2\n")]
+#[case("print `{1 + 2}{'x'}{nil}`;", b"3xnil\n")]
+#[case("a := 1; print a + 1 * 2;", b"3\n")]
+#[case("a := true; print a && 1 < 2;", b"true\n")]
+#[case("a := false; print a || 2 > 1;", b"true\n")]
+#[case("a := 2; print a > 1 ? a * 3 : 0;", b"6\n")]
+#[case("a := 0; a += 2 * 3; print a;", b"6\n")]
+fn same_results(#[case] input: &str, #[case] expected: &[u8]) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.run(input, &mut env).unwrap();
+	assert_eq!(runtime.stdout(), expected);
+
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.set_optimization(false);
+	runtime.run(input, &mut env).unwrap();
+	assert_eq!(runtime.stdout(), expected);
+}
+
+#[test]
+fn optimization_is_optional() {
+	let mut runtime = Runtime::default();
+	let optimized = runtime.parse("print 1 + 2;").unwrap();
+	runtime.set_optimization(false);
+	let unoptimized = runtime.parse("print 1 + 2;").unwrap();
+
+	assert!(optimized.code().len() < unoptimized.code().len());
+	assert_eq!(unoptimized.code().last(), Some(&(OpCode::Return as u8)));
+}
+
+#[rstest]
+#[case("print 1 / 0;")]
+#[case("print 1 + 2 / (3 - 3);")]
+fn division_by_zero_is_kept(#[case] input: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(matches!(
+		runtime.run(input, &mut env),
+		Err(Error::Execution {
+			source: ExecutionError::DivisionByZero
+		})
+	));
+}
+
+#[rstest]
+#[case("print 0x7FFFFFFFFFFFFFFF + 1;")]
+#[case("print -0x7FFFFFFFFFFFFFFF - 2;")]
+fn overflow_is_kept(#[case] input: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(matches!(
+		runtime.run(input, &mut env),
+		Err(Error::Execution {
+			source: ExecutionError::Overflow
+		})
+	));
+}
+
+#[rstest]
+#[case("print true + 1;")]
+#[case("print -'a';")]
+#[case("print 1 ? 2 : 3;")]
+fn invalid_operations_fail_at_runtime(#[case] input: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(matches!(runtime.run(input, &mut env), Err(Error::Execution { .. })));
+}
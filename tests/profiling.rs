@@ -0,0 +1,110 @@
+//! Tests of tracing and profiling
+// Copyright © 2026 Stephan Kunz
+
+#![cfg(feature = "profiling")]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{
+	Runtime,
+	environment::DefaultEnvironment,
+	execution::{Instruction, Profile, op_code::OpCode},
+};
+
+const SCRIPT: &str = "a := 1;\nb := a + 2;\nb * 10;";
+
+#[test]
+fn tracing() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	runtime.run(SCRIPT, &mut env).unwrap();
+	assert!(runtime.trace().is_empty());
+
+	runtime.set_tracing(true);
+	runtime.run(SCRIPT, &mut env).unwrap();
+	let chunk = runtime.compile(SCRIPT).unwrap();
+	let trace = runtime.trace();
+	assert_eq!(trace.len(), chunk.instructions().len());
+	assert_eq!(trace[0].instruction, chunk.instructions()[0]);
+	assert_eq!(trace[0].line, 1);
+	assert!(trace[0].stack.is_empty());
+	assert_eq!(trace.last().unwrap().instruction, Instruction::Return);
+	assert_eq!(trace.last().unwrap().line, 3);
+	assert_eq!(trace.last().unwrap().stack[0].to_string(), "30");
+	assert!(
+		trace[1]
+			.to_string()
+			.starts_with("0001    1 DefineExternal")
+	);
+
+	// the trace is kept for the last run only
+	runtime.run("1;", &mut env).unwrap();
+	assert_eq!(runtime.trace().len(), 2);
+}
+
+#[test]
+fn profiling() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.set_profiling(true);
+
+	runtime.run(SCRIPT, &mut env).unwrap();
+	runtime.run(SCRIPT, &mut env).unwrap();
+	let chunk = runtime.compile(SCRIPT).unwrap();
+	let profile = runtime.profile();
+	assert_eq!(profile.total().count, 2 * chunk.instructions().len() as u64);
+	assert_eq!(
+		profile
+			.op_codes()
+			.find(|(op_code, _)| *op_code == OpCode::Return)
+			.unwrap()
+			.1
+			.count,
+		2
+	);
+	let lines: Vec<usize> = profile
+		.chunk_lines(&chunk)
+		.map(|(line, _)| line)
+		.collect();
+	assert_eq!(lines, [1, 2, 3]);
+	assert!(profile.to_string().contains("Return"));
+
+	runtime.clear_profile();
+	assert_eq!(runtime.profile().total().count, 0);
+	runtime.set_profiling(false);
+	runtime.run(SCRIPT, &mut env).unwrap();
+	assert_eq!(runtime.profile().total().count, 0);
+}
+
+#[test]
+fn profiling_per_script() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	runtime.set_profiling(true);
+
+	let other = "x := 1;\nx + 1;";
+	runtime.run(SCRIPT, &mut env).unwrap();
+	runtime.run(other, &mut env).unwrap();
+	runtime.run(other, &mut env).unwrap();
+	let chunk = runtime.compile(SCRIPT).unwrap();
+	let other_chunk = runtime.compile(other).unwrap();
+	let profile = runtime.profile();
+
+	// the same lines of different scripts are kept apart
+	let counts = |chunk| -> Vec<(usize, u64)> {
+		profile
+			.chunk_lines(chunk)
+			.map(|(line, statistics)| (line, statistics.count))
+			.collect()
+	};
+	assert_eq!(counts(&chunk), [(1, 2), (2, 4), (3, 4)]);
+	assert_eq!(counts(&other_chunk), [(1, 4), (2, 8)]);
+	assert_eq!(profile.lines().count(), 5);
+	assert_ne!(Profile::chunk_id(&chunk), Profile::chunk_id(&other_chunk));
+	assert!(
+		profile
+			.to_string()
+			.contains(&format!("{:016x}     2", Profile::chunk_id(&other_chunk)))
+	);
+}
@@ -0,0 +1,83 @@
+//! Tests of the `REPL` binary
+// Copyright © 2026 Stephan Kunz
+
+#![cfg(feature = "repl")]
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use std::{
+	io::Write,
+	process::{Command, Stdio},
+};
+
+use rstest::rstest;
+
+/// Run a session with the input, returns the output without the greeting.
+fn session(input: &str) -> String {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_tinyscript-repl"))
+		// keep the history away from the home of the user
+		.env("HOME", env!("CARGO_TARGET_TMPDIR"))
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.spawn()
+		.unwrap();
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(input.as_bytes())
+		.unwrap();
+	let output = child.wait_with_output().unwrap();
+	assert!(output.status.success());
+	let output = String::from_utf8(output.stdout).unwrap();
+	let (greeting, rest) = output.split_once('\n').unwrap();
+	assert!(greeting.contains(":help"));
+	rest.to_owned()
+}
+
+#[rstest]
+#[case("1 + 2\n", "3\nbye\n")]
+#[case("x := 1;\nprint x;\nx\n:quit\n", "1\n1\n")]
+#[case("x := 1 +\n2 *\n3;\nx\n", "7\nbye\n")]
+#[case("x := (1\n\n", "compilation error: expected token ), found Token NONE at line 1\nbye\n")]
+#[case("print `a\nb`;\n", "a\nb\nbye\n")]
+#[case("/* a\ncomment */ 'c'\n", "c\nbye\n")]
+#[case(
+	"x\n",
+	"execution error: environment error: the environment variable x has not been defined\nbye\n"
+)]
+fn statements(#[case] input: &str, #[case] expected: &str) {
+	assert_eq!(session(input), expected);
+}
+
+#[rstest]
+#[case("x := 1; y := 'a';\n:env\n", "x = 1\ny = a\nbye\n")]
+#[case(":dis\n", "no script compiled yet\nbye\n")]
+#[case(
+	"print 1;\n:dis\n",
+	"1\n0000    1 OP_CONSTANT          0 1\n0002    | OP_PRINT\n0003    | OP_RETURN\nbye\n"
+)]
+#[case(":enum Red=1 Green=2\nGreen - Red\n", "1\nbye\n")]
+#[case(":enum Red\n", "invalid enum value: Red, expected <Name=value>\nbye\n")]
+#[case(":load tests/none.ts\n", "tests/none.ts: No such file or directory (os error 2)\nbye\n")]
+#[case(
+	":enum Red=1\nx := Red;\n:reset\n:env\nRed\n",
+	"execution error: environment error: the environment variable Red has not been defined\nbye\n"
+)]
+#[case(
+	":debug x := 1;\n:step\n:step\n:continue\n",
+	"paused at line 1 ip 0 stack []\npaused at line 1 ip 1 stack [1]\npaused at line 1 ip 2 stack []\nfinished with: nil\nbye\n"
+)]
+fn commands(#[case] input: &str, #[case] expected: &str) {
+	assert_eq!(session(input), expected);
+}
+
+#[test]
+fn help() {
+	let output = session(":help\n");
+	for command in [
+		":env", ":dis", ":enum", ":load", ":reset", ":quit", ":debug",
+	] {
+		assert!(output.contains(command), "{command} missing");
+	}
+}
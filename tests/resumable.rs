@@ -0,0 +1,98 @@
+//! Tests of resumable execution with `yield`
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use tinyscript::{
+	Error, Runtime,
+	environment::DefaultEnvironment,
+	execution::{Execution, ExecutionError},
+};
+
+use rstest::rstest;
+
+/// Runs a script tick by tick, returning the number of ticks and the result.
+fn ticks(runtime: &mut Runtime, script: &str, env: &mut DefaultEnvironment) -> (usize, String) {
+	let chunk = runtime.parse(script).unwrap();
+	let mut state = runtime.start(&chunk, env).unwrap();
+	let mut count = 1;
+	loop {
+		match state {
+			Execution::Completed(value) => return (count, value.to_string()),
+			Execution::Suspended(suspended) => {
+				state = runtime.resume(&chunk, suspended, env).unwrap();
+				count += 1;
+			}
+		}
+	}
+}
+
+#[rstest]
+#[case("1 + 2;", 1, "3")]
+#[case("yield; 1 + 2;", 2, "3")]
+#[case("a := 1; yield; a += 1; yield; a * 10;", 3, "20")]
+#[case("a := true; a ? 1 : 2; yield;", 2, "1")]
+#[case("2 * 3; yield; yield", 3, "6")]
+fn yields_suspend(#[case] input: &str, #[case] expected_ticks: usize, #[case] expected: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert_eq!(ticks(&mut runtime, input, &mut env), (expected_ticks, expected.into()));
+}
+
+#[test]
+fn stack_survives_suspension() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	let chunk = runtime
+		.parse("'tick'; yield; print 'tock';")
+		.unwrap();
+
+	let suspended = runtime
+		.start(&chunk, &mut env)
+		.unwrap()
+		.into_suspended()
+		.unwrap();
+	assert_eq!(suspended.stack().len(), 1);
+	assert_eq!(suspended.stack()[0].to_string(), "tick");
+
+	// other scripts may run in between
+	runtime.run("print 'other';", &mut env).unwrap();
+	let state = runtime
+		.resume(&chunk, suspended, &mut env)
+		.unwrap();
+	assert!(state.is_completed());
+	assert_eq!(runtime.stdout(), b"other\ntock\n");
+}
+
+#[test]
+fn run_ignores_yield() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	runtime
+		.run("print 1; yield; print 2;", &mut env)
+		.unwrap();
+	assert_eq!(runtime.stdout(), b"1\n2\n");
+}
+
+#[test]
+fn resume_with_other_chunk_fails() {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+	let chunk = runtime.parse("yield; 1;").unwrap();
+	let other = runtime.parse("1; 2; 3;").unwrap();
+
+	let suspended = runtime
+		.start(&chunk, &mut env)
+		.unwrap()
+		.into_suspended()
+		.unwrap();
+	assert!(matches!(
+		runtime.resume(&other, suspended, &mut env),
+		Err(Error::Execution {
+			source: ExecutionError::InvalidResume
+		})
+	));
+}
@@ -0,0 +1,55 @@
+//! Tests of the string library
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+#![cfg(feature = "string")]
+
+use tinyscript::{Runtime, environment::DefaultEnvironment};
+
+use rstest::rstest;
+
+#[rstest]
+#[case("print len('');", b"0\n")]
+#[case("print len('hello');", b"5\n")]
+#[case("print len('äöü');", b"3\n")]
+#[case("print substr('hello world', 6);", b"world\n")]
+#[case("print substr('hello world', 0, 5);", b"hello\n")]
+#[case("print substr('hello', 3, 10);", b"lo\n")]
+#[case("print substr('hello', 10);", b"\n")]
+#[case("print contains('hello world', 'o w');", b"true\n")]
+#[case("print contains('hello world', 'x');", b"false\n")]
+#[case("print starts_with('hello', 'he');", b"true\n")]
+#[case("print starts_with('hello', 'lo');", b"false\n")]
+#[case("print ends_with('hello', 'lo');", b"true\n")]
+#[case("print to_upper('Hello');", b"HELLO\n")]
+#[case("print to_lower('Hello');", b"hello\n")]
+#[case("print split('a,b,c', ',', 1);", b"b\n")]
+#[case("print split('a,b,c', ',', 3);", b"nil\n")]
+#[case("print trim('  hello \t');", b"hello\n")]
+#[case("print replace('a-b-c', '-', '+');", b"a+b+c\n")]
+#[case("print format('plain');", b"plain\n")]
+#[case("print format('{} + {} = {}', 1, 2.5, 1 + 2.5);", b"1 + 2.5 = 3.5\n")]
+#[case("print format('{{{}}}', true);", b"{true}\n")]
+#[case("print len(to_upper('abc') + 'd') * 2;", b"8\n")]
+fn string_functions(#[case] input: &str, #[case] expected: &[u8]) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	runtime.run(input, &mut env).unwrap();
+	assert_eq!(runtime.stdout(), expected);
+}
+
+#[rstest]
+#[case("len(1);")]
+#[case("substr('hello', -1);")]
+#[case("split('a,b', ',', 'x');")]
+#[case("format('{}');")]
+#[case("format('{}', 1, 2);")]
+#[case("format('{', 1);")]
+fn string_function_errors(#[case] input: &str) {
+	let mut env = DefaultEnvironment::default();
+	let mut runtime = Runtime::default();
+
+	assert!(runtime.run(input, &mut env).is_err());
+}
@@ -0,0 +1,174 @@
+//! Tests of the type checker
+// Copyright © 2026 Stephan Kunz
+
+#![allow(missing_docs)]
+#![allow(clippy::unwrap_used)]
+
+use rstest::rstest;
+use tinyscript::{
+	Environment, Error, Library, Runtime,
+	compilation::{CompilationError, Schema, Type, TypeChecker, TypeErrorKind},
+	environment::DefaultEnvironment,
+};
+
+fn schema() -> Schema {
+	let mut schema = Schema::new();
+	schema.register_variable("speed", Type::Float);
+	schema.register_variable("count", Type::Int);
+	schema.register_variable("name", Type::String);
+	schema.register_variable("ready", Type::Bool);
+	schema.register_function("sensor", Type::Float);
+	schema
+}
+
+fn errors(script: &str) -> Vec<TypeErrorKind> {
+	errors_with(schema(), script)
+}
+
+fn errors_with(schema: Schema, script: &str) -> Vec<TypeErrorKind> {
+	let mut runtime = Runtime::default();
+	runtime.register_enum_tuple("Red", 1).unwrap();
+	runtime.set_schema(Some(schema));
+	runtime
+		.check(script)
+		.unwrap()
+		.into_iter()
+		.map(|error| error.kind)
+		.collect()
+}
+
+#[rstest]
+#[case("speed = speed * 2 + count; count += 1; name = name + count; ready = !ready;")]
+#[case("x := count > 1 ? 'many' : 'few'; y := x + `{speed}`; print y;")]
+#[case("ready && count == Red ? speed : count;")]
+#[case("count = count & 0x0f | ~count; speed = -speed; count = +count;")]
+#[case("speed = sensor(count as float); count = int(name); name = typeof(speed) + str(1);")]
+#[case("speed = 1.5; count = len(name); count = len(name) + 1.5; print nil == nil;")]
+#[case("x := 1; x += 2; y := x >= 1 && name != 'a' || false;")]
+fn valid(#[case] script: &str) {
+	assert_eq!(errors(script), vec![]);
+}
+
+#[rstest]
+#[case("'a' - 1;", "-", Some(Type::String), Type::Int)]
+#[case("true + 1;", "+", Some(Type::Bool), Type::Int)]
+#[case("nil * speed;", "*", Some(Type::Nil), Type::Float)]
+#[case("name > count;", ">", Some(Type::String), Type::Int)]
+#[case("speed <= ready;", "<=", Some(Type::Float), Type::Bool)]
+#[case("speed & 1;", "&", Some(Type::Float), Type::Int)]
+#[case("-name;", "-", None, Type::String)]
+#[case("~1.5;", "~", None, Type::Float)]
+#[case("name -= 'x';", "-=", Some(Type::String), Type::String)]
+fn operations(#[case] script: &str, #[case] op: &str, #[case] left: Option<Type>, #[case] right: Type) {
+	let errors = errors(script);
+	assert_eq!(errors.len(), 1);
+	let TypeErrorKind::Operation {
+		op: found,
+		left: found_left,
+		right: found_right,
+		..
+	} = &errors[0]
+	else {
+		unreachable!()
+	};
+	assert_eq!((*found, *found_left, *found_right), (op, left, right));
+}
+
+#[rstest]
+#[case("count ? 1 : 2;", Type::Int)]
+#[case("name && ready;", Type::String)]
+#[case("x := speed; x || true;", Type::Float)]
+fn conditions(#[case] script: &str, #[case] found: Type) {
+	assert_eq!(errors(script), vec![TypeErrorKind::Condition { found }]);
+}
+
+#[rstest]
+#[case("count = 1.5;", "count", Type::Int, Type::Float)]
+#[case("count += 0.5;", "count", Type::Int, Type::Float)]
+#[case("speed := 'fast';", "speed", Type::Float, Type::String)]
+#[case("x := 1; x = ready;", "x", Type::Int, Type::Bool)]
+fn assignments(#[case] script: &str, #[case] name: &str, #[case] expected: Type, #[case] found: Type) {
+	let mut schema = schema();
+	schema.set_fixed_types(true);
+	assert_eq!(
+		errors_with(schema, script),
+		vec![TypeErrorKind::Assignment {
+			name: name.into(),
+			expected,
+			found
+		}]
+	);
+}
+
+#[rstest]
+#[case("f := 1.0; f = 2; f - 'a';", vec![])]
+#[case("a := 1; a := 'x'; a - 1;", vec![])]
+#[case("count = 1.5; count += 0.5; speed := 'fast'; speed - 1;", vec![])]
+#[case(
+	"x := 1; x = 2; x - 'a';",
+	vec![TypeErrorKind::Operation { op: "-", left: Some(Type::Int), right: Type::String, reason: "to Strings you can only 'ADD' something".into() }]
+)]
+fn reassignments(#[case] script: &str, #[case] expected: Vec<TypeErrorKind>) {
+	// the VM accepts values of another type, so the variable's type is not known afterwards
+	assert_eq!(errors(script), expected);
+	// up to the last statement
+	let script = script
+		.trim_end_matches(';')
+		.rsplit_once(';')
+		.unwrap()
+		.0;
+	let mut env = DefaultEnvironment::default();
+	env.define_env("count", 1).unwrap();
+	env.define_env("speed", 1.5).unwrap();
+	assert!(Runtime::default().run(script, &mut env).is_ok());
+}
+
+#[test]
+fn unknown_variables() {
+	assert_eq!(
+		errors("print sped; cnt = 1; cnt += 1; x := 1; x;"),
+		vec![
+			TypeErrorKind::UnknownVariable { name: "sped".into() },
+			TypeErrorKind::UnknownVariable { name: "cnt".into() },
+			TypeErrorKind::UnknownVariable { name: "cnt".into() },
+		]
+	);
+}
+
+#[test]
+fn unknown_types_are_not_checked() {
+	let mut schema = Schema::new();
+	schema.register_variable("value", Type::Any);
+	let library = Library::default();
+	let checker = TypeChecker::new(&schema, &library);
+	let stmts = Runtime::default()
+		.parse_ast("value + 1; value = 'a'; max(1, 2) - 'a'; value > 1 ? 1 : 'one';")
+		.unwrap();
+	assert_eq!(checker.check(&stmts), vec![]);
+}
+
+#[test]
+fn compilation() {
+	let mut runtime = Runtime::default();
+	// without a schema the error happens at runtime
+	let chunk = runtime.parse("x := 'a' - 1;").unwrap();
+	let mut env = DefaultEnvironment::default();
+	assert!(runtime.execute(&chunk, &mut env).is_err());
+
+	runtime.set_schema(Some(schema()));
+	let Err(Error::Compilation { source }) = runtime.parse("x := 1;\ncount = 'a' - count;") else {
+		unreachable!()
+	};
+	let CompilationError::TypeCheck { source } = &source else {
+		unreachable!()
+	};
+	assert_eq!(source.span.line, 2);
+	assert_eq!(
+		source.to_string(),
+		"invalid operation string - int at line 2: to Strings you can only 'ADD' something"
+	);
+	assert!(runtime.parse("count = count + 1;").is_ok());
+
+	runtime.set_schema(None);
+	assert!(runtime.parse("count = 'a' - count;").is_ok());
+}